@@ -0,0 +1,77 @@
+//! Time of verifying a sync of Ed25519 signatures one by one, under both
+//! [`Ed25519Rules`], against a ZIP-215 [`KeyProvider::verify_batch`].
+//!
+//! ```sh
+//! cargo bench -p e2ee-provider --features aws_lc_rs,ed25519_batch
+//! ```
+
+use e2ee_provider::{
+    aws_lc_rs::AwsLcRs,
+    ec::{
+        KeyProvider,
+        encoding::PublicKeyBin,
+        signature::{Algorithm, BatchEntry, Ed25519Rules, Strictness},
+    },
+};
+use std::time::{Duration, Instant};
+
+const ROUNDS: u32 = 20;
+
+fn time(mut f: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        f();
+    }
+
+    start.elapsed() / ROUNDS
+}
+
+fn main() {
+    let zip215 = Strictness {
+        ed25519: Ed25519Rules::Zip215,
+        ..Strictness::default()
+    };
+
+    for len in [16, 64, 256, 1024] {
+        let messages: Vec<_> = (0..len).map(|i| (i as u32).to_be_bytes()).collect();
+        let (keys, signatures): (Vec<_>, Vec<_>) = messages
+            .iter()
+            .map(|message| {
+                let key = AwsLcRs::generate_signing_key(Algorithm::Ed25519).unwrap();
+                let public_key = key.compute_public_key().unwrap().as_be_bytes().unwrap();
+
+                (public_key.as_ref().to_vec(), key.sign(message).unwrap())
+            })
+            .unzip();
+        let entries: Vec<_> = (0..len)
+            .map(|i| BatchEntry {
+                algorithm: Algorithm::Ed25519,
+                public_key: &keys[i],
+                message: &messages[i],
+                signature: &signatures[i],
+            })
+            .collect();
+
+        let one_by_one = time(|| {
+            for entry in &entries {
+                let mut key = AwsLcRs::load_verifying_key_bin(
+                    entry.algorithm,
+                    &PublicKeyBin::Uncompreessed(entry.public_key.to_vec()),
+                )
+                .unwrap();
+                key.set_strictness(zip215).unwrap();
+                key.verify(entry.message, entry.signature).unwrap();
+            }
+        });
+        let strict =
+            time(|| assert!(AwsLcRs::verify_batch(&entries, Strictness::default()).is_valid()));
+        let batch = time(|| assert!(AwsLcRs::verify_batch(&entries, zip215).is_valid()));
+
+        println!(
+            "{len:>5} signatures: strict {strict:>10.2?}, ZIP-215 one by one {one_by_one:>10.2?}, \
+             ZIP-215 batch {batch:>10.2?} ({:.1}x, {:.1}x)",
+            strict.as_secs_f64() / batch.as_secs_f64(),
+            one_by_one.as_secs_f64() / batch.as_secs_f64(),
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use crate::Error;
+use alloc::vec::Vec;
+use zeroize::Zeroize;
+
+/// Supported authenticated encryption with associated data (AEAD)
+/// algorithms.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    /// AES-128 in Galois/Counter Mode, as specified in NIST SP 800-38D.
+    Aes128Gcm = 895,
+    /// AES-256 in Galois/Counter Mode, as specified in NIST SP 800-38D.
+    Aes256Gcm = 901,
+    /// ChaCha20-Poly1305, as specified in
+    /// [RFC 8439](https://datatracker.ietf.org/doc/html/rfc8439).
+    ChaCha20Poly1305 = 1018,
+}
+
+impl TryFrom<i32> for Algorithm {
+    type Error = Error;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            895 => Ok(Self::Aes128Gcm),
+            901 => Ok(Self::Aes256Gcm),
+            1018 => Ok(Self::ChaCha20Poly1305),
+            _ => Err(Error::UnsupportedAeadAlgorithm),
+        }
+    }
+}
+
+impl Algorithm {
+    /// Returns length of the key in bytes.
+    pub fn key_len(&self) -> usize {
+        match self {
+            Self::Aes128Gcm => 16,
+            Self::Aes256Gcm | Self::ChaCha20Poly1305 => 32,
+        }
+    }
+
+    /// Returns length of the nonce in bytes.
+    pub fn nonce_len(&self) -> usize {
+        12
+    }
+
+    /// Returns length of the authentication tag in bytes.
+    pub fn tag_len(&self) -> usize {
+        16
+    }
+}
+
+/// An AEAD algorithm.
+///
+/// Keys and nonces are passed on every call; callers are responsible for
+/// never reusing a nonce under the same key.
+pub trait Aead {
+    /// Encrypts and authenticates `plaintext` and authenticates `aad`.
+    ///
+    /// Returns the ciphertext with the tag appended.
+    fn seal(
+        &self,
+        key: &[u8],
+        nonce: &[u8],
+        aad: &[u8],
+        plaintext: &[u8],
+    ) -> Result<Vec<u8>, Error>;
+
+    /// Authenticates and decrypts `ciphertext` (with the tag appended).
+    ///
+    /// Returns [`Error::Unspecified`] if authentication fails.
+    fn open(
+        &self,
+        key: &[u8],
+        nonce: &[u8],
+        aad: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Plaintext, Error>;
+
+    /// Returns the algorithm of the AEAD.
+    fn algorithm(&self) -> Algorithm;
+}
+
+/// Plaintext recovered by [`Aead::open`].
+pub struct Plaintext {
+    pub(crate) buf: Vec<u8>,
+}
+
+impl Drop for Plaintext {
+    fn drop(&mut self) {
+        self.buf.zeroize();
+    }
+}
+
+impl AsRef<[u8]> for Plaintext {
+    fn as_ref(&self) -> &[u8] {
+        &self.buf
+    }
+}
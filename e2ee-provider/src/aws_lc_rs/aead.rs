@@ -0,0 +1,119 @@
+use crate::{
+    Error,
+    aead::{Aead, Algorithm, Plaintext},
+};
+use alloc::vec::Vec;
+use aws_lc_rs::aead;
+
+/// Any of the AEAD algorithms of aws-lc-rs, selected at runtime.
+pub struct AnyAead {
+    aead: &'static aead::Algorithm,
+    algorithm: Algorithm,
+}
+
+impl AnyAead {
+    /// Returns the AEAD algorithm, if supported.
+    pub fn get(algorithm: Algorithm) -> Option<&'static Self> {
+        match algorithm {
+            Algorithm::Aes128Gcm => Some(&AES_128_GCM),
+            Algorithm::Aes256Gcm => Some(&AES_256_GCM),
+            Algorithm::ChaCha20Poly1305 => Some(&CHACHA20_POLY1305),
+        }
+    }
+
+    /// All supported AEAD algorithms, in order of preference.
+    pub(super) const SUPPORTED: &'static [Algorithm] = &[
+        Algorithm::Aes256Gcm,
+        Algorithm::ChaCha20Poly1305,
+        Algorithm::Aes128Gcm,
+    ];
+}
+
+impl Aead for AnyAead {
+    fn seal(
+        &self,
+        key: &[u8],
+        nonce: &[u8],
+        aad: &[u8],
+        plaintext: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        let key = aead::LessSafeKey::new(aead::UnboundKey::new(self.aead, key)?);
+        let nonce = aead::Nonce::try_assume_unique_for_key(nonce)?;
+        let mut buf = plaintext.to_vec();
+
+        key.seal_in_place_append_tag(nonce, aead::Aad::from(aad), &mut buf)?;
+
+        Ok(buf)
+    }
+
+    fn open(
+        &self,
+        key: &[u8],
+        nonce: &[u8],
+        aad: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Plaintext, Error> {
+        let key = aead::LessSafeKey::new(aead::UnboundKey::new(self.aead, key)?);
+        let nonce = aead::Nonce::try_assume_unique_for_key(nonce)?;
+        let mut plaintext = Plaintext {
+            buf: ciphertext.to_vec(),
+        };
+
+        let len = key
+            .open_in_place(nonce, aead::Aad::from(aad), &mut plaintext.buf)?
+            .len();
+        plaintext.buf.truncate(len);
+
+        Ok(plaintext)
+    }
+
+    fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+}
+
+macro_rules! impl_aead_functions {
+    ($($alg:ident, $aws_lc_rs_alg:ident);*) => {
+        $(paste::paste! {
+            static $aws_lc_rs_alg: AnyAead = AnyAead {
+                aead: &aead::$aws_lc_rs_alg,
+                algorithm: Algorithm::$alg,
+            };
+
+            #[doc = "AEAD using " $alg "."]
+            pub struct $alg;
+
+            impl Aead for $alg {
+                fn seal(
+                    &self,
+                    key: &[u8],
+                    nonce: &[u8],
+                    aad: &[u8],
+                    plaintext: &[u8],
+                ) -> Result<Vec<u8>, Error> {
+                    $aws_lc_rs_alg.seal(key, nonce, aad, plaintext)
+                }
+
+                fn open(
+                    &self,
+                    key: &[u8],
+                    nonce: &[u8],
+                    aad: &[u8],
+                    ciphertext: &[u8],
+                ) -> Result<Plaintext, Error> {
+                    $aws_lc_rs_alg.open(key, nonce, aad, ciphertext)
+                }
+
+                fn algorithm(&self) -> Algorithm {
+                    Algorithm::$alg
+                }
+            }
+        })*
+    };
+}
+
+impl_aead_functions!(
+    Aes128Gcm, AES_128_GCM;
+    Aes256Gcm, AES_256_GCM;
+    ChaCha20Poly1305, CHACHA20_POLY1305
+);
@@ -1,81 +1,119 @@
-use super::AwsLcRs;
 use crate::{
-    digest::{Algorithm, Context, Digest, Hash},
-    provider::Provider,
+    digest::{Algorithm, Digest},
+    generic::{Context, Hash},
 };
-use alloc::boxed::Box;
 use aws_lc_rs::digest;
 
-impl Provider<Algorithm, &'static dyn Hash> for AwsLcRs {
-    fn get(&self, algorithm: Algorithm) -> Option<&'static dyn Hash> {
+/// Any of the hash functions of aws-lc-rs, selected at runtime.
+pub struct AnyDigest {
+    digest: &'static digest::Algorithm,
+    algorithm: Algorithm,
+}
+
+/// Incremental hash computation of an [`AnyDigest`].
+pub struct AnyDigestContext {
+    ctx: digest::Context,
+    algorithm: Algorithm,
+}
+
+impl AnyDigest {
+    /// Returns the hash function, if supported.
+    pub fn get(algorithm: Algorithm) -> Option<&'static Self> {
         match algorithm {
-            Algorithm::Sha224 => Some(&Sha224Digest),
-            Algorithm::Sha256 => Some(&Sha256Digest),
-            Algorithm::Sha384 => Some(&Sha384Digest),
-            Algorithm::Sha512 => Some(&Sha512Digest),
+            Algorithm::Sha224 => Some(&SHA224),
+            Algorithm::Sha256 => Some(&SHA256),
+            Algorithm::Sha384 => Some(&SHA384),
+            Algorithm::Sha512 => Some(&SHA512),
             Algorithm::Sha3_224 => None,
-            Algorithm::Sha3_256 => Some(&Sha3_256Digest),
-            Algorithm::Sha3_384 => Some(&Sha3_384Digest),
-            Algorithm::Sha3_512 => Some(&Sha3_512Digest),
+            Algorithm::Sha3_256 => Some(&SHA3_256),
+            Algorithm::Sha3_384 => Some(&SHA3_384),
+            Algorithm::Sha3_512 => Some(&SHA3_512),
+        }
+    }
+
+    /// All supported hash functions.
+    pub(super) const SUPPORTED: &'static [Algorithm] = &[
+        Algorithm::Sha224,
+        Algorithm::Sha256,
+        Algorithm::Sha384,
+        Algorithm::Sha512,
+        Algorithm::Sha3_256,
+        Algorithm::Sha3_384,
+        Algorithm::Sha3_512,
+    ];
+}
+
+/// Wraps a digest of aws-lc-rs, which is as long as its algorithm outputs.
+fn digest_of(algorithm: Algorithm, digest: digest::Digest) -> Digest {
+    Digest::new(algorithm, digest.as_ref()).expect("digest of the output length of its algorithm")
+}
+
+impl Hash for AnyDigest {
+    type Context = AnyDigestContext;
+
+    fn start(&self) -> AnyDigestContext {
+        AnyDigestContext {
+            ctx: digest::Context::new(self.digest),
+            algorithm: self.algorithm,
+        }
+    }
+
+    fn hash(&self, data: &[u8]) -> Digest {
+        digest_of(self.algorithm, digest::digest(self.digest, data))
+    }
+
+    fn hash_parts(&self, parts: &[&[u8]]) -> Digest {
+        let mut ctx = digest::Context::new(self.digest);
+        for part in parts {
+            ctx.update(part);
         }
+
+        digest_of(self.algorithm, ctx.finish())
     }
 
-    fn supported_algorithms(&self) -> &'static [Algorithm] {
-        &[
-            Algorithm::Sha224,
-            Algorithm::Sha256,
-            Algorithm::Sha384,
-            Algorithm::Sha512,
-            Algorithm::Sha3_256,
-            Algorithm::Sha3_384,
-            Algorithm::Sha3_512,
-        ]
+    fn algorithm(&self) -> Algorithm {
+        self.algorithm
     }
+}
 
-    fn is_algorithm_supported(&self, algorithm: Algorithm) -> bool {
-        algorithm != Algorithm::Sha3_224
+impl Context for AnyDigestContext {
+    fn update(&mut self, data: &[u8]) {
+        self.ctx.update(data);
+    }
+
+    fn finish(self) -> Digest {
+        digest_of(self.algorithm, self.ctx.finish())
+    }
+
+    fn algorithm(&self) -> Algorithm {
+        self.algorithm
     }
 }
 
 macro_rules! impl_hash_functions {
     ($($alg:ident),*) => {
         $(paste::paste! {
+            static [<$alg:upper>]: AnyDigest = AnyDigest {
+                digest: &digest::[<$alg:upper>],
+                algorithm: Algorithm::$alg,
+            };
+
             #[doc = "Hash digest using " $alg "."]
             pub struct [<$alg Digest>];
 
-            #[doc = "Incremental " $alg " hash computation."]
-            struct [<$alg Context>] {
-                ctx: digest::Context
-            }
-
             impl Hash for [<$alg Digest>] {
-                fn hash(&self, data: &[u8]) -> Digest {
-                    Digest {
-                        buf: digest::digest(&digest::[<$alg:upper>], data)
-                            .as_ref().into(),
-                    }
-                }
-
-                fn start(&self) -> Box<dyn Context> {
-                    Box::new([<$alg Context>] {
-                        ctx: digest::Context::new(&digest::[<$alg:upper>])
-                    })
-                }
+                type Context = AnyDigestContext;
 
-                fn algorithm(&self) -> Algorithm {
-                    Algorithm::$alg
+                fn start(&self) -> AnyDigestContext {
+                    [<$alg:upper>].start()
                 }
-            }
 
-            impl Context for [<$alg Context>] {
-                fn update(&mut self, data: &[u8]) {
-                    self.ctx.update(data);
+                fn hash(&self, data: &[u8]) -> Digest {
+                    [<$alg:upper>].hash(data)
                 }
 
-                fn finish(self: Box<Self>) -> Digest {
-                    Digest {
-                        buf: self.ctx.finish().as_ref().into()
-                    }
+                fn hash_parts(&self, parts: &[&[u8]]) -> Digest {
+                    [<$alg:upper>].hash_parts(parts)
                 }
 
                 fn algorithm(&self) -> Algorithm {
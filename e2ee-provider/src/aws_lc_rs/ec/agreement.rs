@@ -0,0 +1,298 @@
+use super::VerifyingKeySerializer;
+use crate::{
+    Error,
+    ec::{
+        agreement::{Algorithm, EphemeralPrivateKey, PrivateKey, PublicKey, SharedSecret},
+        encoding::{
+            PrivateKeyBin, PrivateKeyDer, PrivateKeySerializer, PublicKeyBin, PublicKeyDer,
+            PublicKeySerializer,
+        },
+    },
+};
+use alloc::{boxed::Box, vec::Vec};
+use aws_lc_rs::{
+    agreement::{self, ParsedPublicKeyFormat},
+    encoding::{AsBigEndian, AsDer, Curve25519SeedBin, EcPrivateKeyBin, EcPrivateKeyRfc5915Der},
+};
+use core::any::Any;
+
+/// Bytes of the peer public key, in a format aws-lc-rs can parse.
+fn peer_public_key_bytes(peer_public_key: Box<dyn PublicKey>) -> Result<Vec<u8>, Error> {
+    let serializer = peer_public_key.to_serializer();
+
+    if let Ok(bin) = serializer.as_be_bytes() {
+        return Ok(bin.as_ref().to_vec());
+    }
+
+    match serializer.as_compressed_be_bytes() {
+        Ok(bin) => Ok(bin.as_ref().to_vec()),
+        Err(_) => Ok(serializer.as_x509_der()?.as_ref().to_vec()),
+    }
+}
+
+/// Peer public key parsed by aws-lc-rs. Keys loaded by this backend were
+/// parsed and validated then, and are not parsed again.
+fn parsed_public_key(
+    algorithm: &'static agreement::Algorithm,
+    peer_public_key: Box<dyn PublicKey>,
+) -> Result<agreement::ParsedPublicKey, Error> {
+    let any: &dyn Any = peer_public_key.as_ref();
+    if let Some(key) = any.downcast_ref::<AgreementPublicKey>() {
+        return Ok(key.key.clone());
+    }
+
+    Ok(agreement::ParsedPublicKey::try_from(
+        agreement::UnparsedPublicKey::new(algorithm, peer_public_key_bytes(peer_public_key)?),
+    )?)
+}
+
+fn shared_secret(secret: &[u8]) -> Result<SharedSecret, Error> {
+    SharedSecret::new(secret)
+}
+
+fn agreement_algorithm(algorithm: Algorithm) -> Result<&'static agreement::Algorithm, Error> {
+    match algorithm {
+        Algorithm::EcdhP256 => Ok(&agreement::ECDH_P256),
+        Algorithm::EcdhP384 => Ok(&agreement::ECDH_P384),
+        Algorithm::EcdhP521 => Ok(&agreement::ECDH_P521),
+        Algorithm::X25519 => Ok(&agreement::X25519),
+        Algorithm::X448 => Err(Error::UnsupportedAgreementAlgorithm),
+    }
+}
+
+/// Private key for ECDH or X25519 key agreement.
+pub struct AgreementPrivateKey {
+    pub(super) key: agreement::PrivateKey,
+    pub(super) algorithm: Algorithm,
+}
+
+impl AgreementPrivateKey {
+    /// Loads a private key from PKCS #8 or RFC 5915 DER.
+    pub fn from_der(algorithm: Algorithm, der: &PrivateKeyDer) -> Result<Self, Error> {
+        let key = match der {
+            PrivateKeyDer::Pkcs8V1Key(key) | PrivateKeyDer::EcPrivateKey(key) => {
+                agreement::PrivateKey::from_private_key_der(agreement_algorithm(algorithm)?, key)?
+            }
+            _ => return Err(Error::UnsupportedEncoding),
+        };
+
+        Ok(Self { key, algorithm })
+    }
+
+    /// Loads a private key from big-endian bytes.
+    pub fn from_bin(algorithm: Algorithm, bin: &PrivateKeyBin) -> Result<Self, Error> {
+        let key = match (algorithm, bin) {
+            (Algorithm::X25519, PrivateKeyBin::EdEcSeed(key))
+            | (
+                Algorithm::EcdhP256 | Algorithm::EcdhP384 | Algorithm::EcdhP521,
+                PrivateKeyBin::Ec(key),
+            ) => agreement::PrivateKey::from_private_key(agreement_algorithm(algorithm)?, key)?,
+            _ => return Err(Error::UnsupportedEncoding),
+        };
+
+        Ok(Self { key, algorithm })
+    }
+}
+
+impl PrivateKey for AgreementPrivateKey {
+    fn agree(&self, peer_public_key: Box<dyn PublicKey>) -> Result<SharedSecret, Error> {
+        let peer_public_key = parsed_public_key(self.key.algorithm(), peer_public_key)?;
+
+        agreement::agree(
+            &self.key,
+            peer_public_key,
+            Error::Unspecified,
+            shared_secret,
+        )
+    }
+
+    fn compute_public_key(&self) -> Result<Box<dyn PublicKeySerializer>, Error> {
+        Ok(Box::new(VerifyingKeySerializer {
+            key: self.key.compute_public_key()?,
+        }))
+    }
+
+    fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    fn to_serializer(self: Box<Self>) -> Box<dyn PrivateKeySerializer> {
+        Box::new(AgreementPrivateKeySerializer { key: self.key })
+    }
+}
+
+/// Ephemeral private key for ECDH or X25519 key agreement.
+pub struct AgreementEphemeralPrivateKey {
+    pub(super) key: agreement::EphemeralPrivateKey,
+    pub(super) algorithm: Algorithm,
+}
+
+impl AgreementEphemeralPrivateKey {
+    /// Generates a new ephemeral private key.
+    pub fn generate(algorithm: Algorithm) -> Result<Self, Error> {
+        Ok(Self {
+            key: agreement::EphemeralPrivateKey::generate(
+                agreement_algorithm(algorithm)?,
+                &aws_lc_rs::rand::SystemRandom::new(),
+            )?,
+            algorithm,
+        })
+    }
+}
+
+impl EphemeralPrivateKey for AgreementEphemeralPrivateKey {
+    fn agree_ephemeral(
+        self: Box<Self>,
+        peer_public_key: Box<dyn PublicKey>,
+    ) -> Result<SharedSecret, Error> {
+        let peer_public_key = parsed_public_key(self.key.algorithm(), peer_public_key)?;
+
+        agreement::agree_ephemeral(self.key, peer_public_key, Error::Unspecified, shared_secret)
+    }
+
+    fn compute_public_key(&self) -> Result<Box<dyn PublicKeySerializer>, Error> {
+        Ok(Box::new(VerifyingKeySerializer {
+            key: self.key.compute_public_key()?,
+        }))
+    }
+
+    fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+}
+
+/// Validated public key for ECDH or X25519 key agreement.
+///
+/// Serializes only into the format it was loaded from.
+pub struct AgreementPublicKey {
+    pub(super) key: agreement::ParsedPublicKey,
+    pub(super) algorithm: Algorithm,
+}
+
+impl AgreementPublicKey {
+    /// Loads a public key from X.509 DER.
+    pub fn from_der(algorithm: Algorithm, der: &PublicKeyDer) -> Result<Self, Error> {
+        match der {
+            PublicKeyDer::X509Key(der) => Self::parse(algorithm, der),
+            PublicKeyDer::EcPublicKey(_) | PublicKeyDer::RsaPublicKey(_) => {
+                Err(Error::UnsupportedEncoding)
+            }
+        }
+    }
+
+    /// Loads a public key from big-endian bytes.
+    pub fn from_bin(algorithm: Algorithm, bin: &PublicKeyBin) -> Result<Self, Error> {
+        match bin {
+            PublicKeyBin::Compressed(bytes) | PublicKeyBin::Uncompreessed(bytes) => {
+                Self::parse(algorithm, bytes)
+            }
+        }
+    }
+
+    fn parse(algorithm: Algorithm, bytes: &[u8]) -> Result<Self, Error> {
+        let key = agreement::ParsedPublicKey::try_from(agreement::UnparsedPublicKey::new(
+            agreement_algorithm(algorithm)?,
+            bytes,
+        ))?;
+
+        Ok(Self { key, algorithm })
+    }
+}
+
+impl PublicKey for AgreementPublicKey {
+    fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    fn to_serializer(self: Box<Self>) -> Box<dyn PublicKeySerializer> {
+        Box::new(AgreementPublicKeySerializer { key: self.key })
+    }
+
+    fn try_clone(&self) -> Option<Box<dyn PublicKey>> {
+        Some(Box::new(Self {
+            key: self.key.clone(),
+            algorithm: self.algorithm,
+        }))
+    }
+}
+
+struct AgreementPublicKeySerializer {
+    key: agreement::ParsedPublicKey,
+}
+
+impl PublicKeySerializer for AgreementPublicKeySerializer {
+    fn as_x509_der(&self) -> Result<PublicKeyDer, Error> {
+        match self.key.format() {
+            ParsedPublicKeyFormat::X509 => Ok(PublicKeyDer::X509Key(self.key.as_ref().to_vec())),
+            _ => Err(Error::UnsupportedEncoding),
+        }
+    }
+
+    fn as_rfc_5915_public_key_der(&self) -> Result<PublicKeyDer, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_be_bytes(&self) -> Result<PublicKeyBin, Error> {
+        match self.key.format() {
+            ParsedPublicKeyFormat::Uncompressed | ParsedPublicKeyFormat::Raw => {
+                Ok(PublicKeyBin::Uncompreessed(self.key.as_ref().to_vec()))
+            }
+            _ => Err(Error::UnsupportedEncoding),
+        }
+    }
+
+    fn as_compressed_be_bytes(&self) -> Result<PublicKeyBin, Error> {
+        match self.key.format() {
+            ParsedPublicKeyFormat::Compressed => {
+                Ok(PublicKeyBin::Compressed(self.key.as_ref().to_vec()))
+            }
+            _ => Err(Error::UnsupportedEncoding),
+        }
+    }
+}
+
+struct AgreementPrivateKeySerializer {
+    key: agreement::PrivateKey,
+}
+
+impl PrivateKeySerializer for AgreementPrivateKeySerializer {
+    fn as_pkcs8v1_der(&self) -> Result<PrivateKeyDer, Error> {
+        Ok(PrivateKeyDer::Pkcs8V1Key(
+            AsDer::<aws_lc_rs::encoding::Pkcs8V1Der>::as_der(&self.key)?
+                .as_ref()
+                .to_vec(),
+        ))
+    }
+
+    fn as_pkcs8v2_der(&self) -> Result<PrivateKeyDer, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_rfc_5915_private_key_der(&self) -> Result<PrivateKeyDer, Error> {
+        Ok(PrivateKeyDer::EcPrivateKey(
+            AsDer::<EcPrivateKeyRfc5915Der>::as_der(&self.key)?
+                .as_ref()
+                .to_vec(),
+        ))
+    }
+
+    fn as_ed_ec_be_bytes(&self) -> Result<PrivateKeyBin, Error> {
+        Ok(PrivateKeyBin::EdEcSeed(
+            AsBigEndian::<Curve25519SeedBin>::as_be_bytes(&self.key)?
+                .as_ref()
+                .to_vec(),
+        ))
+    }
+
+    fn as_ec_be_bytes(&self) -> Result<PrivateKeyBin, Error> {
+        Ok(PrivateKeyBin::Ec(
+            AsBigEndian::<EcPrivateKeyBin>::as_be_bytes(&self.key)?
+                .as_ref()
+                .to_vec(),
+        ))
+    }
+
+    fn as_raw_bytes(&self) -> Result<PrivateKeyBin, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+}
@@ -0,0 +1,151 @@
+//! Ed25519 batches are checked by the pure-Rust `ed25519_batch` module;
+//! aws-lc-rs supplies the random coefficients.
+
+use crate::ec::signature::BatchEntry;
+
+#[cfg(feature = "ed25519_batch")]
+use crate::ed25519_batch::{COEFFICIENT_LEN, verify};
+#[cfg(feature = "ed25519_batch")]
+use alloc::vec;
+
+/// Whether all Ed25519 `entries` are valid. `false` leaves it to the caller
+/// to verify them one by one.
+#[cfg(feature = "ed25519_batch")]
+pub(super) fn verify_ed25519(entries: &[BatchEntry<'_>]) -> bool {
+    let mut random = vec![0; entries.len() * COEFFICIENT_LEN];
+
+    aws_lc_rs::rand::fill(&mut random).is_ok() && verify(entries, &random)
+}
+
+#[cfg(not(feature = "ed25519_batch"))]
+pub(super) fn verify_ed25519(_entries: &[BatchEntry<'_>]) -> bool {
+    false
+}
+
+#[test]
+#[cfg(all(test, feature = "ed25519_batch"))]
+fn test() -> Result<(), crate::Error> {
+    use crate::{
+        aws_lc_rs::AwsLcRs,
+        ec::{
+            KeyProvider,
+            encoding::PublicKeyBin,
+            signature::{Algorithm, Ed25519Rules, Strictness},
+        },
+    };
+    use alloc::vec::Vec;
+    use curve25519_dalek::{EdwardsPoint, Scalar, constants::EIGHT_TORSION};
+    use sha2::{Digest, Sha512};
+
+    fn algorithm(i: usize) -> Algorithm {
+        if i % 16 == 5 {
+            Algorithm::EcdsaP256Sha256Fixed
+        } else {
+            Algorithm::Ed25519
+        }
+    }
+
+    fn entries<'a>(
+        messages: &'a [[u8; 100]],
+        signatures: &'a [Vec<u8>],
+        keys: &'a [Vec<u8>],
+    ) -> Vec<BatchEntry<'a>> {
+        (0..messages.len())
+            .map(|i| BatchEntry {
+                algorithm: algorithm(i),
+                public_key: &keys[i],
+                message: &messages[i],
+                signature: &signatures[i],
+            })
+            .collect()
+    }
+
+    let messages: Vec<_> = (0..64u8).map(|i| [i; 100]).collect();
+    let mut keys = Vec::new();
+    let mut signatures = Vec::new();
+    for (i, message) in messages.iter().enumerate() {
+        let key = AwsLcRs::generate_signing_key(algorithm(i))?;
+
+        signatures.push(key.sign(message)?);
+        keys.push(match &key.compute_public_key()?.as_be_bytes()? {
+            PublicKeyBin::Compressed(bytes) | PublicKeyBin::Uncompreessed(bytes) => bytes.clone(),
+        });
+    }
+
+    let strict = Strictness::default();
+    let zip215 = Strictness {
+        ed25519: Ed25519Rules::Zip215,
+        ..strict
+    };
+
+    let batch = entries(&messages, &signatures, &keys);
+    assert!(verify_ed25519(
+        &batch
+            .iter()
+            .filter(|entry| entry.algorithm == Algorithm::Ed25519)
+            .copied()
+            .collect::<Vec<_>>()
+    ));
+    assert!(AwsLcRs::verify_batch(&batch, zip215).is_valid());
+    assert!(AwsLcRs::verify_batch(&batch, strict).is_valid());
+
+    signatures[3][10] ^= 1;
+    signatures[21][1] ^= 1;
+    keys[40] = [0; 32].to_vec();
+    keys[41] = keys[42].clone();
+
+    for strictness in [zip215, strict] {
+        let verification =
+            AwsLcRs::verify_batch(&entries(&messages, &signatures, &keys), strictness);
+        assert!(!verification.is_valid());
+        assert_eq!(verification.failed(), [3, 21, 40, 41]);
+    }
+
+    // Under the small-order identity key, `R = (sqrt(-1), 0)` of order 4
+    // with `S = 0` verifies only with the cofactored equation.
+    let mut identity = [0; 32];
+    identity[0] = 1;
+    let entry = BatchEntry {
+        algorithm: Algorithm::Ed25519,
+        public_key: &identity,
+        message: b"",
+        signature: &[0; 64],
+    };
+    assert!(verify_ed25519(&[entry]));
+    assert!(AwsLcRs::verify_batch(&[entry], zip215).is_valid());
+    assert_eq!(AwsLcRs::verify_batch(&[entry], strict).failed(), [0]);
+
+    // A signer adds a point of order 8 to `R`, which strict verification
+    // rejects and the batch equation cannot see.
+    let a = Scalar::from_bytes_mod_order([7; 32]);
+    let r = Scalar::from_bytes_mod_order([9; 32]);
+    let public_key = EdwardsPoint::mul_base(&a).compress().to_bytes();
+    let r_point = (EdwardsPoint::mul_base(&r) + EIGHT_TORSION[1])
+        .compress()
+        .to_bytes();
+    let k = Scalar::from_bytes_mod_order_wide(
+        &Sha512::new()
+            .chain_update(r_point)
+            .chain_update(public_key)
+            .chain_update(b"mixed order")
+            .finalize()
+            .into(),
+    );
+    let signature = [r_point, (r + k * a).to_bytes()].concat();
+
+    let entry = BatchEntry {
+        algorithm: Algorithm::Ed25519,
+        public_key: &public_key,
+        message: b"mixed order",
+        signature: &signature,
+    };
+    let mut batch = entries(&messages, &signatures, &keys);
+    batch.retain(|entry| entry.algorithm == Algorithm::Ed25519);
+    batch.truncate(3);
+    batch.push(entry);
+
+    assert!(AwsLcRs::verify_batch(&batch, zip215).is_valid());
+    assert_eq!(AwsLcRs::verify_batch(&batch, strict).failed(), [3]);
+
+    Ok(())
+}
@@ -1,9 +1,10 @@
-use super::VerifyingKeySerializer;
+use super::{VerifyingKeySerializer, import_digest};
 use crate::{
     Error,
+    digest::Digest,
     ec::{
         encoding::{PrivateKeyBin, PrivateKeyDer, PrivateKeySerializer, PublicKeySerializer},
-        signature::{Algorithm, SigningKey, VerifyingKey},
+        signature::{Algorithm, SigningKey, Strictness, VerifyingKey, is_low_s_ecdsa},
     },
 };
 use alloc::{boxed::Box, vec::Vec};
@@ -28,6 +29,14 @@ impl SigningKey for EcdsaSigningKey {
             .to_vec())
     }
 
+    fn sign_digest(&self, digest: &Digest) -> Result<Vec<u8>, Error> {
+        Ok(self
+            .key
+            .sign_digest(&import_digest(self.algorithm, digest)?)?
+            .as_ref()
+            .to_vec())
+    }
+
     fn compute_public_key(&self) -> Result<Box<dyn PublicKeySerializer>, Error> {
         Ok(Box::new(VerifyingKeySerializer {
             key: self.key.public_key().clone(),
@@ -47,11 +56,41 @@ impl SigningKey for EcdsaSigningKey {
 pub struct EcdsaVerifyingKey {
     pub(super) key: signature::ParsedPublicKey,
     pub(super) algorithm: Algorithm,
+    pub(super) low_s: bool,
+}
+
+impl EcdsaVerifyingKey {
+    fn check_s(&self, signature: &[u8]) -> Result<(), Error> {
+        if self.low_s && !is_low_s_ecdsa(self.algorithm, signature) {
+            Err(Error::NonCanonicalSignature)
+        } else {
+            Ok(())
+        }
+    }
 }
 
 impl VerifyingKey for EcdsaVerifyingKey {
-    fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
-        self.key.verify_sig(message, signature).is_ok()
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), Error> {
+        self.check_s(signature)?;
+
+        self.key
+            .verify_sig(message, signature)
+            .map_err(|_| Error::BadSignature)
+    }
+
+    fn verify_digest(&self, digest: &Digest, signature: &[u8]) -> Result<(), Error> {
+        let digest = import_digest(self.algorithm, digest)?;
+        self.check_s(signature)?;
+
+        self.key
+            .verify_digest_sig(&digest, signature)
+            .map_err(|_| Error::BadSignature)
+    }
+
+    fn set_strictness(&mut self, strictness: Strictness) -> Result<(), Error> {
+        self.low_s = strictness.ecdsa_low_s;
+
+        Ok(())
     }
 
     fn algorithm(&self) -> Algorithm {
@@ -101,4 +140,112 @@ impl PrivateKeySerializer for EcdsaSigningKeySerializer {
     fn as_ed_ec_be_bytes(&self) -> Result<PrivateKeyBin, Error> {
         Err(Error::UnsupportedEncoding)
     }
+
+    fn as_raw_bytes(&self) -> Result<PrivateKeyBin, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn test() -> Result<(), Error> {
+    use crate::{aws_lc_rs::AwsLcRs, ec::KeyProvider};
+    use alloc::vec;
+
+    // `n - s` with big-endian `n`.
+    fn negate(order: &[u8], s: &[u8]) -> Vec<u8> {
+        let mut negated = vec![0; s.len()];
+        let mut borrow = 0;
+        for i in (0..s.len()).rev() {
+            let difference = order[i] as i16 - s[i] as i16 - borrow;
+            (negated[i], borrow) = (difference.rem_euclid(256) as u8, (difference < 0) as i16);
+        }
+
+        negated
+    }
+
+    let order =
+        hex::decode("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551").unwrap();
+    let key = AwsLcRs::generate_signing_key(Algorithm::EcdsaP256Sha256Fixed)?;
+    let mut verifying_key = AwsLcRs::load_verifying_key_bin(
+        Algorithm::EcdsaP256Sha256Fixed,
+        &key.compute_public_key()?.as_be_bytes()?,
+    )?;
+
+    let signature = key.sign(b"low s")?;
+    let malleated = [&signature[..32], &negate(&order, &signature[32..])].concat();
+
+    verifying_key.verify(b"low s", &signature)?;
+    verifying_key.verify(b"low s", &malleated)?;
+    assert!(matches!(
+        verifying_key.verify(b"high s", &signature),
+        Err(Error::BadSignature)
+    ));
+
+    verifying_key.set_strictness(Strictness {
+        ecdsa_low_s: true,
+        ..Strictness::default()
+    })?;
+    let (low, high) = match verifying_key.verify(b"low s", &signature) {
+        Ok(()) => (signature, malleated),
+        Err(_) => (malleated, signature),
+    };
+
+    verifying_key.verify(b"low s", &low)?;
+    assert!(matches!(
+        verifying_key.verify(b"low s", &high),
+        Err(Error::NonCanonicalSignature)
+    ));
+
+    // The same holds for DER signatures.
+    let der = |signature: &[u8]| {
+        let integer = |bytes: &[u8]| {
+            let bytes = &bytes[bytes.iter().take_while(|&&byte| byte == 0).count()..];
+            let pad = bytes[0] >= 0x80;
+            [
+                &[0x02, bytes.len() as u8 + pad as u8][..],
+                &[0][..pad as usize],
+                bytes,
+            ]
+            .concat()
+        };
+        let sequence = [integer(&signature[..32]), integer(&signature[32..])].concat();
+
+        [&[0x30, sequence.len() as u8][..], &sequence].concat()
+    };
+    let mut verifying_key = AwsLcRs::load_verifying_key_bin(
+        Algorithm::EcdsaP256Sha256Asn1,
+        &key.compute_public_key()?.as_be_bytes()?,
+    )?;
+
+    verifying_key.verify(b"low s", &der(&high))?;
+    verifying_key.set_strictness(Strictness {
+        ecdsa_low_s: true,
+        ..Strictness::default()
+    })?;
+    verifying_key.verify(b"low s", &der(&low))?;
+    assert!(matches!(
+        verifying_key.verify(b"low s", &der(&high)),
+        Err(Error::NonCanonicalSignature)
+    ));
+
+    // Keys loaded from their big-endian scalar sign for the same public key.
+    let key = AwsLcRs::load_signing_key_bin(
+        Algorithm::EcdsaP256Sha256Fixed,
+        &key.to_serializer().as_ec_be_bytes()?,
+    )?;
+    AwsLcRs::load_verifying_key_bin(
+        Algorithm::EcdsaP256Sha256Fixed,
+        &key.compute_public_key()?.as_be_bytes()?,
+    )?
+    .verify(b"be bytes", &key.sign(b"be bytes")?)?;
+    assert!(matches!(
+        AwsLcRs::load_signing_key_bin(
+            Algorithm::EcdsaP384Sha384Fixed,
+            &PrivateKeyBin::Ec(vec![0; 48])
+        ),
+        Err(Error::KeyRejected)
+    ));
+
+    Ok(())
 }
@@ -3,7 +3,10 @@ use crate::{
     Error,
     ec::{
         encoding::{PrivateKeyBin, PrivateKeyDer, PrivateKeySerializer, PublicKeySerializer},
-        signature::{Algorithm, SigningKey, VerifyingKey},
+        signature::{
+            Algorithm, Ed25519Rules, SigningKey, Strictness, VerifyingKey,
+            is_canonical_ed25519_point, is_canonical_ed25519_scalar,
+        },
     },
 };
 use alloc::{boxed::Box, vec::Vec};
@@ -12,6 +15,9 @@ use aws_lc_rs::{
     signature::{self, KeyPair},
 };
 
+#[cfg(feature = "zip215")]
+use crate::zip215::verify as verify_zip215;
+
 /// Signing key for [`Ed25519`] digital signature algorithm.
 ///
 /// [`Ed25519`]: Algorithm::Ed25519
@@ -44,11 +50,43 @@ impl SigningKey for Ed25519SigningKey {
 /// [`Ed25519`]: Algorithm::Ed25519
 pub struct Ed25519VerifyingKey {
     pub(super) key: signature::ParsedPublicKey,
+    pub(super) rules: Ed25519Rules,
 }
 
 impl VerifyingKey for Ed25519VerifyingKey {
-    fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
-        self.key.verify_sig(message, signature).is_ok()
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), Error> {
+        // The key is either raw or in a `SubjectPublicKeyInfo`, which ends
+        // with the raw key.
+        let public_key = self.key.as_ref();
+        let public_key = &public_key[public_key.len().saturating_sub(32)..];
+
+        match self.rules {
+            Ed25519Rules::Strict => {
+                // aws-lc-rs accepts non-canonical public keys.
+                if !is_canonical_ed25519_point(public_key)
+                    || signature.len() == 64
+                        && (!is_canonical_ed25519_point(&signature[..32])
+                            || !is_canonical_ed25519_scalar(&signature[32..]))
+                {
+                    return Err(Error::NonCanonicalSignature);
+                }
+
+                self.key
+                    .verify_sig(message, signature)
+                    .map_err(|_| Error::BadSignature)
+            }
+            Ed25519Rules::Zip215 => verify_zip215(public_key, message, signature),
+        }
+    }
+
+    fn set_strictness(&mut self, strictness: Strictness) -> Result<(), Error> {
+        if strictness.ed25519 == Ed25519Rules::Zip215 && !cfg!(feature = "zip215") {
+            return Err(Error::UnsupportedSignatureAlgorithm);
+        }
+
+        self.rules = strictness.ed25519;
+
+        Ok(())
     }
 
     fn algorithm(&self) -> Algorithm {
@@ -60,6 +98,11 @@ impl VerifyingKey for Ed25519VerifyingKey {
     }
 }
 
+#[cfg(not(feature = "zip215"))]
+fn verify_zip215(_public_key: &[u8], _message: &[u8], _signature: &[u8]) -> Result<(), Error> {
+    Err(Error::UnsupportedSignatureAlgorithm)
+}
+
 struct Ed25519SigningKeySerializer {
     key: signature::Ed25519KeyPair,
 }
@@ -90,4 +133,105 @@ impl PrivateKeySerializer for Ed25519SigningKeySerializer {
             self.key.seed()?.as_be_bytes()?.as_ref().to_vec(),
         ))
     }
+
+    fn as_raw_bytes(&self) -> Result<PrivateKeyBin, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn test() -> Result<(), Error> {
+    use crate::{
+        aws_lc_rs::AwsLcRs,
+        ec::{
+            KeyProvider,
+            encoding::{PublicKeyBin, PublicKeyDer},
+        },
+    };
+
+    fn load(public_key: &str) -> Result<Box<dyn VerifyingKey>, Error> {
+        AwsLcRs::load_verifying_key_bin(
+            Algorithm::Ed25519,
+            &PublicKeyBin::Uncompreessed(hex::decode(public_key).unwrap()),
+        )
+    }
+
+    let zip215 = Strictness {
+        ed25519: Ed25519Rules::Zip215,
+        ..Strictness::default()
+    };
+
+    // RFC 8032, section 7.1, test 1, and the same signature with `S + L`.
+    let signature = hex::decode(
+        "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155\
+         5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+    )
+    .unwrap();
+    let mut malleated = signature.clone();
+    let mut carry = 0;
+    for (byte, order) in malleated[32..].iter_mut().zip([
+        0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde,
+        0x14, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x10,
+    ]) {
+        let sum = *byte as u16 + order + carry;
+        (*byte, carry) = (sum as u8, sum >> 8);
+    }
+
+    let mut key = load("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a")?;
+    key.verify(b"", &signature)?;
+    AwsLcRs::load_verifying_key_der(
+        Algorithm::Ed25519,
+        &PublicKeyDer::X509Key(
+            hex::decode(
+                "302a300506032b6570032100\
+                 d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+            )
+            .unwrap(),
+        ),
+    )?
+    .verify(b"", &signature)?;
+    assert!(matches!(
+        key.verify(b"", &malleated),
+        Err(Error::NonCanonicalSignature)
+    ));
+
+    // A small-order `R` with `S = 0` under the identity key, encoded
+    // canonically and as `y = p + 1`: only the cofactored ZIP-215 equation
+    // holds, and only ZIP-215 accepts the non-canonical key.
+    let small_order = hex::decode(
+        "c7176a703d4dd84fba3c0b760d10670f2a2053fa2c39ccc64ec7fd7792ac037a\
+         0000000000000000000000000000000000000000000000000000000000000000",
+    )
+    .unwrap();
+    let mut identity = load("0100000000000000000000000000000000000000000000000000000000000000")?;
+    let mut non_canonical =
+        load("eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f")?;
+
+    assert!(matches!(
+        identity.verify(b"zip215", &small_order),
+        Err(Error::BadSignature)
+    ));
+    assert!(matches!(
+        non_canonical.verify(b"zip215", &small_order),
+        Err(Error::NonCanonicalSignature)
+    ));
+
+    if cfg!(feature = "zip215") {
+        key.set_strictness(zip215)?;
+        identity.set_strictness(zip215)?;
+        non_canonical.set_strictness(zip215)?;
+
+        key.verify(b"", &signature)?;
+        assert!(matches!(
+            key.verify(b"", &malleated),
+            Err(Error::NonCanonicalSignature)
+        ));
+        identity.verify(b"zip215", &small_order)?;
+        non_canonical.verify(b"zip215", &small_order)?;
+    } else {
+        assert!(key.set_strictness(zip215).is_err());
+    }
+
+    Ok(())
 }
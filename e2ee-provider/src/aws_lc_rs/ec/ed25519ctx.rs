@@ -0,0 +1,97 @@
+//! Ed25519ctx and Ed25519ph keys are backed by the pure-Rust
+//! [`crate::ed25519ctx`] implementation, as aws-lc-rs only exposes pure
+//! Ed25519; aws-lc-rs only supplies the seeds of generated keys.
+
+use crate::{
+    Error,
+    ec::{
+        encoding::{PrivateKeyBin, PrivateKeyDer},
+        signature::{Algorithm, SigningKey, VerifyingKey},
+    },
+};
+use alloc::boxed::Box;
+
+#[cfg(feature = "ed25519ctx")]
+use crate::ed25519ctx::{Ed25519CtxSigningKey, Ed25519CtxVerifyingKey};
+
+#[cfg(feature = "ed25519ctx")]
+fn fill_random(buf: &mut [u8]) -> Result<(), Error> {
+    Ok(aws_lc_rs::rand::fill(buf)?)
+}
+
+#[cfg(feature = "ed25519ctx")]
+pub(super) fn load_signing_key_der(
+    algorithm: Algorithm,
+    der: &PrivateKeyDer,
+) -> Result<Box<dyn SigningKey>, Error> {
+    match der {
+        PrivateKeyDer::Pkcs8V1Key(der) => {
+            Ok(Box::new(Ed25519CtxSigningKey::from_pkcs8(algorithm, der)?))
+        }
+        _ => Err(Error::UnsupportedEncoding),
+    }
+}
+
+#[cfg(feature = "ed25519ctx")]
+pub(super) fn load_signing_key_bin(
+    algorithm: Algorithm,
+    bin: &PrivateKeyBin,
+) -> Result<Box<dyn SigningKey>, Error> {
+    match bin {
+        PrivateKeyBin::EdEcSeed(seed) => {
+            Ok(Box::new(Ed25519CtxSigningKey::from_seed(algorithm, seed)?))
+        }
+        _ => Err(Error::UnsupportedEncoding),
+    }
+}
+
+#[cfg(feature = "ed25519ctx")]
+pub(super) fn generate_signing_key(algorithm: Algorithm) -> Result<Box<dyn SigningKey>, Error> {
+    Ok(Box::new(Ed25519CtxSigningKey::generate(
+        algorithm,
+        fill_random,
+    )?))
+}
+
+#[cfg(feature = "ed25519ctx")]
+pub(super) fn load_verifying_key(
+    algorithm: Algorithm,
+    bytes: &[u8],
+    is_der: bool,
+) -> Result<Box<dyn VerifyingKey>, Error> {
+    Ok(Box::new(if is_der {
+        Ed25519CtxVerifyingKey::from_x509_der(algorithm, bytes)?
+    } else {
+        Ed25519CtxVerifyingKey::from_bytes(algorithm, bytes)?
+    }))
+}
+
+#[cfg(not(feature = "ed25519ctx"))]
+pub(super) fn load_signing_key_der(
+    _algorithm: Algorithm,
+    _der: &PrivateKeyDer,
+) -> Result<Box<dyn SigningKey>, Error> {
+    Err(Error::UnsupportedSignatureAlgorithm)
+}
+
+#[cfg(not(feature = "ed25519ctx"))]
+pub(super) fn load_signing_key_bin(
+    _algorithm: Algorithm,
+    _bin: &PrivateKeyBin,
+) -> Result<Box<dyn SigningKey>, Error> {
+    Err(Error::UnsupportedSignatureAlgorithm)
+}
+
+#[cfg(not(feature = "ed25519ctx"))]
+pub(super) fn generate_signing_key(_algorithm: Algorithm) -> Result<Box<dyn SigningKey>, Error> {
+    Err(Error::UnsupportedSignatureAlgorithm)
+}
+
+#[cfg(not(feature = "ed25519ctx"))]
+pub(super) fn load_verifying_key(
+    _algorithm: Algorithm,
+    _bytes: &[u8],
+    _is_der: bool,
+) -> Result<Box<dyn VerifyingKey>, Error> {
+    Err(Error::UnsupportedSignatureAlgorithm)
+}
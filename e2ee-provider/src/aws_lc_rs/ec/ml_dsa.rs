@@ -0,0 +1,191 @@
+use super::VerifyingKeySerializer;
+use crate::{
+    Error,
+    ec::{
+        encoding::{PrivateKeyBin, PrivateKeyDer, PrivateKeySerializer, PublicKeySerializer},
+        signature::{Algorithm, SigningKey, VerifyingKey},
+    },
+};
+use alloc::{boxed::Box, vec::Vec};
+use aws_lc_rs::{
+    encoding::{AsRawBytes, PqdsaPrivateKeyRaw},
+    signature::{self, KeyPair},
+};
+
+/// Signing key for [`ML-DSA`] digital signature algorithms.
+///
+/// [`ML-DSA`]: Algorithm::MlDsa65
+pub struct MlDsaSigningKey {
+    pub(super) key: signature::PqdsaKeyPair,
+    pub(super) algorithm: Algorithm,
+}
+
+impl SigningKey for MlDsaSigningKey {
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut signature = alloc::vec![0; self.key.algorithm().signature_len()];
+
+        let len = self.key.sign(message, &mut signature)?;
+        signature.truncate(len);
+
+        Ok(signature)
+    }
+
+    fn compute_public_key(&self) -> Result<Box<dyn PublicKeySerializer>, Error> {
+        Ok(Box::new(VerifyingKeySerializer {
+            key: self.key.public_key().clone(),
+        }))
+    }
+
+    fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    fn to_serializer(self: Box<Self>) -> Box<dyn PrivateKeySerializer> {
+        Box::new(MlDsaSigningKeySerializer { key: self.key })
+    }
+}
+
+/// [`ML-DSA`] digital signature algorithm verification key.
+///
+/// [`ML-DSA`]: Algorithm::MlDsa65
+pub struct MlDsaVerifyingKey {
+    pub(super) key: signature::ParsedPublicKey,
+    pub(super) algorithm: Algorithm,
+}
+
+impl VerifyingKey for MlDsaVerifyingKey {
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), Error> {
+        self.key
+            .verify_sig(message, signature)
+            .map_err(|_| Error::BadSignature)
+    }
+
+    fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    fn to_serializer(self: Box<Self>) -> Box<dyn PublicKeySerializer> {
+        Box::new(VerifyingKeySerializer { key: self.key })
+    }
+}
+
+struct MlDsaSigningKeySerializer {
+    key: signature::PqdsaKeyPair,
+}
+
+impl PrivateKeySerializer for MlDsaSigningKeySerializer {
+    fn as_pkcs8v1_der(&self) -> Result<PrivateKeyDer, Error> {
+        Ok(PrivateKeyDer::Pkcs8V1Key(
+            self.key.to_pkcs8v1()?.as_ref().to_vec(),
+        ))
+    }
+
+    fn as_pkcs8v2_der(&self) -> Result<PrivateKeyDer, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_rfc_5915_private_key_der(&self) -> Result<PrivateKeyDer, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_ed_ec_be_bytes(&self) -> Result<PrivateKeyBin, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_ec_be_bytes(&self) -> Result<PrivateKeyBin, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_raw_bytes(&self) -> Result<PrivateKeyBin, Error> {
+        Ok(PrivateKeyBin::Raw(
+            AsRawBytes::<PqdsaPrivateKeyRaw<'static>>::as_raw_bytes(&self.key.private_key())?
+                .as_ref()
+                .to_vec(),
+        ))
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn test() -> Result<(), Error> {
+    use crate::{
+        aws_lc_rs::AwsLcRs,
+        ec::{
+            KeyProvider,
+            encoding::{PublicKeyBin, PublicKeyDer},
+        },
+    };
+
+    // Public and private key lengths of FIPS 204, table 2.
+    for (algorithm, public_key_len, private_key_len, signature_len) in [
+        (Algorithm::MlDsa44, 1312, 2560, 2420),
+        (Algorithm::MlDsa65, 1952, 4032, 3309),
+        (Algorithm::MlDsa87, 2592, 4896, 4627),
+    ] {
+        let key = AwsLcRs::generate_signing_key(algorithm)?;
+        let signature = key.sign(b"message")?;
+        assert_eq!(signature.len(), signature_len);
+
+        let public_key = key.compute_public_key()?;
+        let raw = public_key.as_be_bytes()?;
+        let der = public_key.as_x509_der()?;
+        assert_eq!(raw.as_ref().len(), public_key_len);
+
+        for verifying_key in [
+            AwsLcRs::load_verifying_key_bin(algorithm, &raw)?,
+            AwsLcRs::load_verifying_key_der(algorithm, &der)?,
+        ] {
+            assert_eq!(verifying_key.algorithm(), algorithm);
+            verifying_key.verify(b"message", &signature)?;
+            assert!(matches!(
+                verifying_key.verify(b"other message", &signature),
+                Err(Error::BadSignature)
+            ));
+            assert!(matches!(
+                verifying_key.verify(b"message", &signature[1..]),
+                Err(Error::BadSignature)
+            ));
+        }
+
+        // Keys loaded back from PKCS #8 and from their raw encoding sign for
+        // the same public key.
+        let serializer = key.to_serializer();
+        let pkcs8 = serializer.as_pkcs8v1_der()?;
+        let raw_private_key = serializer.as_raw_bytes()?;
+        assert_eq!(raw_private_key.as_ref().len(), private_key_len);
+        assert!(serializer.as_ec_be_bytes().is_err());
+
+        for key in [
+            AwsLcRs::load_signing_key_der(algorithm, &pkcs8)?,
+            AwsLcRs::load_signing_key_bin(algorithm, &raw_private_key)?,
+        ] {
+            assert_eq!(
+                key.compute_public_key()?.as_be_bytes()?.as_ref(),
+                raw.as_ref()
+            );
+            AwsLcRs::load_verifying_key_bin(algorithm, &raw)?
+                .verify(b"reloaded", &key.sign(b"reloaded")?)?;
+        }
+
+        // Keys of another parameter set are rejected.
+        let other = match algorithm {
+            Algorithm::MlDsa44 => Algorithm::MlDsa65,
+            _ => Algorithm::MlDsa44,
+        };
+        assert!(AwsLcRs::load_verifying_key_bin(other, &raw).is_err());
+        assert!(AwsLcRs::load_signing_key_der(other, &pkcs8).is_err());
+        assert!(
+            AwsLcRs::load_verifying_key_der(algorithm, &PublicKeyDer::X509Key(alloc::vec![0; 8]))
+                .is_err()
+        );
+        assert!(
+            AwsLcRs::load_verifying_key_bin(
+                algorithm,
+                &PublicKeyBin::Uncompreessed(raw.as_ref()[1..].to_vec())
+            )
+            .is_err()
+        );
+    }
+
+    Ok(())
+}
@@ -1,6 +1,10 @@
 use crate::{
     Error,
-    ec::encoding::{PublicKeyBin, PublicKeyDer, PublicKeySerializer},
+    digest::{self, Digest},
+    ec::{
+        encoding::{PublicKeyBin, PublicKeyDer, PublicKeySerializer},
+        signature::Algorithm,
+    },
 };
 use alloc::borrow::ToOwned;
 use aws_lc_rs::encoding::AsDer;
@@ -11,13 +15,26 @@ pub mod ed25519;
 /// Elliptic curve digital signature algorithm using aws-lc-rs.
 pub mod ecdsa;
 
-mod provider;
+/// RSA digital signature algorithms using aws-lc-rs.
+pub mod rsa;
+
+/// Module-lattice-based digital signature algorithm using aws-lc-rs.
+pub mod ml_dsa;
+
+mod batch;
+
+mod ed25519ctx;
+
+mod rfc6979;
+
+mod slh_dsa;
 
-//  /// X25519 key agreement using aws-lc-rs.
-//  pub mod x25519;
+mod xeddsa;
 
-//  /// ECDH key agreement using aws-lc-rs.
-//  pub mod ecdh;
+/// ECDH and X25519 key agreement using aws-lc-rs.
+pub mod agreement;
+
+mod provider;
 
 struct VerifyingKeySerializer<T> {
     key: T,
@@ -45,3 +62,23 @@ where
         Err(Error::UnsupportedEncoding)
     }
 }
+
+/// Converts `digest` for aws-lc-rs, checking that it was computed with the
+/// hash function of `algorithm`.
+fn import_digest(
+    algorithm: Algorithm,
+    digest: &Digest,
+) -> Result<aws_lc_rs::digest::Digest, Error> {
+    let aws_lc_rs_alg = match algorithm.digest_algorithm() {
+        Some(alg) if alg != digest.algorithm() => return Err(Error::UnsupportedDigestFunction),
+        Some(digest::Algorithm::Sha256) => &aws_lc_rs::digest::SHA256,
+        Some(digest::Algorithm::Sha384) => &aws_lc_rs::digest::SHA384,
+        Some(digest::Algorithm::Sha512) => &aws_lc_rs::digest::SHA512,
+        _ => return Err(Error::UnsupportedSignatureAlgorithm),
+    };
+
+    Ok(aws_lc_rs::digest::Digest::import_less_safe(
+        digest.as_ref(),
+        aws_lc_rs_alg,
+    )?)
+}
@@ -2,62 +2,123 @@ use crate::{
     Error,
     aws_lc_rs::{AwsLcRs, ec::ed25519::Ed25519VerifyingKey},
     ec::{
-        KeyProvider, agreement,
+        Curve, KeyProvider, agreement,
         encoding::{PrivateKeyBin, PrivateKeyDer, PublicKeyBin, PublicKeyDer},
         signature,
     },
 };
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 use aws_lc_rs::signature::{
     ECDSA_P256_SHA256_ASN1, ECDSA_P256_SHA256_ASN1_SIGNING, ECDSA_P256_SHA256_FIXED,
     ECDSA_P256_SHA256_FIXED_SIGNING, ECDSA_P384_SHA384_ASN1, ECDSA_P384_SHA384_ASN1_SIGNING,
-    ECDSA_P384_SHA384_FIXED, ECDSA_P384_SHA384_FIXED_SIGNING, ED25519, VerificationAlgorithm,
+    ECDSA_P384_SHA384_FIXED, ECDSA_P384_SHA384_FIXED_SIGNING, ED25519, EcdsaSigningAlgorithm,
+    ML_DSA_44, ML_DSA_44_SIGNING, ML_DSA_65, ML_DSA_65_SIGNING, ML_DSA_87, ML_DSA_87_SIGNING,
+    PqdsaSigningAlgorithm, VerificationAlgorithm,
 };
 
 use super::{
+    agreement::{AgreementEphemeralPrivateKey, AgreementPrivateKey, AgreementPublicKey},
+    batch,
     ecdsa::{EcdsaSigningKey, EcdsaVerifyingKey},
     ed25519::Ed25519SigningKey,
+    ed25519ctx,
+    ml_dsa::{MlDsaSigningKey, MlDsaVerifyingKey},
+    rfc6979,
+    rsa::{RsaSigningKey, RsaVerifyingKey},
+    slh_dsa, xeddsa,
 };
 
+fn ecdsa_signing_algorithm(
+    algorithm: signature::Algorithm,
+) -> Result<&'static EcdsaSigningAlgorithm, Error> {
+    match algorithm {
+        signature::Algorithm::EcdsaP256Sha256Asn1 => Ok(&ECDSA_P256_SHA256_ASN1_SIGNING),
+        signature::Algorithm::EcdsaP256Sha256Fixed => Ok(&ECDSA_P256_SHA256_FIXED_SIGNING),
+        signature::Algorithm::EcdsaP384Sha384Asn1 => Ok(&ECDSA_P384_SHA384_ASN1_SIGNING),
+        signature::Algorithm::EcdsaP384Sha384Fixed => Ok(&ECDSA_P384_SHA384_FIXED_SIGNING),
+        _ => Err(Error::UnsupportedSignatureAlgorithm),
+    }
+}
+
+fn ml_dsa_signing_algorithm(
+    algorithm: signature::Algorithm,
+) -> Result<&'static PqdsaSigningAlgorithm, Error> {
+    match algorithm {
+        signature::Algorithm::MlDsa44 => Ok(&ML_DSA_44_SIGNING),
+        signature::Algorithm::MlDsa65 => Ok(&ML_DSA_65_SIGNING),
+        signature::Algorithm::MlDsa87 => Ok(&ML_DSA_87_SIGNING),
+        _ => Err(Error::UnsupportedSignatureAlgorithm),
+    }
+}
+
 impl KeyProvider for AwsLcRs {
     fn load_private_key_der(
-        _algorithm: agreement::Algorithm,
-        _der: &PrivateKeyDer,
+        algorithm: agreement::Algorithm,
+        der: &PrivateKeyDer,
     ) -> Result<Box<dyn agreement::PrivateKey>, Error> {
-        todo!()
+        Ok(Box::new(AgreementPrivateKey::from_der(algorithm, der)?))
     }
 
     fn load_private_key_bin(
-        _algorithm: agreement::Algorithm,
-        _bin: &PrivateKeyBin,
+        algorithm: agreement::Algorithm,
+        bin: &PrivateKeyBin,
     ) -> Result<Box<dyn agreement::PrivateKey>, Error> {
-        todo!()
+        Ok(Box::new(AgreementPrivateKey::from_bin(algorithm, bin)?))
     }
 
     fn load_public_key_der(
-        _algorithm: agreement::Algorithm,
-        _der: &PublicKeyDer,
+        algorithm: agreement::Algorithm,
+        der: &PublicKeyDer,
     ) -> Result<Box<dyn agreement::PublicKey>, Error> {
-        todo!()
+        Ok(Box::new(AgreementPublicKey::from_der(algorithm, der)?))
     }
 
     fn load_public_key_bin(
-        _algorithm: agreement::Algorithm,
-        _bin: &PublicKeyBin,
+        algorithm: agreement::Algorithm,
+        bin: &PublicKeyBin,
     ) -> Result<Box<dyn agreement::PublicKey>, Error> {
-        todo!()
+        Ok(Box::new(AgreementPublicKey::from_bin(algorithm, bin)?))
     }
 
     fn generate_ephemeral_private_key(
-        _algorithm: agreement::Algorithm,
+        algorithm: agreement::Algorithm,
     ) -> Result<Box<dyn agreement::EphemeralPrivateKey>, Error> {
-        todo!()
+        Ok(Box::new(AgreementEphemeralPrivateKey::generate(algorithm)?))
     }
 
     fn load_signing_key_der(
         algorithm: signature::Algorithm,
         der: &PrivateKeyDer,
     ) -> Result<Box<dyn signature::SigningKey>, Error> {
+        if algorithm.is_slh_dsa() {
+            return slh_dsa::load_signing_key_der(algorithm, der);
+        }
+
+        if algorithm == signature::Algorithm::XEdDsa25519 {
+            return xeddsa::load_signing_key_der(der);
+        }
+
+        if matches!(
+            algorithm,
+            signature::Algorithm::Ed25519Ctx | signature::Algorithm::Ed25519Ph
+        ) {
+            return ed25519ctx::load_signing_key_der(algorithm, der);
+        }
+
+        if algorithm.is_rsa() {
+            let key = match der {
+                PrivateKeyDer::Pkcs8V1Key(key) => {
+                    aws_lc_rs::signature::RsaKeyPair::from_pkcs8(key)?
+                }
+                PrivateKeyDer::RsaPrivateKey(key) => {
+                    aws_lc_rs::signature::RsaKeyPair::from_der(key)?
+                }
+                _ => return Err(Error::UnsupportedEncoding),
+            };
+
+            return Ok(Box::new(RsaSigningKey::new(key, algorithm)?));
+        }
+
         match algorithm {
             signature::Algorithm::Ed448 => return Err(Error::UnsupportedSignatureAlgorithm),
             signature::Algorithm::Ed25519 => {
@@ -70,35 +131,62 @@ impl KeyProvider for AwsLcRs {
                     },
                 }));
             }
+            signature::Algorithm::MlDsa44
+            | signature::Algorithm::MlDsa65
+            | signature::Algorithm::MlDsa87 => {
+                return Ok(Box::new(MlDsaSigningKey {
+                    key: match der {
+                        PrivateKeyDer::Pkcs8V1Key(key) => {
+                            aws_lc_rs::signature::PqdsaKeyPair::from_pkcs8(
+                                ml_dsa_signing_algorithm(algorithm)?,
+                                key,
+                            )?
+                        }
+                        _ => return Err(Error::UnsupportedEncoding),
+                    },
+                    algorithm,
+                }));
+            }
             _ => {}
         };
 
-        let aws_lc_rs_alg = match algorithm {
-            signature::Algorithm::EcdsaP256Sha256Asn1 => &ECDSA_P256_SHA256_ASN1_SIGNING,
-            signature::Algorithm::EcdsaP256Sha256Fixed => &ECDSA_P256_SHA256_FIXED_SIGNING,
-            signature::Algorithm::EcdsaP384Sha384Asn1 => &ECDSA_P384_SHA384_ASN1_SIGNING,
-            signature::Algorithm::EcdsaP384Sha384Fixed => &ECDSA_P384_SHA384_FIXED_SIGNING,
-            _ => unreachable!(),
-        };
+        Ok(Box::new(load_ecdsa_signing_key_der(algorithm, der)?))
+    }
 
-        Ok(Box::new(EcdsaSigningKey {
-            key: match der {
-                PrivateKeyDer::Pkcs8V1Key(key) => {
-                    aws_lc_rs::signature::EcdsaKeyPair::from_pkcs8(aws_lc_rs_alg, key)?
-                }
-                PrivateKeyDer::EcPrivateKey(key) => {
-                    aws_lc_rs::signature::EcdsaKeyPair::from_private_key_der(aws_lc_rs_alg, key)?
-                }
-                _ => return Err(Error::UnsupportedEncoding),
-            },
-            algorithm,
-        }))
+    fn load_deterministic_signing_key_der(
+        algorithm: signature::Algorithm,
+        der: &PrivateKeyDer,
+    ) -> Result<Box<dyn signature::SigningKey>, Error> {
+        if !matches!(algorithm.curve(), Some(Curve::P256 | Curve::P384)) {
+            return Err(Error::UnsupportedSignatureAlgorithm);
+        }
+
+        rfc6979::load_signing_key(load_ecdsa_signing_key_der(algorithm, der)?)
     }
 
     fn load_signing_key_bin(
         algorithm: signature::Algorithm,
         bin: &PrivateKeyBin,
     ) -> Result<Box<dyn signature::SigningKey>, Error> {
+        if algorithm.is_slh_dsa() {
+            return slh_dsa::load_signing_key_bin(algorithm, bin);
+        }
+
+        if algorithm == signature::Algorithm::XEdDsa25519 {
+            return xeddsa::load_signing_key_bin(bin);
+        }
+
+        if matches!(
+            algorithm,
+            signature::Algorithm::Ed25519Ctx | signature::Algorithm::Ed25519Ph
+        ) {
+            return ed25519ctx::load_signing_key_bin(algorithm, bin);
+        }
+
+        if algorithm.is_rsa() {
+            return Err(Error::UnsupportedEncoding);
+        }
+
         match algorithm {
             signature::Algorithm::Ed448 => return Err(Error::UnsupportedSignatureAlgorithm),
             signature::Algorithm::Ed25519 => {
@@ -111,15 +199,96 @@ impl KeyProvider for AwsLcRs {
                     },
                 }));
             }
+            signature::Algorithm::MlDsa44
+            | signature::Algorithm::MlDsa65
+            | signature::Algorithm::MlDsa87 => {
+                return Ok(Box::new(MlDsaSigningKey {
+                    key: match bin {
+                        PrivateKeyBin::Raw(key) => {
+                            aws_lc_rs::signature::PqdsaKeyPair::from_raw_private_key(
+                                ml_dsa_signing_algorithm(algorithm)?,
+                                key,
+                            )?
+                        }
+                        _ => return Err(Error::UnsupportedEncoding),
+                    },
+                    algorithm,
+                }));
+            }
             _ => {}
         };
 
-        let _be_bytes = match bin {
+        let be_bytes = match bin {
             PrivateKeyBin::Ec(be_bytes) => be_bytes,
             _ => return Err(Error::UnsupportedEncoding),
         };
 
-        todo!("implement loading signing keys from big-endian bytes")
+        // aws-lc-rs needs the public point along with the scalar; ECDH on
+        // the same curve computes it.
+        let public_key = aws_lc_rs::agreement::PrivateKey::from_private_key(
+            match algorithm.curve() {
+                Some(Curve::P256) => &aws_lc_rs::agreement::ECDH_P256,
+                Some(Curve::P384) => &aws_lc_rs::agreement::ECDH_P384,
+                _ => return Err(Error::UnsupportedSignatureAlgorithm),
+            },
+            be_bytes,
+        )?
+        .compute_public_key()?;
+
+        Ok(Box::new(EcdsaSigningKey {
+            key: aws_lc_rs::signature::EcdsaKeyPair::from_private_key_and_public_key(
+                ecdsa_signing_algorithm(algorithm)?,
+                be_bytes,
+                public_key.as_ref(),
+            )?,
+            algorithm,
+        }))
+    }
+
+    fn generate_signing_key(
+        algorithm: signature::Algorithm,
+    ) -> Result<Box<dyn signature::SigningKey>, Error> {
+        if algorithm.is_slh_dsa() {
+            return slh_dsa::generate_signing_key(algorithm);
+        }
+
+        if algorithm == signature::Algorithm::XEdDsa25519 {
+            return xeddsa::generate_signing_key();
+        }
+
+        if matches!(
+            algorithm,
+            signature::Algorithm::Ed25519Ctx | signature::Algorithm::Ed25519Ph
+        ) {
+            return ed25519ctx::generate_signing_key(algorithm);
+        }
+
+        if algorithm.is_rsa() {
+            let key = aws_lc_rs::signature::RsaKeyPair::generate(aws_lc_rs::rsa::KeySize::Rsa3072)?;
+
+            return Ok(Box::new(RsaSigningKey::new(key, algorithm)?));
+        }
+
+        Ok(match algorithm {
+            signature::Algorithm::Ed448 => return Err(Error::UnsupportedSignatureAlgorithm),
+            signature::Algorithm::Ed25519 => Box::new(Ed25519SigningKey {
+                key: aws_lc_rs::signature::Ed25519KeyPair::generate()?,
+            }),
+            signature::Algorithm::MlDsa44
+            | signature::Algorithm::MlDsa65
+            | signature::Algorithm::MlDsa87 => Box::new(MlDsaSigningKey {
+                key: aws_lc_rs::signature::PqdsaKeyPair::generate(ml_dsa_signing_algorithm(
+                    algorithm,
+                )?)?,
+                algorithm,
+            }),
+            _ => Box::new(EcdsaSigningKey {
+                key: aws_lc_rs::signature::EcdsaKeyPair::generate(ecdsa_signing_algorithm(
+                    algorithm,
+                )?)?,
+                algorithm,
+            }),
+        })
     }
 
     fn load_verifying_key_der(
@@ -127,9 +296,30 @@ impl KeyProvider for AwsLcRs {
         der: &PublicKeyDer,
     ) -> Result<Box<dyn signature::VerifyingKey>, Error> {
         let der = match der {
-            PublicKeyDer::X509Key(der) | PublicKeyDer::EcPublicKey(der) => der,
+            PublicKeyDer::X509Key(der)
+            | PublicKeyDer::EcPublicKey(der)
+            | PublicKeyDer::RsaPublicKey(der) => der,
         };
 
+        if algorithm.is_slh_dsa() {
+            return slh_dsa::load_verifying_key(algorithm, der, true);
+        }
+
+        if algorithm == signature::Algorithm::XEdDsa25519 {
+            return xeddsa::load_verifying_key(der, true);
+        }
+
+        if matches!(
+            algorithm,
+            signature::Algorithm::Ed25519Ctx | signature::Algorithm::Ed25519Ph
+        ) {
+            return ed25519ctx::load_verifying_key(algorithm, der, true);
+        }
+
+        if algorithm.is_rsa() {
+            return Ok(Box::new(RsaVerifyingKey::from_der(algorithm, der)?));
+        }
+
         load_verifying_key(algorithm, der)
     }
 
@@ -141,35 +331,131 @@ impl KeyProvider for AwsLcRs {
             PublicKeyBin::Compressed(bytes) | PublicKeyBin::Uncompreessed(bytes) => bytes,
         };
 
+        if algorithm.is_slh_dsa() {
+            return slh_dsa::load_verifying_key(algorithm, bytes, false);
+        }
+
+        if algorithm == signature::Algorithm::XEdDsa25519 {
+            return xeddsa::load_verifying_key(bytes, false);
+        }
+
+        if matches!(
+            algorithm,
+            signature::Algorithm::Ed25519Ctx | signature::Algorithm::Ed25519Ph
+        ) {
+            return ed25519ctx::load_verifying_key(algorithm, bytes, false);
+        }
+
+        if algorithm.is_rsa() {
+            return Err(Error::UnsupportedEncoding);
+        }
+
         load_verifying_key(algorithm, bytes)
     }
+
+    fn verify_batch(
+        entries: &[signature::BatchEntry<'_>],
+        strictness: signature::Strictness,
+    ) -> signature::BatchVerification {
+        let batched = |entry: &signature::BatchEntry<'_>| {
+            entry.algorithm == signature::Algorithm::Ed25519
+                && strictness.ed25519 == signature::Ed25519Rules::Zip215
+        };
+
+        let ed25519: Vec<_> = entries
+            .iter()
+            .filter(|entry| batched(entry))
+            .copied()
+            .collect();
+        let ed25519_valid = !ed25519.is_empty() && batch::verify_ed25519(&ed25519);
+
+        let failed = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| {
+                if ed25519_valid && batched(entry) {
+                    return false;
+                }
+
+                let key = if entry.algorithm.is_rsa() {
+                    Self::load_verifying_key_der(
+                        entry.algorithm,
+                        &PublicKeyDer::X509Key(entry.public_key.to_vec()),
+                    )
+                } else {
+                    Self::load_verifying_key_bin(
+                        entry.algorithm,
+                        &PublicKeyBin::Uncompreessed(entry.public_key.to_vec()),
+                    )
+                };
+
+                !key.is_ok_and(|mut key| {
+                    key.set_strictness(strictness).is_ok()
+                        && key.verify(entry.message, entry.signature).is_ok()
+                })
+            })
+            .map(|(i, _)| i)
+            .collect();
+
+        signature::BatchVerification { failed }
+    }
+}
+
+fn load_ecdsa_signing_key_der(
+    algorithm: signature::Algorithm,
+    der: &PrivateKeyDer,
+) -> Result<EcdsaSigningKey, Error> {
+    let aws_lc_rs_alg = ecdsa_signing_algorithm(algorithm)?;
+
+    Ok(EcdsaSigningKey {
+        key: match der {
+            PrivateKeyDer::Pkcs8V1Key(key) => {
+                aws_lc_rs::signature::EcdsaKeyPair::from_pkcs8(aws_lc_rs_alg, key)?
+            }
+            PrivateKeyDer::EcPrivateKey(key) => {
+                aws_lc_rs::signature::EcdsaKeyPair::from_private_key_der(aws_lc_rs_alg, key)?
+            }
+            _ => return Err(Error::UnsupportedEncoding),
+        },
+        algorithm,
+    })
 }
 
 fn load_verifying_key(
     algorithm: signature::Algorithm,
     bytes: &[u8],
 ) -> Result<Box<dyn signature::VerifyingKey>, Error> {
-    if matches!(algorithm, signature::Algorithm::Ed448) {
-        return Err(Error::UnsupportedSignatureAlgorithm);
-    }
-
     let aws_lc_rs_alg: &dyn VerificationAlgorithm = match algorithm {
-        signature::Algorithm::Ed448 => unreachable!(),
         signature::Algorithm::Ed25519 => &ED25519,
         signature::Algorithm::EcdsaP256Sha256Asn1 => &ECDSA_P256_SHA256_ASN1,
         signature::Algorithm::EcdsaP256Sha256Fixed => &ECDSA_P256_SHA256_FIXED,
         signature::Algorithm::EcdsaP384Sha384Asn1 => &ECDSA_P384_SHA384_ASN1,
         signature::Algorithm::EcdsaP384Sha384Fixed => &ECDSA_P384_SHA384_FIXED,
+        signature::Algorithm::MlDsa44 => &ML_DSA_44,
+        signature::Algorithm::MlDsa65 => &ML_DSA_65,
+        signature::Algorithm::MlDsa87 => &ML_DSA_87,
+        _ => return Err(Error::UnsupportedSignatureAlgorithm),
     };
 
     let public_key = aws_lc_rs::signature::ParsedPublicKey::new(aws_lc_rs_alg, bytes)?;
+    // Keys verify with the default rules until `set_strictness` is called.
+    let strictness = signature::Strictness::default();
 
-    if matches!(algorithm, signature::Algorithm::Ed25519) {
-        Ok(Box::new(Ed25519VerifyingKey { key: public_key }))
-    } else {
-        Ok(Box::new(EcdsaVerifyingKey {
+    match algorithm {
+        signature::Algorithm::Ed25519 => Ok(Box::new(Ed25519VerifyingKey {
+            key: public_key,
+            rules: strictness.ed25519,
+        })),
+        signature::Algorithm::MlDsa44
+        | signature::Algorithm::MlDsa65
+        | signature::Algorithm::MlDsa87 => Ok(Box::new(MlDsaVerifyingKey {
             key: public_key,
             algorithm,
-        }))
+        })),
+        _ => Ok(Box::new(EcdsaVerifyingKey {
+            key: public_key,
+            algorithm,
+            low_s: strictness.ecdsa_low_s,
+        })),
     }
 }
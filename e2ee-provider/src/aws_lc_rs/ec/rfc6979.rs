@@ -0,0 +1,212 @@
+//! Deterministic ECDSA (RFC 6979) keys sign with the pure-Rust p256 and p384
+//! crates, since aws-lc-rs always draws its nonces from the system RNG. The
+//! aws-lc-rs key is kept for public key computation and serialization.
+
+use super::ecdsa::EcdsaSigningKey;
+use crate::{Error, ec::signature::SigningKey};
+use alloc::boxed::Box;
+
+#[cfg(feature = "rfc6979")]
+use super::import_digest;
+#[cfg(feature = "rfc6979")]
+use crate::{
+    digest::Digest,
+    ec::{
+        encoding::{PrivateKeySerializer, PublicKeySerializer},
+        signature::Algorithm,
+    },
+};
+#[cfg(feature = "rfc6979")]
+use alloc::vec::Vec;
+#[cfg(feature = "rfc6979")]
+use aws_lc_rs::encoding::{AsBigEndian, EcPrivateKeyBin};
+#[cfg(feature = "rfc6979")]
+use p256::ecdsa::signature::hazmat::PrehashSigner;
+
+#[cfg(feature = "rfc6979")]
+enum NonceKey {
+    P256(p256::ecdsa::SigningKey),
+    P384(p384::ecdsa::SigningKey),
+}
+
+/// ECDSA signing key deriving its nonces from the private key and the message
+/// digest, as specified in RFC 6979.
+#[cfg(feature = "rfc6979")]
+struct DeterministicEcdsaSigningKey {
+    key: EcdsaSigningKey,
+    nonce_key: NonceKey,
+}
+
+#[cfg(feature = "rfc6979")]
+impl SigningKey for DeterministicEcdsaSigningKey {
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        let digest = match self.key.algorithm.digest_algorithm() {
+            Some(crate::digest::Algorithm::Sha256) => &aws_lc_rs::digest::SHA256,
+            _ => &aws_lc_rs::digest::SHA384,
+        };
+
+        self.sign_prehash(aws_lc_rs::digest::digest(digest, message).as_ref())
+    }
+
+    fn sign_digest(&self, digest: &Digest) -> Result<Vec<u8>, Error> {
+        self.sign_prehash(import_digest(self.key.algorithm, digest)?.as_ref())
+    }
+
+    fn compute_public_key(&self) -> Result<Box<dyn PublicKeySerializer>, Error> {
+        self.key.compute_public_key()
+    }
+
+    fn algorithm(&self) -> Algorithm {
+        self.key.algorithm
+    }
+
+    fn to_serializer(self: Box<Self>) -> Box<dyn PrivateKeySerializer> {
+        Box::new(self.key).to_serializer()
+    }
+}
+
+#[cfg(feature = "rfc6979")]
+impl DeterministicEcdsaSigningKey {
+    fn sign_prehash(&self, prehash: &[u8]) -> Result<Vec<u8>, Error> {
+        let fixed = matches!(
+            self.key.algorithm,
+            Algorithm::EcdsaP256Sha256Fixed | Algorithm::EcdsaP384Sha384Fixed
+        );
+
+        Ok(match &self.nonce_key {
+            NonceKey::P256(key) => {
+                let signature: p256::ecdsa::Signature =
+                    key.sign_prehash(prehash).map_err(|_| Error::Unspecified)?;
+
+                if fixed {
+                    signature.to_bytes().to_vec()
+                } else {
+                    signature.to_der().as_bytes().to_vec()
+                }
+            }
+            NonceKey::P384(key) => {
+                let signature: p384::ecdsa::Signature =
+                    key.sign_prehash(prehash).map_err(|_| Error::Unspecified)?;
+
+                if fixed {
+                    signature.to_bytes().to_vec()
+                } else {
+                    signature.to_der().as_bytes().to_vec()
+                }
+            }
+        })
+    }
+}
+
+#[cfg(feature = "rfc6979")]
+pub(super) fn load_signing_key(key: EcdsaSigningKey) -> Result<Box<dyn SigningKey>, Error> {
+    let scalar = AsBigEndian::<EcPrivateKeyBin<'static>>::as_be_bytes(&key.key.private_key())?;
+
+    let nonce_key = match key.algorithm.digest_algorithm() {
+        Some(crate::digest::Algorithm::Sha256) => NonceKey::P256(
+            p256::ecdsa::SigningKey::from_slice(scalar.as_ref()).map_err(|_| Error::KeyRejected)?,
+        ),
+        Some(crate::digest::Algorithm::Sha384) => NonceKey::P384(
+            p384::ecdsa::SigningKey::from_slice(scalar.as_ref()).map_err(|_| Error::KeyRejected)?,
+        ),
+        _ => return Err(Error::UnsupportedSignatureAlgorithm),
+    };
+
+    Ok(Box::new(DeterministicEcdsaSigningKey { key, nonce_key }))
+}
+
+#[cfg(not(feature = "rfc6979"))]
+pub(super) fn load_signing_key(_key: EcdsaSigningKey) -> Result<Box<dyn SigningKey>, Error> {
+    Err(Error::UnsupportedSignatureAlgorithm)
+}
+
+#[test]
+#[cfg(all(test, feature = "rfc6979"))]
+fn test() -> Result<(), Error> {
+    use crate::{
+        aws_lc_rs::{
+            AwsLcRs,
+            digest::{Sha256Digest, Sha384Digest},
+        },
+        digest::Hash,
+        ec::{KeyProvider, encoding::PrivateKeyDer},
+    };
+
+    // RFC 5915 ECPrivateKey without the optional public key.
+    fn ec_private_key(oid: &[u8], scalar: &str) -> PrivateKeyDer {
+        let scalar = hex::decode(scalar).unwrap();
+        let mut der = [&[0x02, 0x01, 0x01, 0x04, scalar.len() as u8], &scalar[..]].concat();
+        der.extend([&[0xa0, oid.len() as u8 + 2, 0x06, oid.len() as u8], oid].concat());
+
+        PrivateKeyDer::EcPrivateKey([&[0x30, der.len() as u8], &der[..]].concat())
+    }
+
+    let p256 = ec_private_key(
+        &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07],
+        "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721",
+    );
+    let p384 = ec_private_key(
+        &[0x2b, 0x81, 0x04, 0x00, 0x22],
+        "6b9d3dad2e1b8c1c05b19875b6659f4de23c3b667bf297ba9aa47740787137d8\
+         96d5724e4c70a825f872c9ea60d2edf5",
+    );
+
+    // RFC 6979, sections A.2.5 and A.2.6, message "sample".
+    let key = AwsLcRs::load_deterministic_signing_key_der(Algorithm::EcdsaP256Sha256Fixed, &p256)?;
+    assert_eq!(
+        key.sign(b"sample")?,
+        hex::decode(
+            "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716\
+             f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"
+        )
+        .unwrap()
+    );
+
+    let key = AwsLcRs::load_deterministic_signing_key_der(Algorithm::EcdsaP384Sha384Fixed, &p384)?;
+    assert_eq!(
+        key.sign(b"sample")?,
+        hex::decode(
+            "94edbb92a5ecb8aad4736e56c691916b3f88140666ce9fa73d64c4ea95ad133c\
+             81a648152e44acf96e36dd1e80fabe46\
+             99ef4aeb15f178cea1fe40db2603138f130e740a19624526203b6351d0a3a94f\
+             a329c145786e679e7b82c71a38628ac8"
+        )
+        .unwrap()
+    );
+
+    // Prehashed signing matches, and ASN.1 signatures verify with aws-lc-rs.
+    let key = AwsLcRs::load_deterministic_signing_key_der(Algorithm::EcdsaP256Sha256Asn1, &p256)?;
+    let mut context = Sha256Digest.start();
+    context.update(b"sam");
+    context.update(b"ple");
+    let signature = key.sign_context(context)?;
+
+    assert_eq!(signature, key.sign(b"sample")?);
+    assert_eq!(signature, key.sign_digest(&Sha256Digest.hash(b"sample"))?);
+
+    let verifying_key = AwsLcRs::load_verifying_key_bin(
+        Algorithm::EcdsaP256Sha256Asn1,
+        &key.compute_public_key()?.as_be_bytes()?,
+    )?;
+
+    verifying_key.verify(b"sample", &signature)?;
+    verifying_key.verify_digest(&Sha256Digest.hash(b"sample"), &signature)?;
+    assert!(
+        verifying_key
+            .verify_digest(&Sha256Digest.hash(b"sampel"), &signature)
+            .is_err()
+    );
+
+    // Digests of another hash function are rejected.
+    let randomized = AwsLcRs::load_signing_key_der(Algorithm::EcdsaP384Sha384Asn1, &p384)?;
+    assert!(
+        randomized
+            .sign_digest(&Sha256Digest.hash(b"sample"))
+            .is_err()
+    );
+    assert!(key.sign_digest(&Sha384Digest.hash(b"sample")).is_err());
+
+    assert!(AwsLcRs::load_deterministic_signing_key_der(Algorithm::Ed25519, &p256).is_err());
+
+    Ok(())
+}
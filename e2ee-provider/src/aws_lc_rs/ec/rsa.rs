@@ -0,0 +1,322 @@
+use super::import_digest;
+use crate::{
+    Error,
+    digest::Digest,
+    ec::{
+        encoding::{
+            PrivateKeyBin, PrivateKeyDer, PrivateKeySerializer, PublicKeyBin, PublicKeyDer,
+            PublicKeySerializer,
+        },
+        signature::{Algorithm, SigningKey, VerifyingKey},
+    },
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use aws_lc_rs::{
+    encoding::AsDer,
+    rand,
+    signature::{self, KeyPair, RsaEncoding, RsaParameters},
+};
+
+/// Supported modulus lengths, in bits.
+const MODULUS_BITS: core::ops::RangeInclusive<usize> = 2048..=4096;
+
+fn signing_encoding(algorithm: Algorithm) -> &'static dyn RsaEncoding {
+    match algorithm {
+        Algorithm::RsaPssSha256 => &signature::RSA_PSS_SHA256,
+        Algorithm::RsaPssSha384 => &signature::RSA_PSS_SHA384,
+        Algorithm::RsaPssSha512 => &signature::RSA_PSS_SHA512,
+        _ => unreachable!(),
+    }
+}
+
+fn verification_algorithm(algorithm: Algorithm) -> &'static RsaParameters {
+    match algorithm {
+        Algorithm::RsaPssSha256 => &signature::RSA_PSS_2048_8192_SHA256,
+        Algorithm::RsaPssSha384 => &signature::RSA_PSS_2048_8192_SHA384,
+        Algorithm::RsaPssSha512 => &signature::RSA_PSS_2048_8192_SHA512,
+        Algorithm::RsaPkcs1Sha256 => &signature::RSA_PKCS1_2048_8192_SHA256,
+        Algorithm::RsaPkcs1Sha384 => &signature::RSA_PKCS1_2048_8192_SHA384,
+        Algorithm::RsaPkcs1Sha512 => &signature::RSA_PKCS1_2048_8192_SHA512,
+        _ => unreachable!(),
+    }
+}
+
+/// RSA-PSS signing key.
+pub struct RsaSigningKey {
+    pub(super) key: signature::RsaKeyPair,
+    pub(super) algorithm: Algorithm,
+}
+
+impl RsaSigningKey {
+    /// Wraps `key`, rejecting PKCS#1 v1.5 and moduli outside 2048 to 4096
+    /// bits.
+    pub(super) fn new(key: signature::RsaKeyPair, algorithm: Algorithm) -> Result<Self, Error> {
+        if !matches!(
+            algorithm,
+            Algorithm::RsaPssSha256 | Algorithm::RsaPssSha384 | Algorithm::RsaPssSha512
+        ) {
+            return Err(Error::UnsupportedSignatureAlgorithm);
+        }
+
+        if !MODULUS_BITS.contains(&(key.public_modulus_len() * 8)) {
+            return Err(Error::KeyRejected);
+        }
+
+        Ok(Self { key, algorithm })
+    }
+}
+
+impl SigningKey for RsaSigningKey {
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut signature = vec![0; self.key.public_modulus_len()];
+        self.key.sign(
+            signing_encoding(self.algorithm),
+            &rand::SystemRandom::new(),
+            message,
+            &mut signature,
+        )?;
+
+        Ok(signature)
+    }
+
+    fn sign_digest(&self, digest: &Digest) -> Result<Vec<u8>, Error> {
+        let mut signature = vec![0; self.key.public_modulus_len()];
+        self.key.sign_digest(
+            signing_encoding(self.algorithm),
+            &import_digest(self.algorithm, digest)?,
+            &mut signature,
+        )?;
+
+        Ok(signature)
+    }
+
+    fn compute_public_key(&self) -> Result<Box<dyn PublicKeySerializer>, Error> {
+        Ok(Box::new(RsaPublicKeySerializer {
+            key: self.key.public_key().clone(),
+        }))
+    }
+
+    fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    fn to_serializer(self: Box<Self>) -> Box<dyn PrivateKeySerializer> {
+        Box::new(RsaSigningKeySerializer { key: self.key })
+    }
+}
+
+/// RSA-PSS and RSA PKCS#1 v1.5 signature verification.
+pub struct RsaVerifyingKey {
+    pub(super) key: signature::ParsedPublicKey,
+    pub(super) public_key: signature::RsaSubjectPublicKey,
+    pub(super) algorithm: Algorithm,
+}
+
+impl RsaVerifyingKey {
+    /// Parses an RFC 8017 `RSAPublicKey` or X.509 `SubjectPublicKeyInfo`,
+    /// rejecting moduli outside 2048 to 4096 bits.
+    pub(super) fn from_der(algorithm: Algorithm, der: &[u8]) -> Result<Self, Error> {
+        let public_key = signature::RsaSubjectPublicKey::from_der(der)?;
+        let bits = RsaParameters::public_modulus_len(public_key.as_ref())? as usize;
+        if !MODULUS_BITS.contains(&bits) {
+            return Err(Error::KeyRejected);
+        }
+
+        Ok(Self {
+            key: signature::ParsedPublicKey::new(verification_algorithm(algorithm), der)?,
+            public_key,
+            algorithm,
+        })
+    }
+}
+
+impl VerifyingKey for RsaVerifyingKey {
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), Error> {
+        self.key
+            .verify_sig(message, signature)
+            .map_err(|_| Error::BadSignature)
+    }
+
+    fn verify_digest(&self, digest: &Digest, signature: &[u8]) -> Result<(), Error> {
+        self.key
+            .verify_digest_sig(&import_digest(self.algorithm, digest)?, signature)
+            .map_err(|_| Error::BadSignature)
+    }
+
+    fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    fn to_serializer(self: Box<Self>) -> Box<dyn PublicKeySerializer> {
+        Box::new(RsaPublicKeySerializer {
+            key: self.public_key,
+        })
+    }
+}
+
+struct RsaPublicKeySerializer {
+    key: signature::RsaSubjectPublicKey,
+}
+
+impl PublicKeySerializer for RsaPublicKeySerializer {
+    fn as_x509_der(&self) -> Result<PublicKeyDer, Error> {
+        Ok(PublicKeyDer::X509Key(self.key.as_der()?.as_ref().to_vec()))
+    }
+
+    fn as_rfc_5915_public_key_der(&self) -> Result<PublicKeyDer, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_be_bytes(&self) -> Result<PublicKeyBin, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_compressed_be_bytes(&self) -> Result<PublicKeyBin, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+}
+
+struct RsaSigningKeySerializer {
+    key: signature::RsaKeyPair,
+}
+
+impl PrivateKeySerializer for RsaSigningKeySerializer {
+    fn as_pkcs8v1_der(&self) -> Result<PrivateKeyDer, Error> {
+        Ok(PrivateKeyDer::Pkcs8V1Key(
+            self.key.as_der()?.as_ref().to_vec(),
+        ))
+    }
+
+    fn as_pkcs8v2_der(&self) -> Result<PrivateKeyDer, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_rfc_5915_private_key_der(&self) -> Result<PrivateKeyDer, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_ed_ec_be_bytes(&self) -> Result<PrivateKeyBin, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_ec_be_bytes(&self) -> Result<PrivateKeyBin, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_raw_bytes(&self) -> Result<PrivateKeyBin, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn test() -> Result<(), Error> {
+    use crate::{
+        aws_lc_rs::{AwsLcRs, digest::Sha512Digest},
+        digest::Hash,
+        ec::KeyProvider,
+    };
+
+    // 2048-bit key and signatures of "device certificate" made with
+    // OpenSSL 3.5: PKCS#1 v1.5 with SHA-256, and PSS with SHA-384 and a
+    // 48-byte salt.
+    let spki = hex::decode(
+        "30820122300d06092a864886f70d01010105000382010f003082010a02820101\
+             00b1407d27e0d1de7b4737a932a70784056ac7ee2d62b2bde1cf3e398856b171\
+             f117915d3ce4ad886eb44ea6ed87ccace77ec5821af0f785e05acc38f03bf936\
+             bc6fa9c718bff4cb552a8f16e852cd7ef59bc733776d5c366779516f59cb67ed\
+             6116dc17a592895ca2067080a296d88d346e9d547592cc46e38418d426346da2\
+             ad08fad30616bd18f055b6f9ca73ad7aa85c9bc48b6c160a6978e78dd7ca9500\
+             34ad3bf800f816e99926e1d9e782ab9fab94a661326e42149868eded8c133ab9\
+             9462c217da572d1923afcf686336629a353b831750d706d9496c60c62b60d93f\
+             fef49e2863ef887722afec2928f1f8510e0b1e8aebdeeaac414edfc711b550d8\
+             650203010001",
+    )
+    .unwrap();
+    let rsa_public_key = hex::decode(
+        "3082010a0282010100b1407d27e0d1de7b4737a932a70784056ac7ee2d62b2bd\
+             e1cf3e398856b171f117915d3ce4ad886eb44ea6ed87ccace77ec5821af0f785\
+             e05acc38f03bf936bc6fa9c718bff4cb552a8f16e852cd7ef59bc733776d5c36\
+             6779516f59cb67ed6116dc17a592895ca2067080a296d88d346e9d547592cc46\
+             e38418d426346da2ad08fad30616bd18f055b6f9ca73ad7aa85c9bc48b6c160a\
+             6978e78dd7ca950034ad3bf800f816e99926e1d9e782ab9fab94a661326e4214\
+             9868eded8c133ab99462c217da572d1923afcf686336629a353b831750d706d9\
+             496c60c62b60d93ffef49e2863ef887722afec2928f1f8510e0b1e8aebdeeaac\
+             414edfc711b550d8650203010001",
+    )
+    .unwrap();
+    let pkcs1_signature = hex::decode(
+        "362871d269b440f6574dc09c43773c2a082361f7cebbec160b3753c7f05ab790\
+             956303f3d34bfac5d47b63b55815888e1139d65d0f9c0c249350a143d11858c3\
+             04062e5fcfd99ae85c56317b1d155a2c56bdd7cb2ac50fd6327d1111c61fecd7\
+             73b95df6ee03b6d35d86665aa7298a04ce9b1af79320195490c4166c22e4f99b\
+             402d2299034e880005ae32f63670765788c2c456c5e38971dfe6d37e080661b6\
+             47363ece0c6bdf95ae93db3d66c79fb082b87701e9fe26c9b6b9df3f7eeb99e6\
+             f7c39eb71f9ca2344984194fdbf73c25c30848c2fcbb42d149653ea567255982\
+             dc57299cc8d25672149b0325dde519868a7ad8d7a352da1b622fd564d53cdfb9",
+    )
+    .unwrap();
+    let pss_signature = hex::decode(
+        "8946ca9431f6151cfd6b75bc430ac1dcb17300e66e406ab3f2489ed240afa3e5\
+             85b0dfe5b41d838f68e12901a10b69220d0d337fc4c5d9640730d9685da788ad\
+             61b4f8263252970845ce029f8ff7a62a0be35b04143bb4b1a385d908fa45420e\
+             5c6ae5d5bb3abff89973a50e096fcad63f19f935c1ac1714ce38beeddd3ca5bf\
+             dd9b3a44c5d0b05deb141ba0d183ec5fd2c9794f4cdd6250b89341e2220338de\
+             86be4af4233fc32741a8f496ff18991c006b8efaadcb09b62fc6081b7cd28c6b\
+             07dd6d4ec85278945b0d4acdb674d676f003dbb21a82f3e1adcb29fbf93a1a64\
+             44b9b49d66bc6f645a43e7b1ba5e92303e49f69d10c137dc54e716189adda1fc",
+    )
+    .unwrap();
+    let message = b"device certificate";
+
+    let key =
+        AwsLcRs::load_verifying_key_der(Algorithm::RsaPkcs1Sha256, &PublicKeyDer::X509Key(spki))?;
+    key.verify(message, &pkcs1_signature)?;
+    assert!(key.verify(message, &pss_signature).is_err());
+
+    let key = AwsLcRs::load_verifying_key_der(
+        Algorithm::RsaPssSha384,
+        &PublicKeyDer::RsaPublicKey(rsa_public_key),
+    )?;
+    key.verify(message, &pss_signature)?;
+    assert!(key.verify(b"device certificatE", &pss_signature).is_err());
+
+    // 1024-bit keys are rejected.
+    let small = hex::decode(
+        "30819f300d06092a864886f70d010101050003818d0030818902818100c5fee7\
+             7f964936d37914058d6a8101fda90ab0c3051986008708933ecda268831245d4\
+             21fcee3d180d7802fa50eefb866bbd30d000dc3eeef1a8414993d995e70a79fd\
+             720f68a61528c09bfdd92056f966b769517856e54e911efd52f981d38d34d15b\
+             5f02983d30392ce1315d954777510381bad123994e5986acafe0b2a78d020301\
+             0001",
+    )
+    .unwrap();
+    assert!(
+        AwsLcRs::load_verifying_key_der(Algorithm::RsaPkcs1Sha256, &PublicKeyDer::X509Key(small))
+            .is_err()
+    );
+
+    // PSS keys round trip through PKCS#8 and sign prehashed messages.
+    let key = AwsLcRs::generate_signing_key(Algorithm::RsaPssSha512)?;
+    let verifying_key = AwsLcRs::load_verifying_key_der(
+        Algorithm::RsaPssSha512,
+        &key.compute_public_key()?.as_x509_der()?,
+    )?;
+    let mut context = Sha512Digest.start();
+    context.update(message);
+    let signature = key.sign_context(context)?;
+
+    assert_eq!(signature.len(), 384);
+    verifying_key.verify(message, &signature)?;
+    verifying_key.verify_digest(&Sha512Digest.hash(message), &signature)?;
+
+    let der = key.to_serializer().as_pkcs8v1_der()?;
+    let key = AwsLcRs::load_signing_key_der(Algorithm::RsaPssSha512, &der)?;
+    verifying_key.verify(message, &key.sign(message)?)?;
+
+    // PKCS#1 v1.5 is verification only.
+    assert!(AwsLcRs::load_signing_key_der(Algorithm::RsaPkcs1Sha512, &der).is_err());
+    assert!(AwsLcRs::generate_signing_key(Algorithm::RsaPkcs1Sha256).is_err());
+
+    Ok(())
+}
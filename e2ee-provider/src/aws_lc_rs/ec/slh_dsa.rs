@@ -0,0 +1,97 @@
+//! SLH-DSA keys are backed by the pure-Rust [`crate::slh_dsa`]
+//! implementation; aws-lc-rs only supplies randomness for key generation and
+//! hedged signing.
+
+use crate::{
+    Error,
+    ec::{
+        encoding::{PrivateKeyBin, PrivateKeyDer},
+        signature::{Algorithm, SigningKey, VerifyingKey},
+    },
+};
+use alloc::boxed::Box;
+
+#[cfg(feature = "slh_dsa")]
+use crate::slh_dsa::{SlhDsaSigningKey, SlhDsaVerifyingKey};
+
+#[cfg(feature = "slh_dsa")]
+fn fill_random(buf: &mut [u8]) -> Result<(), Error> {
+    Ok(aws_lc_rs::rand::fill(buf)?)
+}
+
+#[cfg(feature = "slh_dsa")]
+pub(super) fn load_signing_key_der(
+    algorithm: Algorithm,
+    der: &PrivateKeyDer,
+) -> Result<Box<dyn SigningKey>, Error> {
+    match der {
+        PrivateKeyDer::Pkcs8V1Key(der) => Ok(Box::new(
+            SlhDsaSigningKey::from_pkcs8(algorithm, der)?.hedged(fill_random),
+        )),
+        _ => Err(Error::UnsupportedEncoding),
+    }
+}
+
+#[cfg(feature = "slh_dsa")]
+pub(super) fn load_signing_key_bin(
+    algorithm: Algorithm,
+    bin: &PrivateKeyBin,
+) -> Result<Box<dyn SigningKey>, Error> {
+    match bin {
+        PrivateKeyBin::Raw(sk) => Ok(Box::new(
+            SlhDsaSigningKey::from_raw(algorithm, sk)?.hedged(fill_random),
+        )),
+        _ => Err(Error::UnsupportedEncoding),
+    }
+}
+
+#[cfg(feature = "slh_dsa")]
+pub(super) fn generate_signing_key(algorithm: Algorithm) -> Result<Box<dyn SigningKey>, Error> {
+    Ok(Box::new(SlhDsaSigningKey::generate(
+        algorithm,
+        fill_random,
+    )?))
+}
+
+#[cfg(feature = "slh_dsa")]
+pub(super) fn load_verifying_key(
+    algorithm: Algorithm,
+    bytes: &[u8],
+    is_der: bool,
+) -> Result<Box<dyn VerifyingKey>, Error> {
+    Ok(Box::new(if is_der {
+        SlhDsaVerifyingKey::from_x509_der(algorithm, bytes)?
+    } else {
+        SlhDsaVerifyingKey::from_raw(algorithm, bytes)?
+    }))
+}
+
+#[cfg(not(feature = "slh_dsa"))]
+pub(super) fn load_signing_key_der(
+    _algorithm: Algorithm,
+    _der: &PrivateKeyDer,
+) -> Result<Box<dyn SigningKey>, Error> {
+    Err(Error::UnsupportedSignatureAlgorithm)
+}
+
+#[cfg(not(feature = "slh_dsa"))]
+pub(super) fn load_signing_key_bin(
+    _algorithm: Algorithm,
+    _bin: &PrivateKeyBin,
+) -> Result<Box<dyn SigningKey>, Error> {
+    Err(Error::UnsupportedSignatureAlgorithm)
+}
+
+#[cfg(not(feature = "slh_dsa"))]
+pub(super) fn generate_signing_key(_algorithm: Algorithm) -> Result<Box<dyn SigningKey>, Error> {
+    Err(Error::UnsupportedSignatureAlgorithm)
+}
+
+#[cfg(not(feature = "slh_dsa"))]
+pub(super) fn load_verifying_key(
+    _algorithm: Algorithm,
+    _bytes: &[u8],
+    _is_der: bool,
+) -> Result<Box<dyn VerifyingKey>, Error> {
+    Err(Error::UnsupportedSignatureAlgorithm)
+}
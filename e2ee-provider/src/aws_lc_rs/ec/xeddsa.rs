@@ -0,0 +1,80 @@
+//! XEdDSA keys are backed by the pure-Rust [`crate::xeddsa`] implementation;
+//! aws-lc-rs only supplies the signing randomness.
+
+use crate::{
+    Error,
+    ec::{
+        encoding::{PrivateKeyBin, PrivateKeyDer},
+        signature::{SigningKey, VerifyingKey},
+    },
+};
+use alloc::boxed::Box;
+
+#[cfg(feature = "xeddsa")]
+use crate::xeddsa::{XEdDsaPrivateKey, XEdDsaVerifyingKey};
+
+#[cfg(feature = "xeddsa")]
+fn fill_random(buf: &mut [u8]) -> Result<(), Error> {
+    Ok(aws_lc_rs::rand::fill(buf)?)
+}
+
+#[cfg(feature = "xeddsa")]
+pub(super) fn load_signing_key_der(der: &PrivateKeyDer) -> Result<Box<dyn SigningKey>, Error> {
+    match der {
+        PrivateKeyDer::Pkcs8V1Key(der) => {
+            Ok(Box::new(XEdDsaPrivateKey::from_pkcs8(der, fill_random)?))
+        }
+        _ => Err(Error::UnsupportedEncoding),
+    }
+}
+
+#[cfg(feature = "xeddsa")]
+pub(super) fn load_signing_key_bin(bin: &PrivateKeyBin) -> Result<Box<dyn SigningKey>, Error> {
+    match bin {
+        PrivateKeyBin::EdEcSeed(k) => Ok(Box::new(XEdDsaPrivateKey::from_x25519_bytes(
+            k,
+            fill_random,
+        )?)),
+        _ => Err(Error::UnsupportedEncoding),
+    }
+}
+
+#[cfg(feature = "xeddsa")]
+pub(super) fn generate_signing_key() -> Result<Box<dyn SigningKey>, Error> {
+    Ok(Box::new(XEdDsaPrivateKey::generate(fill_random)?))
+}
+
+#[cfg(feature = "xeddsa")]
+pub(super) fn load_verifying_key(
+    bytes: &[u8],
+    is_der: bool,
+) -> Result<Box<dyn VerifyingKey>, Error> {
+    Ok(Box::new(if is_der {
+        XEdDsaVerifyingKey::from_x509_der(bytes)?
+    } else {
+        XEdDsaVerifyingKey::from_x25519_bytes(bytes)?
+    }))
+}
+
+#[cfg(not(feature = "xeddsa"))]
+pub(super) fn load_signing_key_der(_der: &PrivateKeyDer) -> Result<Box<dyn SigningKey>, Error> {
+    Err(Error::UnsupportedSignatureAlgorithm)
+}
+
+#[cfg(not(feature = "xeddsa"))]
+pub(super) fn load_signing_key_bin(_bin: &PrivateKeyBin) -> Result<Box<dyn SigningKey>, Error> {
+    Err(Error::UnsupportedSignatureAlgorithm)
+}
+
+#[cfg(not(feature = "xeddsa"))]
+pub(super) fn generate_signing_key() -> Result<Box<dyn SigningKey>, Error> {
+    Err(Error::UnsupportedSignatureAlgorithm)
+}
+
+#[cfg(not(feature = "xeddsa"))]
+pub(super) fn load_verifying_key(
+    _bytes: &[u8],
+    _is_der: bool,
+) -> Result<Box<dyn VerifyingKey>, Error> {
+    Err(Error::UnsupportedSignatureAlgorithm)
+}
@@ -1,27 +1,55 @@
-use super::AwsLcRs;
 use crate::{
     Error,
-    hkdf::{Algorithm, Expander, Hkdf, Okm},
-    provider::Provider,
+    generic::{Expander, Hkdf},
+    hkdf::{Algorithm, Okm},
 };
-use alloc::boxed::Box;
-use aws_lc_rs::hkdf;
+use aws_lc_rs::{hkdf, hmac};
 
-impl Provider<Algorithm, &'static dyn Hkdf> for AwsLcRs {
-    fn get(&self, algorithm: Algorithm) -> Option<&'static dyn Hkdf> {
+/// Any of the HKDF algorithms of aws-lc-rs, selected at runtime.
+pub struct AnyHkdf {
+    hkdf: hkdf::Algorithm,
+    hmac: hmac::Algorithm,
+}
+
+/// HKDF expander holding the pseudorandom key of an [`AnyHkdf`].
+pub struct PrkExpander {
+    prk: hkdf::Prk,
+}
+
+impl AnyHkdf {
+    /// Returns the HKDF algorithm, if supported.
+    pub fn get(algorithm: Algorithm) -> Option<&'static Self> {
         match algorithm {
-            Algorithm::Sha256 => Some(&HkdfSha256),
-            Algorithm::Sha384 => Some(&HkdfSha384),
-            Algorithm::Sha512 => Some(&HkdfSha512),
+            Algorithm::Sha256 => Some(&SHA256),
+            Algorithm::Sha384 => Some(&SHA384),
+            Algorithm::Sha512 => Some(&SHA512),
+        }
+    }
+
+    /// All supported HKDF algorithms.
+    pub(super) const SUPPORTED: &'static [Algorithm] =
+        &[Algorithm::Sha256, Algorithm::Sha384, Algorithm::Sha512];
+}
+
+impl Hkdf for AnyHkdf {
+    type Expander = PrkExpander;
+
+    fn extract(&self, salt: &[u8], secret: &[u8]) -> PrkExpander {
+        PrkExpander {
+            prk: hkdf::Salt::new(self.hkdf, salt).extract(secret),
         }
     }
 
-    fn supported_algorithms(&self) -> &'static [Algorithm] {
-        &[Algorithm::Sha256, Algorithm::Sha384, Algorithm::Sha512]
+    fn extract_prk(&self, salt: &[u8], secret: &[u8]) -> Okm {
+        let key = hmac::Key::new(self.hmac, salt);
+
+        Okm::new(hmac::sign(&key, secret).as_ref()).expect("HMAC tags fit in an Okm")
     }
 
-    fn is_algorithm_supported(&self, _algorithm: Algorithm) -> bool {
-        true
+    fn expander(&self, prk: &[u8]) -> PrkExpander {
+        PrkExpander {
+            prk: hkdf::Prk::new_less_safe(self.hkdf, prk),
+        }
     }
 }
 
@@ -34,32 +62,38 @@ impl hkdf::KeyType for Keylen {
     }
 }
 
+impl Expander for PrkExpander {
+    fn expand_into(&self, info: &[&[u8]], output: &mut [u8]) -> Result<(), Error> {
+        self.prk.expand(info, Keylen(output.len()))?.fill(output)?;
+
+        Ok(())
+    }
+}
+
 macro_rules! impl_hkdf_functions {
     ($($alg:ident),*) => {
         $(paste::paste! {
+            static [<$alg:upper>]: AnyHkdf = AnyHkdf {
+                hkdf: hkdf::[<HKDF_ $alg:upper>],
+                hmac: hmac::[<HMAC_ $alg:upper>],
+            };
+
             #[doc = "HMAC-key derivation using " $alg "."]
             pub struct [<Hkdf $alg>];
 
-            #[doc = "HKDF " $alg " expander."]
-            struct [<Hkdf $alg Expander>] {
-                prk: hkdf::Prk
-            }
-
             impl Hkdf for [<Hkdf $alg>] {
-                fn extract(&self, salt: &[u8], secret: &[u8]) -> Box<dyn Expander> {
-                    Box::new([<Hkdf $alg Expander>] {
-                        prk: hkdf::Salt::new(hkdf::[<HKDF_ $alg:upper>], salt).extract(secret)
-                    })
-                }
-            }
+                type Expander = PrkExpander;
 
-            impl Expander for [<Hkdf $alg Expander>] {
-                fn expand(&self, info: &[&[u8]], len: usize) -> Result<Okm, Error> {
-                    let mut okm = Okm { buf: alloc::vec![0; len] };
+                fn extract(&self, salt: &[u8], secret: &[u8]) -> PrkExpander {
+                    [<$alg:upper>].extract(salt, secret)
+                }
 
-                    self.prk.expand(info, Keylen(len))?.fill(&mut okm.buf)?;
+                fn extract_prk(&self, salt: &[u8], secret: &[u8]) -> Okm {
+                    [<$alg:upper>].extract_prk(salt, secret)
+                }
 
-                    Ok(okm)
+                fn expander(&self, prk: &[u8]) -> PrkExpander {
+                    [<$alg:upper>].expander(prk)
                 }
             }
         })*
@@ -67,3 +101,79 @@ macro_rules! impl_hkdf_functions {
 }
 
 impl_hkdf_functions!(Sha256, Sha384, Sha512);
+
+#[test]
+#[cfg(test)]
+fn test() -> Result<(), Error> {
+    struct TestCase {
+        ikm: &'static str,
+        salt: &'static str,
+        info: &'static str,
+        len: usize,
+        output: &'static str,
+    }
+
+    let test_cases = [
+        TestCase {
+            ikm: "0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b",
+            salt: "000102030405060708090a0b0c",
+            info: "f0f1f2f3f4f5f6f7f8f9",
+            len: 42,
+            output: "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865",
+        },
+        TestCase {
+            ikm: "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f404142434445464748494a4b4c4d4e4f",
+            salt: "606162636465666768696a6b6c6d6e6f707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9fa0a1a2a3a4a5a6a7a8a9aaabacadaeaf",
+            info: "b0b1b2b3b4b5b6b7b8b9babbbcbdbebfc0c1c2c3c4c5c6c7c8c9cacbcccdcecfd0d1d2d3d4d5d6d7d8d9dadbdcdddedfe0e1e2e3e4e5e6e7e8e9eaebecedeeeff0f1f2f3f4f5f6f7f8f9fafbfcfdfeff",
+            len: 82,
+            output: "b11e398dc80327a1c8e7f78c596a49344f012eda2d4efad8a050cc4c19afa97c59045a99cac7827271cb41c65e590e09da3275600c2f09b8367793a9aca3db71cc30c58179ec3e87c14c01d5c1f3434f1d87",
+        },
+        TestCase {
+            ikm: "0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b",
+            salt: "",
+            info: "",
+            len: 42,
+            output: "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8",
+        },
+    ];
+
+    for test_case in test_cases {
+        let expander = HkdfSha256.extract(
+            &hex::decode(test_case.salt).unwrap(),
+            &hex::decode(test_case.ikm).unwrap(),
+        );
+
+        let okm = expander.expand(&[&hex::decode(test_case.info).unwrap()], test_case.len)?;
+
+        assert_eq!(okm.as_ref(), hex::decode(test_case.output).unwrap());
+
+        let prk = HkdfSha256.extract_prk(
+            &hex::decode(test_case.salt).unwrap(),
+            &hex::decode(test_case.ikm).unwrap(),
+        );
+        let okm = crate::hkdf::Hkdf::expand_prk(
+            &HkdfSha256,
+            prk.as_ref(),
+            &[&hex::decode(test_case.info).unwrap()],
+            test_case.len,
+        )?;
+
+        assert_eq!(okm.as_ref(), hex::decode(test_case.output).unwrap());
+    }
+
+    let prk = HkdfSha256.extract_prk(&[], &[0; 32]);
+    let mut output = [0; crate::hkdf::MAX_OKM_LEN + 1];
+    crate::hkdf::Hkdf::expand_prk_into(&HkdfSha256, prk.as_ref(), &[], &mut output)?;
+    assert!(crate::hkdf::Hkdf::expand_prk(&HkdfSha256, prk.as_ref(), &[], output.len()).is_err());
+    assert_eq!(
+        crate::hkdf::Hkdf::expand_prk(&HkdfSha256, prk.as_ref(), &[], 42)?.as_ref(),
+        &output[..42]
+    );
+    assert!(crate::hkdf::Okm::new(&output).is_err());
+    assert_eq!(
+        crate::hkdf::Okm::new(&output[..42])?.as_ref(),
+        &output[..42]
+    );
+
+    Ok(())
+}
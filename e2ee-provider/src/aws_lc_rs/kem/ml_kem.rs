@@ -0,0 +1,200 @@
+use crate::{
+    Error,
+    kem::{
+        Algorithm, DecapsulationKey, EncapsulationKey, SharedSecret,
+        encoding::{
+            DecapsulationKeyBin, DecapsulationKeySerializer, EncapsulationKeyBin,
+            EncapsulationKeySerializer,
+        },
+    },
+};
+use alloc::{boxed::Box, vec::Vec};
+use aws_lc_rs::kem;
+
+/// Decapsulation key for [`ML-KEM`] algorithms.
+///
+/// [`ML-KEM`]: Algorithm::MlKem768
+pub struct MlKemDecapsulationKey {
+    pub(super) key: kem::DecapsulationKey,
+    pub(super) algorithm: Algorithm,
+}
+
+impl DecapsulationKey for MlKemDecapsulationKey {
+    fn decapsulate(&self, ciphertext: &[u8]) -> Result<SharedSecret, Error> {
+        Ok(SharedSecret {
+            buf: self.key.decapsulate(ciphertext.into())?.as_ref().to_vec(),
+        })
+    }
+
+    fn encapsulation_key(&self) -> Result<Box<dyn EncapsulationKey>, Error> {
+        Ok(Box::new(MlKemEncapsulationKey {
+            key: encapsulation_key(&self.key)?,
+            algorithm: self.algorithm,
+        }))
+    }
+
+    fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    fn to_serializer(self: Box<Self>) -> Box<dyn DecapsulationKeySerializer> {
+        Box::new(MlKemDecapsulationKeySerializer { key: self.key })
+    }
+}
+
+/// Extracts the encapsulation key embedded in an ML-KEM decapsulation key.
+///
+/// aws-lc-rs does not keep the public part of keys loaded from bytes, so it is
+/// taken from the FIPS 203 encoding `dk_PKE || ek || H(ek) || z`, where `ek`
+/// is 32 bytes longer than `dk_PKE`.
+pub(super) fn encapsulation_key(
+    key: &kem::DecapsulationKey,
+) -> Result<kem::EncapsulationKey, Error> {
+    let dk = key.key_bytes()?;
+    let dk_pke_len = (dk.as_ref().len() - 96) / 2;
+
+    Ok(kem::EncapsulationKey::new(
+        key.algorithm(),
+        &dk.as_ref()[dk_pke_len..2 * dk_pke_len + 32],
+    )?)
+}
+
+/// Encapsulation key for [`ML-KEM`] algorithms.
+///
+/// [`ML-KEM`]: Algorithm::MlKem768
+pub struct MlKemEncapsulationKey {
+    pub(super) key: kem::EncapsulationKey,
+    pub(super) algorithm: Algorithm,
+}
+
+impl EncapsulationKey for MlKemEncapsulationKey {
+    fn encapsulate(&self) -> Result<(Vec<u8>, SharedSecret), Error> {
+        let (ciphertext, shared_secret) = self.key.encapsulate()?;
+
+        Ok((
+            ciphertext.as_ref().to_vec(),
+            SharedSecret {
+                buf: shared_secret.as_ref().to_vec(),
+            },
+        ))
+    }
+
+    fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    fn to_serializer(self: Box<Self>) -> Box<dyn EncapsulationKeySerializer> {
+        Box::new(MlKemEncapsulationKeySerializer { key: self.key })
+    }
+}
+
+struct MlKemDecapsulationKeySerializer {
+    key: kem::DecapsulationKey,
+}
+
+impl DecapsulationKeySerializer for MlKemDecapsulationKeySerializer {
+    fn as_raw_bytes(&self) -> Result<DecapsulationKeyBin, Error> {
+        Ok(DecapsulationKeyBin::Raw(
+            self.key.key_bytes()?.as_ref().to_vec(),
+        ))
+    }
+}
+
+struct MlKemEncapsulationKeySerializer {
+    key: kem::EncapsulationKey,
+}
+
+impl EncapsulationKeySerializer for MlKemEncapsulationKeySerializer {
+    fn as_raw_bytes(&self) -> Result<EncapsulationKeyBin, Error> {
+        Ok(EncapsulationKeyBin::Raw(
+            self.key.key_bytes()?.as_ref().to_vec(),
+        ))
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn test() -> Result<(), Error> {
+    use crate::{aws_lc_rs::AwsLcRs, kem::KeyProvider};
+
+    for algorithm in [
+        Algorithm::MlKem512,
+        Algorithm::MlKem768,
+        Algorithm::MlKem1024,
+    ] {
+        let decapsulation_key = AwsLcRs::generate_decapsulation_key(algorithm)?;
+        let encapsulation_key = decapsulation_key
+            .encapsulation_key()?
+            .to_serializer()
+            .as_raw_bytes()?;
+        assert_eq!(
+            encapsulation_key.as_ref().len(),
+            algorithm.encapsulation_key_len()
+        );
+
+        let encapsulation_key = AwsLcRs::load_encapsulation_key_bin(algorithm, &encapsulation_key)?;
+        let (ciphertext, shared_secret) = encapsulation_key.encapsulate()?;
+        assert_eq!(ciphertext.len(), algorithm.ciphertext_len());
+        assert_eq!(shared_secret.as_ref().len(), algorithm.shared_secret_len());
+        assert_eq!(
+            decapsulation_key.decapsulate(&ciphertext)?.as_ref(),
+            shared_secret.as_ref()
+        );
+
+        // A key loaded back from its encoding decapsulates the same, and
+        // recovers the encapsulation key embedded in it.
+        let bytes = decapsulation_key.to_serializer().as_raw_bytes()?;
+        assert_eq!(bytes.as_ref().len(), algorithm.decapsulation_key_len());
+
+        let decapsulation_key = AwsLcRs::load_decapsulation_key_bin(algorithm, &bytes)?;
+        assert_eq!(
+            decapsulation_key.decapsulate(&ciphertext)?.as_ref(),
+            shared_secret.as_ref()
+        );
+        assert_eq!(
+            decapsulation_key
+                .encapsulation_key()?
+                .to_serializer()
+                .as_raw_bytes()?
+                .as_ref(),
+            encapsulation_key.to_serializer().as_raw_bytes()?.as_ref()
+        );
+
+        // Tampered ciphertexts are implicitly rejected with an unrelated
+        // secret; ciphertexts of the wrong length are errors.
+        let mut tampered = ciphertext.clone();
+        tampered[0] ^= 1;
+        assert_ne!(
+            decapsulation_key.decapsulate(&tampered)?.as_ref(),
+            shared_secret.as_ref()
+        );
+        assert!(decapsulation_key.decapsulate(&ciphertext[1..]).is_err());
+
+        // Keys of the wrong length, or of another parameter set.
+        assert!(
+            AwsLcRs::load_decapsulation_key_bin(
+                algorithm,
+                &DecapsulationKeyBin::Raw(bytes.as_ref()[1..].to_vec())
+            )
+            .is_err()
+        );
+        assert!(
+            AwsLcRs::load_encapsulation_key_bin(
+                algorithm,
+                &EncapsulationKeyBin::Raw(alloc::vec![0; algorithm.encapsulation_key_len() + 1])
+            )
+            .is_err()
+        );
+        assert!(
+            AwsLcRs::load_encapsulation_key_bin(
+                Algorithm::MlKem768,
+                &EncapsulationKeyBin::Raw(
+                    alloc::vec![0; Algorithm::MlKem512.encapsulation_key_len()]
+                )
+            )
+            .is_err()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,175 @@
+//! ML-KEM-768 key generation from a seed, `ML-KEM.KeyGen_internal` of
+//! FIPS 203, which aws-lc-rs does not expose. The expanded decapsulation key
+//! is then loaded into aws-lc-rs.
+
+use alloc::vec::Vec;
+use sha3::{
+    Sha3_256, Sha3_512, Shake128, Shake256,
+    digest::{Digest, ExtendableOutput, Update, XofReader},
+};
+use zeroize::Zeroize;
+
+const Q: u32 = 3329;
+const K: usize = 3;
+const ETA1: usize = 2;
+
+type Poly = [u32; 256];
+
+/// `zeta^BitRev7(i)` for `zeta = 17`, the primitive 256-th root of unity.
+const ZETAS: [u32; 128] = {
+    let mut zetas = [0; 128];
+    let mut i = 0;
+    while i < 128 {
+        zetas[i] = pow17((i as u8).reverse_bits() as u32 >> 1);
+        i += 1;
+    }
+    zetas
+};
+
+/// `17^(2 BitRev7(i) + 1)`, the moduli of the base case multiplications.
+const GAMMAS: [u32; 128] = {
+    let mut gammas = [0; 128];
+    let mut i = 0;
+    while i < 128 {
+        gammas[i] = pow17(2 * ((i as u8).reverse_bits() as u32 >> 1) + 1);
+        i += 1;
+    }
+    gammas
+};
+
+const fn pow17(mut exp: u32) -> u32 {
+    let (mut base, mut acc) = (17, 1);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            acc = acc * base % Q;
+        }
+        base = base * base % Q;
+        exp >>= 1;
+    }
+    acc
+}
+
+/// Expanded decapsulation key `dk_PKE || ek || H(ek) || z` of the seed
+/// `d || z`.
+pub(super) fn expand_decapsulation_key(d: &[u8; 32], z: &[u8; 32]) -> Vec<u8> {
+    let mut rho_sigma: [u8; 64] = Sha3_512::new()
+        .chain_update(d)
+        .chain_update([K as u8])
+        .finalize()
+        .into();
+    let (rho, sigma) = rho_sigma.split_at(32);
+
+    let mut s = [[0; 256]; K];
+    let mut e = [[0; 256]; K];
+    for (n, poly) in s.iter_mut().chain(e.iter_mut()).enumerate() {
+        sample_cbd(sigma, n as u8, poly);
+        ntt(poly);
+    }
+
+    let mut ek = Vec::with_capacity(384 * K + 32);
+    for (i, e) in e.iter().enumerate() {
+        let mut t = *e;
+        for (j, s) in s.iter().enumerate() {
+            let a = sample_ntt(rho, j as u8, i as u8);
+            for (k, t) in t.chunks_exact_mut(2).enumerate() {
+                let (c0, c1) = base_case_multiply(&a[2 * k..], &s[2 * k..], GAMMAS[k]);
+                t[0] = (t[0] + c0) % Q;
+                t[1] = (t[1] + c1) % Q;
+            }
+        }
+        byte_encode_12(&t, &mut ek);
+    }
+    ek.extend_from_slice(rho);
+
+    let mut dk = Vec::with_capacity(768 * K + 96);
+    s.iter().for_each(|s| byte_encode_12(s, &mut dk));
+    dk.extend_from_slice(&ek);
+    dk.extend_from_slice(&Sha3_256::digest(&ek));
+    dk.extend_from_slice(z);
+
+    rho_sigma.zeroize();
+    s.zeroize();
+    e.zeroize();
+
+    dk
+}
+
+/// `SamplePolyCBD_eta1(PRF_eta1(sigma, n))`.
+fn sample_cbd(sigma: &[u8], n: u8, poly: &mut Poly) {
+    let mut bytes = [0; 64 * ETA1];
+    Shake256::default()
+        .chain(sigma)
+        .chain([n])
+        .finalize_xof_into(&mut bytes);
+
+    // With `eta = 2`, every nibble holds the two pairs of bits of a
+    // coefficient.
+    for (i, coefficient) in poly.iter_mut().enumerate() {
+        let nibble = (bytes[i / 2] >> (4 * (i % 2))) as u32;
+        let x = (nibble & 1) + (nibble >> 1 & 1);
+        let y = (nibble >> 2 & 1) + (nibble >> 3 & 1);
+        *coefficient = (x + Q - y) % Q;
+    }
+
+    bytes.zeroize();
+}
+
+/// `SampleNTT(rho || j || i)`, an element of the matrix `A` in the NTT
+/// domain.
+fn sample_ntt(rho: &[u8], j: u8, i: u8) -> Poly {
+    let mut xof = Shake128::default().chain(rho).chain([j, i]).finalize_xof();
+    let mut poly = [0; 256];
+    let mut len = 0;
+
+    while len < 256 {
+        let mut c = [0; 3];
+        xof.read(&mut c);
+
+        let d1 = c[0] as u32 | (c[1] as u32 & 0x0f) << 8;
+        let d2 = (c[1] as u32) >> 4 | (c[2] as u32) << 4;
+        for d in [d1, d2] {
+            if d < Q && len < 256 {
+                poly[len] = d;
+                len += 1;
+            }
+        }
+    }
+
+    poly
+}
+
+fn ntt(poly: &mut Poly) {
+    let mut i = 1;
+    let mut len = 128;
+    while len >= 2 {
+        for start in (0..256).step_by(2 * len) {
+            let zeta = ZETAS[i];
+            i += 1;
+            for j in start..start + len {
+                let t = zeta * poly[j + len] % Q;
+                poly[j + len] = (poly[j] + Q - t) % Q;
+                poly[j] = (poly[j] + t) % Q;
+            }
+        }
+        len /= 2;
+    }
+}
+
+/// Product of the degree one polynomials `a` and `b` modulo
+/// `X^2 - gamma`.
+fn base_case_multiply(a: &[u32], b: &[u32], gamma: u32) -> (u32, u32) {
+    (
+        (a[0] * b[0] + a[1] * b[1] % Q * gamma) % Q,
+        (a[0] * b[1] + a[1] * b[0]) % Q,
+    )
+}
+
+fn byte_encode_12(poly: &Poly, out: &mut Vec<u8>) {
+    for pair in poly.chunks_exact(2) {
+        out.extend_from_slice(&[
+            pair[0] as u8,
+            (pair[0] >> 8 | (pair[1] & 0x0f) << 4) as u8,
+            (pair[1] >> 4) as u8,
+        ]);
+    }
+}
@@ -0,0 +1,9 @@
+/// Module-lattice-based key encapsulation mechanism using aws-lc-rs.
+pub mod ml_kem;
+
+/// X-Wing hybrid KEM using aws-lc-rs.
+pub mod xwing;
+
+mod ml_kem_768;
+
+mod provider;
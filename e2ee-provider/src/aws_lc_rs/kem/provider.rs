@@ -0,0 +1,70 @@
+use super::{
+    ml_kem::{MlKemDecapsulationKey, MlKemEncapsulationKey},
+    xwing::{XWingDecapsulationKey, XWingEncapsulationKey},
+};
+use crate::{
+    Error,
+    aws_lc_rs::AwsLcRs,
+    kem::{
+        Algorithm, DecapsulationKey, EncapsulationKey, KeyProvider,
+        encoding::{DecapsulationKeyBin, EncapsulationKeyBin},
+    },
+};
+use alloc::boxed::Box;
+use aws_lc_rs::kem::{self, ML_KEM_512, ML_KEM_768, ML_KEM_1024};
+
+fn aws_lc_rs_alg(algorithm: Algorithm) -> Result<&'static kem::Algorithm, Error> {
+    match algorithm {
+        Algorithm::MlKem512 => Ok(&ML_KEM_512),
+        Algorithm::MlKem768 => Ok(&ML_KEM_768),
+        Algorithm::MlKem1024 => Ok(&ML_KEM_1024),
+        Algorithm::XWing => Err(Error::UnsupportedKemAlgorithm),
+    }
+}
+
+impl KeyProvider for AwsLcRs {
+    fn load_decapsulation_key_bin(
+        algorithm: Algorithm,
+        bin: &DecapsulationKeyBin,
+    ) -> Result<Box<dyn DecapsulationKey>, Error> {
+        let DecapsulationKeyBin::Raw(bytes) = bin;
+
+        if matches!(algorithm, Algorithm::XWing) {
+            return Ok(Box::new(XWingDecapsulationKey::from_bytes(bytes)?));
+        }
+
+        Ok(Box::new(MlKemDecapsulationKey {
+            key: kem::DecapsulationKey::new(aws_lc_rs_alg(algorithm)?, bytes)?,
+            algorithm,
+        }))
+    }
+
+    fn load_encapsulation_key_bin(
+        algorithm: Algorithm,
+        bin: &EncapsulationKeyBin,
+    ) -> Result<Box<dyn EncapsulationKey>, Error> {
+        let EncapsulationKeyBin::Raw(bytes) = bin;
+
+        if matches!(algorithm, Algorithm::XWing) {
+            return Ok(Box::new(XWingEncapsulationKey::from_bytes(bytes)?));
+        }
+
+        Ok(Box::new(MlKemEncapsulationKey {
+            key: kem::EncapsulationKey::new(aws_lc_rs_alg(algorithm)?, bytes)?,
+            algorithm,
+        }))
+    }
+
+    fn generate_decapsulation_key(
+        algorithm: Algorithm,
+    ) -> Result<Box<dyn DecapsulationKey>, Error> {
+        if matches!(algorithm, Algorithm::XWing) {
+            return Ok(Box::new(XWingDecapsulationKey::generate()?));
+        }
+
+        Ok(Box::new(MlKemDecapsulationKey {
+            key: kem::DecapsulationKey::generate(aws_lc_rs_alg(algorithm)?)?,
+            algorithm,
+        }))
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{
+    Error,
+    key_wrap::{Algorithm, KeyWrap, UnwrappedKey},
+};
+use alloc::vec::Vec;
+use aws_lc_rs::key_wrap::{self, AesBlockCipher, KeyWrap as _, KeyWrapPadded as _};
+
+/// Any of the AES key wrap algorithms of aws-lc-rs, selected at runtime.
+pub struct AnyKeyWrap {
+    cipher: &'static AesBlockCipher,
+    padded: bool,
+    algorithm: Algorithm,
+}
+
+impl AnyKeyWrap {
+    /// Returns the key wrapping algorithm, if supported.
+    pub fn get(algorithm: Algorithm) -> Option<&'static Self> {
+        match algorithm {
+            Algorithm::Aes128Kw => Some(&AES_128_KW),
+            Algorithm::Aes256Kw => Some(&AES_256_KW),
+            Algorithm::Aes128Kwp => Some(&AES_128_KWP),
+            Algorithm::Aes256Kwp => Some(&AES_256_KWP),
+        }
+    }
+
+    /// All supported key wrapping algorithms, in order of preference.
+    pub(super) const SUPPORTED: &'static [Algorithm] = &[
+        Algorithm::Aes256Kwp,
+        Algorithm::Aes128Kwp,
+        Algorithm::Aes256Kw,
+        Algorithm::Aes128Kw,
+    ];
+}
+
+impl KeyWrap for AnyKeyWrap {
+    fn wrap(&self, kek: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+        let kek = key_wrap::AesKek::new(self.cipher, kek)?;
+        let overhead = if self.padded { 15 } else { 8 };
+        let mut buf = alloc::vec![0; plaintext.len() + overhead];
+
+        let len = if self.padded {
+            kek.wrap_with_padding(plaintext, &mut buf)?.len()
+        } else {
+            kek.wrap(plaintext, &mut buf)?.len()
+        };
+        buf.truncate(len);
+
+        Ok(buf)
+    }
+
+    fn unwrap(&self, kek: &[u8], ciphertext: &[u8]) -> Result<UnwrappedKey, Error> {
+        let kek = key_wrap::AesKek::new(self.cipher, kek)?;
+        // Owned by `UnwrappedKey` from the start, so that partially
+        // written plaintext is zeroized on the error path too.
+        let mut key = UnwrappedKey {
+            buf: alloc::vec![0; ciphertext.len()],
+        };
+
+        let len = if self.padded {
+            kek.unwrap_with_padding(ciphertext, &mut key.buf)?.len()
+        } else {
+            kek.unwrap(ciphertext, &mut key.buf)?.len()
+        };
+        key.buf.truncate(len);
+
+        Ok(key)
+    }
+
+    fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+}
+
+macro_rules! impl_key_wrap_functions {
+    ($($alg:ident, $static:ident, $cipher:ident, $padded:literal);*) => {
+        $(paste::paste! {
+            static $static: AnyKeyWrap = AnyKeyWrap {
+                cipher: &key_wrap::$cipher,
+                padded: $padded,
+                algorithm: Algorithm::$alg,
+            };
+
+            #[doc = "AES key wrap using " $alg "."]
+            pub struct $alg;
+
+            impl KeyWrap for $alg {
+                fn wrap(&self, kek: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+                    $static.wrap(kek, plaintext)
+                }
+
+                fn unwrap(&self, kek: &[u8], ciphertext: &[u8]) -> Result<UnwrappedKey, Error> {
+                    $static.unwrap(kek, ciphertext)
+                }
+
+                fn algorithm(&self) -> Algorithm {
+                    Algorithm::$alg
+                }
+            }
+        })*
+    };
+}
+
+impl_key_wrap_functions!(
+    Aes128Kw, AES_128_KW, AES_128, false;
+    Aes256Kw, AES_256_KW, AES_256, false;
+    Aes128Kwp, AES_128_KWP, AES_128, true;
+    Aes256Kwp, AES_256_KWP, AES_256, true
+);
+
+#[test]
+#[cfg(test)]
+fn test() -> Result<(), Error> {
+    use crate::ec::encoding::PrivateKeyDer;
+
+    // RFC 3394, section 4.1.
+    let kek = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let key = hex::decode("00112233445566778899aabbccddeeff").unwrap();
+    let wrapped = Aes128Kw.wrap(&kek, &key)?;
+
+    assert_eq!(
+        wrapped,
+        hex::decode("1fa68b0a8112b447aef34bd8fb5a7b829d3e862371d2cfe5").unwrap()
+    );
+    assert_eq!(Aes128Kw.unwrap(&kek, &wrapped)?.as_ref(), key);
+
+    let kek = [7; 32];
+    let der = PrivateKeyDer::Pkcs8V1Key((0..83).collect());
+    let mut wrapped = Aes256Kwp.wrap_private_key_der(&kek, &der)?;
+    let unwrapped = Aes256Kwp.unwrap_private_key_der(&kek, &wrapped, PrivateKeyDer::Pkcs8V1Key)?;
+
+    assert_eq!(unwrapped.as_ref(), der.as_ref());
+
+    wrapped[0] ^= 1;
+    assert!(Aes256Kwp.unwrap(&kek, &wrapped).is_err());
+
+    Ok(())
+}
@@ -4,14 +4,108 @@ pub mod digest;
 /// aws-lc-rs HMAC-key derivation implementations.
 pub mod hkdf;
 
+/// aws-lc-rs AEAD implementations.
+pub mod aead;
+
+/// aws-lc-rs AES key wrap implementations.
+pub mod key_wrap;
+
 /// aws-lc-rs elliptic curve wrappers.
 pub mod ec;
 
+/// aws-lc-rs key encapsulation wrappers.
+pub mod kem;
+
 /// Unit type implementing cryptographic providers required by e2ee.
 pub struct AwsLcRs;
 
-use crate::{HashProvider, HkdfProvider};
+use crate::{
+    Error, aead as aead_, digest as digest_,
+    ec::{
+        agreement as agreement_,
+        encoding::{PrivateKeyBin, PrivateKeyDer, PublicKeyBin, PublicKeyDer},
+    },
+    generic, hkdf as hkdf_, key_wrap as key_wrap_,
+};
+use ec::agreement::{AgreementEphemeralPrivateKey, AgreementPrivateKey, AgreementPublicKey};
+
+impl generic::CryptoProvider for AwsLcRs {
+    type Hash = digest::AnyDigest;
+    type Hkdf = hkdf::AnyHkdf;
+    type KeyWrap = key_wrap::AnyKeyWrap;
+    type Aead = aead::AnyAead;
+    type PrivateKey = AgreementPrivateKey;
+    type EphemeralPrivateKey = AgreementEphemeralPrivateKey;
+    type PublicKey = AgreementPublicKey;
+
+    fn hash(&self, algorithm: digest_::Algorithm) -> Option<&'static digest::AnyDigest> {
+        digest::AnyDigest::get(algorithm)
+    }
+
+    fn hkdf(&self, algorithm: hkdf_::Algorithm) -> Option<&'static hkdf::AnyHkdf> {
+        hkdf::AnyHkdf::get(algorithm)
+    }
+
+    fn key_wrap(&self, algorithm: key_wrap_::Algorithm) -> Option<&'static key_wrap::AnyKeyWrap> {
+        key_wrap::AnyKeyWrap::get(algorithm)
+    }
+
+    fn aead(&self, algorithm: aead_::Algorithm) -> Option<&'static aead::AnyAead> {
+        aead::AnyAead::get(algorithm)
+    }
+
+    fn supported_hash_algorithms(&self) -> &'static [digest_::Algorithm] {
+        digest::AnyDigest::SUPPORTED
+    }
+
+    fn supported_hkdf_algorithms(&self) -> &'static [hkdf_::Algorithm] {
+        hkdf::AnyHkdf::SUPPORTED
+    }
+
+    fn supported_key_wrap_algorithms(&self) -> &'static [key_wrap_::Algorithm] {
+        key_wrap::AnyKeyWrap::SUPPORTED
+    }
+
+    fn supported_aead_algorithms(&self) -> &'static [aead_::Algorithm] {
+        aead::AnyAead::SUPPORTED
+    }
+
+    fn load_private_key_der(
+        &self,
+        algorithm: agreement_::Algorithm,
+        der: &PrivateKeyDer,
+    ) -> Result<AgreementPrivateKey, Error> {
+        AgreementPrivateKey::from_der(algorithm, der)
+    }
+
+    fn load_private_key_bin(
+        &self,
+        algorithm: agreement_::Algorithm,
+        bin: &PrivateKeyBin,
+    ) -> Result<AgreementPrivateKey, Error> {
+        AgreementPrivateKey::from_bin(algorithm, bin)
+    }
+
+    fn load_public_key_der(
+        &self,
+        algorithm: agreement_::Algorithm,
+        der: &PublicKeyDer,
+    ) -> Result<AgreementPublicKey, Error> {
+        AgreementPublicKey::from_der(algorithm, der)
+    }
 
-impl HashProvider for AwsLcRs {}
+    fn load_public_key_bin(
+        &self,
+        algorithm: agreement_::Algorithm,
+        bin: &PublicKeyBin,
+    ) -> Result<AgreementPublicKey, Error> {
+        AgreementPublicKey::from_bin(algorithm, bin)
+    }
 
-impl HkdfProvider for AwsLcRs {}
+    fn generate_ephemeral_private_key(
+        &self,
+        algorithm: agreement_::Algorithm,
+    ) -> Result<AgreementEphemeralPrivateKey, Error> {
+        AgreementEphemeralPrivateKey::generate(algorithm)
+    }
+}
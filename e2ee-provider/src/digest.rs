@@ -1,10 +1,13 @@
 use crate::Error;
-use alloc::{boxed::Box, vec::Vec};
+use alloc::boxed::Box;
 use zeroize::Zeroize;
 
+/// Length of the longest digest, that of SHA-512 and SHA3-512.
+pub const MAX_OUTPUT_LEN: usize = 64;
+
 /// Supported hash algorithms.
 #[non_exhaustive]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Algorithm {
     /// SHA3-224, as specified in FIPS 202.
     Sha3_224 = 1096,
@@ -62,6 +65,18 @@ pub trait Hash {
     /// Computes and returns the digest of the given data.
     fn hash(&self, data: &[u8]) -> Digest;
 
+    /// Computes the digest of the concatenation of `parts`.
+    ///
+    /// Providers override this to hash without boxing a [`Context`].
+    fn hash_parts(&self, parts: &[&[u8]]) -> Digest {
+        let mut context = self.start();
+        for part in parts {
+            context.update(part);
+        }
+
+        context.finish()
+    }
+
     /// Returns the digest of data.
     fn algorithm(&self) -> Algorithm;
 }
@@ -79,8 +94,35 @@ pub trait Context {
 }
 
 /// The output (digest) of a hash function.
+///
+/// Kept in a buffer of [`MAX_OUTPUT_LEN`] bytes, so no heap allocation is
+/// needed.
 pub struct Digest {
-    pub(crate) buf: Vec<u8>,
+    pub(crate) buf: [u8; MAX_OUTPUT_LEN],
+    pub(crate) algorithm: Algorithm,
+}
+
+impl Digest {
+    /// Wraps the digest `bytes` computed with `algorithm`.
+    ///
+    /// Returns [`Error::Unspecified`] if `bytes` is not [`output_len`] long.
+    ///
+    /// [`output_len`]: Algorithm::output_len
+    pub fn new(algorithm: Algorithm, bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != algorithm.output_len() {
+            return Err(Error::Unspecified);
+        }
+
+        let mut buf = [0; MAX_OUTPUT_LEN];
+        buf[..bytes.len()].copy_from_slice(bytes);
+
+        Ok(Self { buf, algorithm })
+    }
+
+    /// Hash function that computed the digest.
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
 }
 
 impl Drop for Digest {
@@ -91,6 +133,6 @@ impl Drop for Digest {
 
 impl AsRef<[u8]> for Digest {
     fn as_ref(&self) -> &[u8] {
-        &self.buf
+        &self.buf[..self.algorithm.output_len()]
     }
 }
@@ -3,12 +3,16 @@ use super::{
     encoding::{PrivateKeySerializer, PublicKeySerializer},
 };
 use crate::Error;
-use alloc::{boxed::Box, vec::Vec};
+use alloc::boxed::Box;
+use core::any::Any;
 use zeroize::Zeroize;
 
+/// Length of the longest shared secret, that of ECDH with P-521.
+pub const MAX_SHARED_SECRET_LEN: usize = 66;
+
 /// Supported key agreement functions.
 #[non_exhaustive]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Algorithm {
     /// ECDH using the [`P256`] curve.
     ///
@@ -101,17 +105,48 @@ pub trait EphemeralPrivateKey {
 }
 
 /// A public key for key agreement.
-pub trait PublicKey {
+///
+/// Backends may downcast the peer public key of [`PrivateKey::agree`] to
+/// their own type, so that keys they loaded are not parsed again.
+pub trait PublicKey: Any {
     /// Kind of the public key we have.
     fn algorithm(&self) -> Algorithm;
 
     /// Interface for serializing the key into binary formats.
     fn to_serializer(self: Box<Self>) -> Box<dyn PublicKeySerializer>;
+
+    /// Copies the key as it was parsed and validated, to pass it to several
+    /// agreements. Returns `None` if the backend cannot copy it.
+    fn try_clone(&self) -> Option<Box<dyn PublicKey>> {
+        None
+    }
 }
 
 /// Result of a key agreement.
+///
+/// Kept in a buffer of [`MAX_SHARED_SECRET_LEN`] bytes, so no heap
+/// allocation is needed.
 pub struct SharedSecret {
-    pub(crate) buf: Vec<u8>,
+    pub(crate) buf: [u8; MAX_SHARED_SECRET_LEN],
+    pub(crate) len: usize,
+}
+
+impl SharedSecret {
+    /// Wraps the result of a key agreement.
+    ///
+    /// Returns [`Error::Unspecified`] if `bytes` is longer than
+    /// [`MAX_SHARED_SECRET_LEN`].
+    pub fn new(bytes: &[u8]) -> Result<Self, Error> {
+        let mut buf = [0; MAX_SHARED_SECRET_LEN];
+        buf.get_mut(..bytes.len())
+            .ok_or(Error::Unspecified)?
+            .copy_from_slice(bytes);
+
+        Ok(Self {
+            buf,
+            len: bytes.len(),
+        })
+    }
 }
 
 impl Drop for SharedSecret {
@@ -122,6 +157,6 @@ impl Drop for SharedSecret {
 
 impl AsRef<[u8]> for SharedSecret {
     fn as_ref(&self) -> &[u8] {
-        &self.buf
+        &self.buf[..self.len]
     }
 }
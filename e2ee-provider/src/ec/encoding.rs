@@ -14,6 +14,9 @@ pub enum PrivateKeyDer {
     /// Elliptic curve private key structure in DER format, defined in
     /// [RFC 5915](https://datatracker.ietf.org/doc/html/rfc5915).
     EcPrivateKey(Vec<u8>),
+    /// RSA private key structure in DER format, defined in
+    /// [RFC 8017](https://datatracker.ietf.org/doc/html/rfc8017).
+    RsaPrivateKey(Vec<u8>),
 }
 
 /// Serialized public key DER.
@@ -25,6 +28,9 @@ pub enum PublicKeyDer {
     /// Elliptic curve public key structure in DER format, defined in
     /// [RFC 5480](https://datatracker.ietf.org/doc/html/rfc5480).
     EcPublicKey(Vec<u8>),
+    /// RSA public key structure in DER format, defined in
+    /// [RFC 8017](https://datatracker.ietf.org/doc/html/rfc8017).
+    RsaPublicKey(Vec<u8>),
 }
 
 /// Serialized private key bytes.
@@ -36,6 +42,9 @@ pub enum PrivateKeyBin {
     /// Elliptic curve private key encoded as a big-endian fixed-length
     /// integer.
     Ec(Vec<u8>),
+    /// Private key in the raw encoding defined by its algorithm, e.g.
+    /// `skEncode` of FIPS 204.
+    Raw(Vec<u8>),
 }
 
 /// Serialized public key bytes.
@@ -68,6 +77,11 @@ pub trait PrivateKeySerializer {
     ///
     /// X25519 and X448 are not supported.
     fn as_ec_be_bytes(&self) -> Result<PrivateKeyBin, Error>;
+
+    /// Exposes the private key in the raw encoding defined by its algorithm.
+    ///
+    /// Only algorithms without an elliptic curve encoding are supported.
+    fn as_raw_bytes(&self) -> Result<PrivateKeyBin, Error>;
 }
 
 /// Methods for serializing public keys into DER or binary formats.
@@ -89,9 +103,10 @@ pub trait PublicKeySerializer {
 impl Drop for PrivateKeyDer {
     fn drop(&mut self) {
         match self {
-            Self::Pkcs8V1Key(key) | Self::Pkcs8V2Key(key) | Self::EcPrivateKey(key) => {
-                key.zeroize()
-            }
+            Self::Pkcs8V1Key(key)
+            | Self::Pkcs8V2Key(key)
+            | Self::EcPrivateKey(key)
+            | Self::RsaPrivateKey(key) => key.zeroize(),
         }
     }
 }
@@ -99,7 +114,10 @@ impl Drop for PrivateKeyDer {
 impl AsRef<[u8]> for PrivateKeyDer {
     fn as_ref(&self) -> &[u8] {
         match self {
-            Self::Pkcs8V1Key(key) | Self::Pkcs8V2Key(key) | Self::EcPrivateKey(key) => key,
+            Self::Pkcs8V1Key(key)
+            | Self::Pkcs8V2Key(key)
+            | Self::EcPrivateKey(key)
+            | Self::RsaPrivateKey(key) => key,
         }
     }
 }
@@ -107,7 +125,7 @@ impl AsRef<[u8]> for PrivateKeyDer {
 impl Drop for PublicKeyDer {
     fn drop(&mut self) {
         match self {
-            Self::X509Key(key) | Self::EcPublicKey(key) => key.zeroize(),
+            Self::X509Key(key) | Self::EcPublicKey(key) | Self::RsaPublicKey(key) => key.zeroize(),
         }
     }
 }
@@ -115,7 +133,7 @@ impl Drop for PublicKeyDer {
 impl AsRef<[u8]> for PublicKeyDer {
     fn as_ref(&self) -> &[u8] {
         match self {
-            Self::X509Key(key) | Self::EcPublicKey(key) => key,
+            Self::X509Key(key) | Self::EcPublicKey(key) | Self::RsaPublicKey(key) => key,
         }
     }
 }
@@ -123,7 +141,7 @@ impl AsRef<[u8]> for PublicKeyDer {
 impl Drop for PrivateKeyBin {
     fn drop(&mut self) {
         match self {
-            Self::Ec(bytes) | Self::EdEcSeed(bytes) => bytes.zeroize(),
+            Self::Ec(bytes) | Self::EdEcSeed(bytes) | Self::Raw(bytes) => bytes.zeroize(),
         }
     }
 }
@@ -131,7 +149,7 @@ impl Drop for PrivateKeyBin {
 impl AsRef<[u8]> for PrivateKeyBin {
     fn as_ref(&self) -> &[u8] {
         match self {
-            Self::Ec(bytes) | Self::EdEcSeed(bytes) => bytes,
+            Self::Ec(bytes) | Self::EdEcSeed(bytes) | Self::Raw(bytes) => bytes,
         }
     }
 }
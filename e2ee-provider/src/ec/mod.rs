@@ -5,7 +5,7 @@ use encoding::{PrivateKeyBin, PrivateKeyDer, PublicKeyBin, PublicKeyDer};
 /// Elliptic curve key agreement interface.
 pub mod agreement;
 
-/// Elliptic curve digital signature algorithms.
+/// Digital signature algorithms.
 pub mod signature;
 
 /// Public and private key encoding formats.
@@ -87,12 +87,27 @@ pub trait KeyProvider {
         der: &PrivateKeyDer,
     ) -> Result<Box<dyn signature::SigningKey>, Error>;
 
+    /// Loads an ECDSA signing key from DER whose signatures are
+    /// deterministic, as specified in RFC 6979.
+    ///
+    /// Signing with such a key does not depend on a random number generator,
+    /// and the same message always yields the same signature.
+    fn load_deterministic_signing_key_der(
+        algorithm: signature::Algorithm,
+        der: &PrivateKeyDer,
+    ) -> Result<Box<dyn signature::SigningKey>, Error>;
+
     /// Loads an elliptic curve signing key from raw bytes.
     fn load_signing_key_bin(
         algorithm: signature::Algorithm,
         bin: &PrivateKeyBin,
     ) -> Result<Box<dyn signature::SigningKey>, Error>;
 
+    /// Generates a new signing key.
+    fn generate_signing_key(
+        algorithm: signature::Algorithm,
+    ) -> Result<Box<dyn signature::SigningKey>, Error>;
+
     /// Loads an elliptic curve verifying key from DER.
     fn load_verifying_key_der(
         algorithm: signature::Algorithm,
@@ -104,4 +119,18 @@ pub trait KeyProvider {
         algorithm: signature::Algorithm,
         bin: &PublicKeyBin,
     ) -> Result<Box<dyn signature::VerifyingKey>, Error>;
+
+    /// Verifies many signatures at once under `strictness`, reporting which
+    /// of them failed.
+    ///
+    /// Every entry gets the verdict it would get alone. Ed25519 entries are
+    /// batched only under [`Ed25519Rules::Zip215`], whose cofactored equation
+    /// is the batch equation; entries of other rules or algorithms are
+    /// verified one by one.
+    ///
+    /// [`Ed25519Rules::Zip215`]: signature::Ed25519Rules::Zip215
+    fn verify_batch(
+        entries: &[signature::BatchEntry<'_>],
+        strictness: signature::Strictness,
+    ) -> signature::BatchVerification;
 }
@@ -2,7 +2,10 @@ use super::{
     Curve,
     encoding::{PrivateKeySerializer, PublicKeySerializer},
 };
-use crate::Error;
+use crate::{
+    Error,
+    digest::{self, Context, Digest},
+};
 use alloc::{boxed::Box, vec::Vec};
 
 /// Supported signature algorithms.
@@ -37,22 +40,169 @@ pub enum Algorithm {
     ///
     /// [`Curve25519`]: Curve::Curve25519
     Ed25519 = 1087,
+    /// Ed25519ctx, [`Ed25519`] signatures bound to a non-empty context
+    /// string, as specified in RFC 8032.
+    ///
+    /// The value is the NID of Ed25519 shifted left by eight bits, with the
+    /// `phflag` of `dom2` in the low bits.
+    ///
+    /// [`Ed25519`]: Algorithm::Ed25519
+    Ed25519Ctx = 1087 << 8,
+    /// Ed25519ph, [`Ed25519`] signatures of the SHA-512 hash of the message
+    /// bound to an optional context string, as specified in RFC 8032.
+    ///
+    /// [`Ed25519`]: Algorithm::Ed25519
+    Ed25519Ph = (1087 << 8) | 1,
     /// Verification of [`Curve448`] signatures.
     ///
     /// [`Curve448`]: Curve::Curve448
     Ed448 = 1088,
+    /// XEdDSA signatures with [`Curve25519`] (X25519) keys, as specified by
+    /// [Signal](https://signal.org/docs/specifications/xeddsa/).
+    ///
+    /// The value is the NID of X25519, whose keys it signs with.
+    ///
+    /// [`Curve25519`]: Curve::Curve25519
+    XEdDsa25519 = 1034,
+    /// RSASSA-PSS signatures using SHA-256 for both the message and MGF1, as
+    /// specified in RFC 8017, with 2048 to 4096-bit keys.
+    ///
+    /// The value is the NID of RSASSA-PSS shifted left by 16 bits, with the
+    /// NID of the hash function in the low bits.
+    RsaPssSha256 = (912 << 16) | 672,
+    /// RSASSA-PSS signatures using SHA-384, with 2048 to 4096-bit keys.
+    RsaPssSha384 = (912 << 16) | 673,
+    /// RSASSA-PSS signatures using SHA-512, with 2048 to 4096-bit keys.
+    RsaPssSha512 = (912 << 16) | 674,
+    /// Verification of RSASSA-PKCS1-v1_5 signatures using SHA-256, as
+    /// specified in RFC 8017, with 2048 to 4096-bit keys.
+    ///
+    /// Signing with PKCS#1 v1.5 is not supported.
+    RsaPkcs1Sha256 = 668,
+    /// Verification of RSASSA-PKCS1-v1_5 signatures using SHA-384, with 2048
+    /// to 4096-bit keys.
+    RsaPkcs1Sha384 = 669,
+    /// Verification of RSASSA-PKCS1-v1_5 signatures using SHA-512, with 2048
+    /// to 4096-bit keys.
+    RsaPkcs1Sha512 = 670,
+    /// ML-DSA-44, as specified in FIPS 204.
+    MlDsa44 = 1457,
+    /// ML-DSA-65, as specified in FIPS 204.
+    MlDsa65 = 1458,
+    /// ML-DSA-87, as specified in FIPS 204.
+    MlDsa87 = 1459,
+    /// SLH-DSA-SHA2-128s, as specified in FIPS 205.
+    SlhDsaSha2_128s = 1460,
+    /// SLH-DSA-SHA2-128f, as specified in FIPS 205.
+    SlhDsaSha2_128f = 1461,
+    /// SLH-DSA-SHA2-192s, as specified in FIPS 205.
+    SlhDsaSha2_192s = 1462,
+    /// SLH-DSA-SHA2-192f, as specified in FIPS 205.
+    SlhDsaSha2_192f = 1463,
+    /// SLH-DSA-SHA2-256s, as specified in FIPS 205.
+    SlhDsaSha2_256s = 1464,
+    /// SLH-DSA-SHA2-256f, as specified in FIPS 205.
+    SlhDsaSha2_256f = 1465,
+    /// SLH-DSA-SHAKE-128s, as specified in FIPS 205.
+    SlhDsaShake128s = 1466,
+    /// SLH-DSA-SHAKE-128f, as specified in FIPS 205.
+    SlhDsaShake128f = 1467,
+    /// SLH-DSA-SHAKE-192s, as specified in FIPS 205.
+    SlhDsaShake192s = 1468,
+    /// SLH-DSA-SHAKE-192f, as specified in FIPS 205.
+    SlhDsaShake192f = 1469,
+    /// SLH-DSA-SHAKE-256s, as specified in FIPS 205.
+    SlhDsaShake256s = 1470,
+    /// SLH-DSA-SHAKE-256f, as specified in FIPS 205.
+    SlhDsaShake256f = 1471,
 }
 
 impl Algorithm {
     /// Elliptic curve associated with the algorithm.
-    pub fn curve(&self) -> Curve {
+    ///
+    /// Returns `None` for algorithms not based on elliptic curves.
+    pub fn curve(&self) -> Option<Curve> {
         match self {
-            Self::EcdsaP256Sha256Asn1 | Self::EcdsaP256Sha256Fixed => Curve::P256,
-            Self::EcdsaP384Sha384Asn1 | Self::EcdsaP384Sha384Fixed => Curve::P384,
-            Self::Ed25519 => Curve::Curve25519,
-            Self::Ed448 => Curve::Curve448,
+            Self::EcdsaP256Sha256Asn1 | Self::EcdsaP256Sha256Fixed => Some(Curve::P256),
+            Self::EcdsaP384Sha384Asn1 | Self::EcdsaP384Sha384Fixed => Some(Curve::P384),
+            Self::Ed25519 | Self::Ed25519Ctx | Self::Ed25519Ph | Self::XEdDsa25519 => {
+                Some(Curve::Curve25519)
+            }
+            Self::Ed448 => Some(Curve::Curve448),
+            Self::RsaPssSha256
+            | Self::RsaPssSha384
+            | Self::RsaPssSha512
+            | Self::RsaPkcs1Sha256
+            | Self::RsaPkcs1Sha384
+            | Self::RsaPkcs1Sha512 => None,
+            Self::MlDsa44 | Self::MlDsa65 | Self::MlDsa87 => None,
+            Self::SlhDsaSha2_128s
+            | Self::SlhDsaSha2_128f
+            | Self::SlhDsaSha2_192s
+            | Self::SlhDsaSha2_192f
+            | Self::SlhDsaSha2_256s
+            | Self::SlhDsaSha2_256f
+            | Self::SlhDsaShake128s
+            | Self::SlhDsaShake128f
+            | Self::SlhDsaShake192s
+            | Self::SlhDsaShake192f
+            | Self::SlhDsaShake256s
+            | Self::SlhDsaShake256f => None,
         }
     }
+
+    /// Hash function applied to the message before signing.
+    ///
+    /// Returns `None` for algorithms that sign the message itself, which
+    /// cannot sign a prehashed [`Digest`].
+    pub fn digest_algorithm(&self) -> Option<digest::Algorithm> {
+        match self {
+            Self::EcdsaP256Sha256Asn1
+            | Self::EcdsaP256Sha256Fixed
+            | Self::RsaPssSha256
+            | Self::RsaPkcs1Sha256 => Some(digest::Algorithm::Sha256),
+            Self::EcdsaP384Sha384Asn1
+            | Self::EcdsaP384Sha384Fixed
+            | Self::RsaPssSha384
+            | Self::RsaPkcs1Sha384 => Some(digest::Algorithm::Sha384),
+            Self::Ed25519Ph | Self::RsaPssSha512 | Self::RsaPkcs1Sha512 => {
+                Some(digest::Algorithm::Sha512)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the algorithm is an RSA signature scheme.
+    pub fn is_rsa(&self) -> bool {
+        matches!(
+            self,
+            Self::RsaPssSha256
+                | Self::RsaPssSha384
+                | Self::RsaPssSha512
+                | Self::RsaPkcs1Sha256
+                | Self::RsaPkcs1Sha384
+                | Self::RsaPkcs1Sha512
+        )
+    }
+
+    /// Whether the algorithm is one of the SLH-DSA (FIPS 205) parameter sets.
+    pub fn is_slh_dsa(&self) -> bool {
+        matches!(
+            self,
+            Self::SlhDsaSha2_128s
+                | Self::SlhDsaSha2_128f
+                | Self::SlhDsaSha2_192s
+                | Self::SlhDsaSha2_192f
+                | Self::SlhDsaSha2_256s
+                | Self::SlhDsaSha2_256f
+                | Self::SlhDsaShake128s
+                | Self::SlhDsaShake128f
+                | Self::SlhDsaShake192s
+                | Self::SlhDsaShake192f
+                | Self::SlhDsaShake256s
+                | Self::SlhDsaShake256f
+        )
+    }
 }
 
 impl TryFrom<i32> for Algorithm {
@@ -65,7 +215,31 @@ impl TryFrom<i32> for Algorithm {
             1114 => Ok(Self::EcdsaP384Sha384Asn1),
             -1114 => Ok(Self::EcdsaP384Sha384Fixed),
             1087 => Ok(Self::Ed25519),
+            0x43f00 => Ok(Self::Ed25519Ctx),
+            0x43f01 => Ok(Self::Ed25519Ph),
             1088 => Ok(Self::Ed448),
+            1034 => Ok(Self::XEdDsa25519),
+            0x0390_02a0 => Ok(Self::RsaPssSha256),
+            0x0390_02a1 => Ok(Self::RsaPssSha384),
+            0x0390_02a2 => Ok(Self::RsaPssSha512),
+            668 => Ok(Self::RsaPkcs1Sha256),
+            669 => Ok(Self::RsaPkcs1Sha384),
+            670 => Ok(Self::RsaPkcs1Sha512),
+            1457 => Ok(Self::MlDsa44),
+            1458 => Ok(Self::MlDsa65),
+            1459 => Ok(Self::MlDsa87),
+            1460 => Ok(Self::SlhDsaSha2_128s),
+            1461 => Ok(Self::SlhDsaSha2_128f),
+            1462 => Ok(Self::SlhDsaSha2_192s),
+            1463 => Ok(Self::SlhDsaSha2_192f),
+            1464 => Ok(Self::SlhDsaSha2_256s),
+            1465 => Ok(Self::SlhDsaSha2_256f),
+            1466 => Ok(Self::SlhDsaShake128s),
+            1467 => Ok(Self::SlhDsaShake128f),
+            1468 => Ok(Self::SlhDsaShake192s),
+            1469 => Ok(Self::SlhDsaShake192f),
+            1470 => Ok(Self::SlhDsaShake256s),
+            1471 => Ok(Self::SlhDsaShake256f),
             _ => Err(Error::UnsupportedSignatureAlgorithm),
         }
     }
@@ -76,6 +250,39 @@ pub trait SigningKey {
     /// Signs given `message` using the selected digest function.
     fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error>;
 
+    /// Signs `message` bound to the `context` string, for algorithms with
+    /// domain separation such as [`Ed25519Ctx`] and [`Ed25519Ph`].
+    ///
+    /// Protocols pass a fixed label per kind of signed object, so that a
+    /// signature cannot be replayed as another kind.
+    ///
+    /// [`Ed25519Ctx`]: Algorithm::Ed25519Ctx
+    /// [`Ed25519Ph`]: Algorithm::Ed25519Ph
+    fn sign_with_context(&self, context: &[u8], message: &[u8]) -> Result<Vec<u8>, Error> {
+        let _ = (context, message);
+
+        Err(Error::UnsupportedSignatureAlgorithm)
+    }
+
+    /// Signs a message of which `digest` is the finished hash.
+    ///
+    /// The digest must be computed with the [`digest_algorithm`] of the key.
+    /// Signatures are the same as [`sign`] would produce for the message.
+    ///
+    /// [`digest_algorithm`]: Algorithm::digest_algorithm
+    /// [`sign`]: SigningKey::sign
+    fn sign_digest(&self, digest: &Digest) -> Result<Vec<u8>, Error> {
+        let _ = digest;
+
+        Err(Error::UnsupportedSignatureAlgorithm)
+    }
+
+    /// Finishes `context` and signs the message hashed into it, so large
+    /// messages need not be buffered.
+    fn sign_context(&self, context: Box<dyn Context>) -> Result<Vec<u8>, Error> {
+        self.sign_digest(&context.finish())
+    }
+
     /// Computes public key of the signing key.
     fn compute_public_key(&self) -> Result<Box<dyn PublicKeySerializer>, Error>;
 
@@ -87,9 +294,60 @@ pub trait SigningKey {
 }
 
 /// A public key for verifying digital signatures.
+///
+/// Verification fails with [`Error::BadSignature`] if the signature does not
+/// match, and with [`Error::NonCanonicalSignature`] if the [`Strictness`] of
+/// the key rejects its encoding.
 pub trait VerifyingKey {
     /// Verifies the signature of the given `message`.
-    fn verify(&self, message: &[u8], signature: &[u8]) -> bool;
+    #[must_use = "a signature that fails to verify must be rejected"]
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), Error>;
+
+    /// Verifies the signature of `message` bound to the `context` string.
+    ///
+    /// Algorithms without domain separation never verify.
+    #[must_use = "a signature that fails to verify must be rejected"]
+    fn verify_with_context(
+        &self,
+        context: &[u8],
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Error> {
+        let _ = (context, message, signature);
+
+        Err(Error::UnsupportedSignatureAlgorithm)
+    }
+
+    /// Verifies the signature of a message of which `digest` is the finished
+    /// hash.
+    ///
+    /// Digests of a hash function other than the [`digest_algorithm`] of the
+    /// key never verify.
+    ///
+    /// [`digest_algorithm`]: Algorithm::digest_algorithm
+    #[must_use = "a signature that fails to verify must be rejected"]
+    fn verify_digest(&self, digest: &Digest, signature: &[u8]) -> Result<(), Error> {
+        let _ = (digest, signature);
+
+        Err(Error::UnsupportedSignatureAlgorithm)
+    }
+
+    /// Finishes `context` and verifies the signature of the message hashed
+    /// into it.
+    #[must_use = "a signature that fails to verify must be rejected"]
+    fn verify_context(&self, context: Box<dyn Context>, signature: &[u8]) -> Result<(), Error> {
+        self.verify_digest(&context.finish(), signature)
+    }
+
+    /// Sets the rules signatures are checked with, [`Strictness::default`]
+    /// until changed.
+    ///
+    /// Keys of algorithms the strictness has no rules for ignore it.
+    fn set_strictness(&mut self, strictness: Strictness) -> Result<(), Error> {
+        let _ = strictness;
+
+        Ok(())
+    }
 
     /// Kind of the private key we have.
     fn algorithm(&self) -> Algorithm;
@@ -97,3 +355,161 @@ pub trait VerifyingKey {
     /// Interface for serializing the key into binary formats.
     fn to_serializer(self: Box<Self>) -> Box<dyn PublicKeySerializer>;
 }
+
+/// Rules for accepting Ed25519 signatures.
+///
+/// Implementations disagree on edge cases such as non-canonical point
+/// encodings and points of small order, so peers have to pick the same rules
+/// to reach the same verdict on every signature.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Ed25519Rules {
+    /// RFC 8032 verification with the cofactorless equation
+    /// `[S] B == R + [k] A`, rejecting non-canonical encodings of `A` and `R`.
+    ///
+    /// No batch equation gives the same verdicts, so batches of strict
+    /// signatures are verified one by one.
+    #[default]
+    Strict,
+    /// ZIP-215 verification with the cofactored equation
+    /// `[8] [S] B == [8] R + [8] [k] A`, accepting non-canonical encodings of
+    /// `A` and `R`. The batch equation is the sum of these, so a signature
+    /// verifies alone if and only if it verifies in a ZIP-215 batch.
+    Zip215,
+}
+
+/// How strictly verifying keys accept signatures.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Strictness {
+    /// Rules for [`Ed25519`] signatures.
+    ///
+    /// [`Ed25519`]: Algorithm::Ed25519
+    pub ed25519: Ed25519Rules,
+    /// Rejects ECDSA signatures whose `s` is above half of the group order,
+    /// so that `(r, n - s)` cannot be passed off as another valid signature.
+    pub ecdsa_low_s: bool,
+}
+
+/// Whether `bytes` is the canonical encoding of an Ed25519 point: `y < p`,
+/// and no sign bit for `x = 0`.
+///
+/// This and the following checks let providers apply a [`Strictness`] that
+/// their backend does not enforce.
+pub fn is_canonical_ed25519_point(bytes: &[u8]) -> bool {
+    let Ok(y) = <&[u8; 32]>::try_from(bytes) else {
+        return false;
+    };
+
+    let y_is_large = y[1..31].iter().all(|&byte| byte == 0xff) && y[31] & 0x7f == 0x7f;
+    let x_is_zero = (y[1..31].iter().all(|&byte| byte == 0) && y[31] & 0x7f == 0 && y[0] == 1)
+        || (y_is_large && y[0] == 0xec);
+
+    !((y_is_large && y[0] >= 0xed) || (x_is_zero && y[31] & 0x80 != 0))
+}
+
+/// Whether `bytes` is a little-endian Ed25519 scalar below the group order.
+pub fn is_canonical_ed25519_scalar(bytes: &[u8]) -> bool {
+    const ORDER: [u8; 32] = [
+        0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde,
+        0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x10,
+    ];
+
+    bytes.len() == 32 && bytes.iter().rev().lt(ORDER.iter().rev())
+}
+
+/// Whether the `s` of an ECDSA `signature` is at most half of the group
+/// order. Malformed signatures are not low-S.
+pub fn is_low_s_ecdsa(algorithm: Algorithm, signature: &[u8]) -> bool {
+    const P256_HALF_ORDER: [u8; 32] = [
+        0x7f, 0xff, 0xff, 0xff, 0x80, 0x00, 0x00, 0x00, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xde, 0x73, 0x7d, 0x56, 0xd3, 0x8b, 0xcf, 0x42, 0x79, 0xdc, 0xe5, 0x61, 0x7e, 0x31,
+        0x92, 0xa8,
+    ];
+    const P384_HALF_ORDER: [u8; 48] = [
+        0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xe3, 0xb1, 0xa6, 0xc0, 0xfa, 0x1b,
+        0x96, 0xef, 0xac, 0x0d, 0x06, 0xd9, 0x24, 0x58, 0x53, 0xbd, 0x76, 0x76, 0x0c, 0xb5, 0x66,
+        0x62, 0x94, 0xb9,
+    ];
+
+    let (half_order, s) = match algorithm {
+        Algorithm::EcdsaP256Sha256Fixed if signature.len() == 64 => {
+            (&P256_HALF_ORDER[..], Some(&signature[32..]))
+        }
+        Algorithm::EcdsaP384Sha384Fixed if signature.len() == 96 => {
+            (&P384_HALF_ORDER[..], Some(&signature[48..]))
+        }
+        Algorithm::EcdsaP256Sha256Asn1 => (&P256_HALF_ORDER[..], asn1_ecdsa_s(signature)),
+        Algorithm::EcdsaP384Sha384Asn1 => (&P384_HALF_ORDER[..], asn1_ecdsa_s(signature)),
+        _ => return false,
+    };
+    let Some(s) = s else {
+        return false;
+    };
+
+    // Big-endian comparison after dropping leading zeros.
+    let s = &s[s.iter().take_while(|&&byte| byte == 0).count()..];
+    s.len() < half_order.len() || (s.len() == half_order.len() && s <= half_order)
+}
+
+/// `s` of an `ECDSA-Sig-Value ::= SEQUENCE { r INTEGER, s INTEGER }`.
+fn asn1_ecdsa_s(signature: &[u8]) -> Option<&[u8]> {
+    fn tlv(tag: u8, der: &[u8]) -> Option<(&[u8], &[u8])> {
+        let (&[actual, first], rest) = der.split_first_chunk()?;
+        let (len, rest) = match first {
+            0..=0x7f => (first as usize, rest),
+            0x81 => {
+                let (&[len], rest) = rest.split_first_chunk()?;
+                (len as usize, rest)
+            }
+            _ => return None,
+        };
+
+        (actual == tag && rest.len() >= len).then(|| rest.split_at(len))
+    }
+
+    let (sequence, []) = tlv(0x30, signature)? else {
+        return None;
+    };
+    let (_r, rest) = tlv(0x02, sequence)?;
+    let (s, []) = tlv(0x02, rest)? else {
+        return None;
+    };
+
+    Some(s)
+}
+
+/// A signature to check as part of a batch verification.
+#[derive(Clone, Copy)]
+pub struct BatchEntry<'a> {
+    /// Algorithm of the signature.
+    pub algorithm: Algorithm,
+    /// Verifying key, in the binary encoding of the algorithm, or as an
+    /// X.509 SubjectPublicKeyInfo for RSA.
+    pub public_key: &'a [u8],
+    /// Signed message.
+    pub message: &'a [u8],
+    /// Signature of the message.
+    pub signature: &'a [u8],
+}
+
+/// Outcome of a batch verification.
+#[must_use]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchVerification {
+    pub(crate) failed: Vec<usize>,
+}
+
+impl BatchVerification {
+    /// Whether every signature of the batch is valid.
+    pub fn is_valid(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    /// Indices of the entries that failed verification, in ascending order.
+    ///
+    /// Entries with malformed or unsupported keys count as failed.
+    pub fn failed(&self) -> &[usize] {
+        &self.failed
+    }
+}
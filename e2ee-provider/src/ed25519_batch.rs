@@ -0,0 +1,77 @@
+use crate::ec::signature::BatchEntry;
+use alloc::vec::Vec;
+use curve25519_dalek::{
+    EdwardsPoint, Scalar,
+    constants::ED25519_BASEPOINT_POINT,
+    edwards::CompressedEdwardsY,
+    traits::{IsIdentity, VartimeMultiscalarMul},
+};
+use sha2::{Digest, Sha512};
+
+/// Length of the random coefficient of each entry.
+pub(crate) const COEFFICIENT_LEN: usize = 16;
+
+/// Decompresses a point, accepting non-canonical encodings as ZIP-215 does.
+fn decompress(bytes: &[u8]) -> Option<EdwardsPoint> {
+    CompressedEdwardsY::from_slice(bytes).ok()?.decompress()
+}
+
+/// Checks the Ed25519 `entries` with a single multiscalar multiplication
+///
+/// `[8] ([-sum(z_i s_i)] B + sum([z_i] R_i) + sum([z_i k_i] A_i)) == 0`,
+///
+/// where the `z_i` are taken from `random`, `COEFFICIENT_LEN` bytes per entry.
+///
+/// Points are decoded and `S` is checked as in ZIP-215, so the equation holds
+/// if and only if every entry verifies alone under ZIP-215, but for a chance
+/// of `2^-128`. It does not match RFC 8032 verification, which rejects
+/// non-canonical encodings and, with the cofactorless equation, `R` and `A`
+/// with a small-order component.
+///
+/// Returns `false` if any entry is malformed or the equation does not hold;
+/// the caller then finds the invalid entries one by one.
+pub(crate) fn verify(entries: &[BatchEntry<'_>], random: &[u8]) -> bool {
+    debug_assert_eq!(random.len(), entries.len() * COEFFICIENT_LEN);
+
+    let mut scalars = Vec::with_capacity(2 * entries.len() + 1);
+    let mut points = Vec::with_capacity(2 * entries.len() + 1);
+    let mut s_sum = Scalar::ZERO;
+
+    for (entry, random) in entries.iter().zip(random.chunks_exact(COEFFICIENT_LEN)) {
+        if entry.signature.len() != 64 {
+            return false;
+        }
+
+        let (r, s) = entry.signature.split_at(32);
+        let (Some(a_point), Some(r_point)) = (decompress(entry.public_key), decompress(r)) else {
+            return false;
+        };
+        let Some(s) = Option::<Scalar>::from(Scalar::from_canonical_bytes(s.try_into().unwrap()))
+        else {
+            return false;
+        };
+
+        let k = Scalar::from_bytes_mod_order_wide(
+            &Sha512::new()
+                .chain_update(r)
+                .chain_update(entry.public_key)
+                .chain_update(entry.message)
+                .finalize()
+                .into(),
+        );
+        let mut z = [0; 32];
+        z[..COEFFICIENT_LEN].copy_from_slice(random);
+        let z = Scalar::from_bytes_mod_order(z);
+
+        s_sum += z * s;
+        scalars.extend([z, z * k]);
+        points.extend([r_point, a_point]);
+    }
+
+    scalars.push(-s_sum);
+    points.push(ED25519_BASEPOINT_POINT);
+
+    EdwardsPoint::vartime_multiscalar_mul(scalars, points)
+        .mul_by_cofactor()
+        .is_identity()
+}
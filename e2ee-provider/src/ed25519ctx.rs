@@ -0,0 +1,434 @@
+use crate::{
+    Error, digest,
+    ec::{
+        encoding::{
+            PrivateKeyBin, PrivateKeyDer, PrivateKeySerializer, PublicKeyBin, PublicKeyDer,
+            PublicKeySerializer,
+        },
+        signature::{Algorithm, SigningKey, VerifyingKey},
+    },
+};
+use alloc::{boxed::Box, vec::Vec};
+use curve25519_dalek::{EdwardsPoint, Scalar, edwards::CompressedEdwardsY};
+use sha2::{Digest, Sha512};
+use zeroize::Zeroize;
+
+/// Fills the buffer with cryptographically secure random bytes.
+pub type FillRandom = fn(&mut [u8]) -> Result<(), Error>;
+
+/// Length of an Ed25519ctx or Ed25519ph signature.
+pub const SIGNATURE_LEN: usize = 64;
+
+/// `PrivateKeyInfo` of an Ed25519 key up to the seed, RFC 8410.
+const PKCS8_PREFIX: [u8; 16] = [
+    0x30, 0x2e, 0x02, 0x01, 0x00, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x04, 0x22, 0x04, 0x20,
+];
+
+/// `SubjectPublicKeyInfo` of an Ed25519 key up to the key bytes, RFC 8410.
+const SPKI_PREFIX: [u8; 12] = [
+    0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+];
+
+/// Hashes the concatenation of `parts` after `dom2(phflag, context)`.
+///
+/// Fails for contexts longer than 255 bytes, and for empty contexts of
+/// Ed25519ctx, which RFC 8032 forbids.
+fn hash_dom2(algorithm: Algorithm, context: &[u8], parts: &[&[u8]]) -> Result<Scalar, Error> {
+    let phflag = match algorithm {
+        Algorithm::Ed25519Ctx if !context.is_empty() => 0,
+        Algorithm::Ed25519Ph => 1,
+        _ => return Err(Error::Unspecified),
+    };
+    let context_len = u8::try_from(context.len()).map_err(|_| Error::Unspecified)?;
+
+    let hash = parts.iter().fold(
+        Sha512::new()
+            .chain_update(b"SigEd25519 no Ed25519 collisions")
+            .chain_update([phflag, context_len])
+            .chain_update(context),
+        |hash, part| hash.chain_update(part),
+    );
+
+    Ok(Scalar::from_bytes_mod_order_wide(&hash.finalize().into()))
+}
+
+/// `PH(M)`: the message itself for Ed25519ctx, its SHA-512 for Ed25519ph.
+fn prehash(algorithm: Algorithm, message: &[u8]) -> Vec<u8> {
+    match algorithm {
+        Algorithm::Ed25519Ph => Sha512::digest(message).to_vec(),
+        _ => message.to_vec(),
+    }
+}
+
+/// Checks that `digest` can stand in for `PH(M)`.
+fn check_digest(algorithm: Algorithm, digest: &digest::Digest) -> Result<(), Error> {
+    match algorithm.digest_algorithm() {
+        Some(alg) if alg == digest.algorithm() => Ok(()),
+        Some(_) => Err(Error::UnsupportedDigestFunction),
+        None => Err(Error::UnsupportedSignatureAlgorithm),
+    }
+}
+
+/// Ed25519 private key that signs with a context string, as Ed25519ctx or
+/// Ed25519ph (RFC 8032).
+///
+/// The key material is that of a plain Ed25519 key.
+pub struct Ed25519CtxSigningKey {
+    seed: [u8; 32],
+    scalar: Scalar,
+    prefix: [u8; 32],
+    public_key: [u8; 32],
+    algorithm: Algorithm,
+}
+
+impl Drop for Ed25519CtxSigningKey {
+    fn drop(&mut self) {
+        self.seed.zeroize();
+        self.scalar.zeroize();
+        self.prefix.zeroize();
+    }
+}
+
+impl Ed25519CtxSigningKey {
+    /// Loads a key from its 32-byte seed.
+    ///
+    /// `algorithm` must be [`Ed25519Ctx`] or [`Ed25519Ph`].
+    ///
+    /// [`Ed25519Ctx`]: Algorithm::Ed25519Ctx
+    /// [`Ed25519Ph`]: Algorithm::Ed25519Ph
+    pub fn from_seed(algorithm: Algorithm, seed: &[u8]) -> Result<Self, Error> {
+        if !matches!(algorithm, Algorithm::Ed25519Ctx | Algorithm::Ed25519Ph) {
+            return Err(Error::UnsupportedSignatureAlgorithm);
+        }
+
+        let seed: [u8; 32] = seed.try_into().map_err(|_| Error::KeyRejected)?;
+        let mut h: [u8; 64] = Sha512::digest(seed).into();
+        let mut s: [u8; 32] = h[..32].try_into().unwrap();
+        let prefix = h[32..].try_into().unwrap();
+        let public_key = EdwardsPoint::mul_base_clamped(s).compress().to_bytes();
+        let scalar = Scalar::from_bytes_mod_order(clamp(s));
+        h.zeroize();
+        s.zeroize();
+
+        Ok(Self {
+            seed,
+            scalar,
+            prefix,
+            public_key,
+            algorithm,
+        })
+    }
+
+    /// Loads a key from PKCS#8 v1 DER.
+    pub fn from_pkcs8(algorithm: Algorithm, der: &[u8]) -> Result<Self, Error> {
+        match der.strip_prefix(&PKCS8_PREFIX[..]) {
+            Some(seed) => Self::from_seed(algorithm, seed),
+            None => Err(Error::KeyRejected),
+        }
+    }
+
+    /// Generates a new key.
+    pub fn generate(algorithm: Algorithm, fill: FillRandom) -> Result<Self, Error> {
+        let mut seed = [0; 32];
+        fill(&mut seed)?;
+        let key = Self::from_seed(algorithm, &seed);
+        seed.zeroize();
+
+        key
+    }
+
+    /// Ed25519 public key.
+    pub fn public_key(&self) -> [u8; 32] {
+        self.public_key
+    }
+
+    /// Signs `PH(M)`, the message or its SHA-512.
+    fn sign_prehashed(&self, context: &[u8], ph: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut r = hash_dom2(self.algorithm, context, &[&self.prefix, ph])?;
+        let r_point = EdwardsPoint::mul_base(&r).compress().to_bytes();
+        let k = hash_dom2(self.algorithm, context, &[&r_point, &self.public_key, ph])?;
+        let s = r + k * self.scalar;
+        r.zeroize();
+
+        Ok([&r_point[..], s.as_bytes()].concat())
+    }
+}
+
+/// Clears the cofactor bits and sets the high bit of a secret scalar.
+fn clamp(mut s: [u8; 32]) -> [u8; 32] {
+    s[0] &= 248;
+    s[31] &= 127;
+    s[31] |= 64;
+
+    s
+}
+
+impl SigningKey for Ed25519CtxSigningKey {
+    /// Signs with an empty context, which only Ed25519ph allows.
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        self.sign_with_context(b"", message)
+    }
+
+    fn sign_with_context(&self, context: &[u8], message: &[u8]) -> Result<Vec<u8>, Error> {
+        self.sign_prehashed(context, &prehash(self.algorithm, message))
+    }
+
+    fn sign_digest(&self, digest: &digest::Digest) -> Result<Vec<u8>, Error> {
+        check_digest(self.algorithm, digest)?;
+
+        self.sign_prehashed(b"", digest.as_ref())
+    }
+
+    fn compute_public_key(&self) -> Result<Box<dyn PublicKeySerializer>, Error> {
+        Ok(Box::new(Ed25519PublicKeySerializer {
+            public_key: self.public_key,
+        }))
+    }
+
+    fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    fn to_serializer(self: Box<Self>) -> Box<dyn PrivateKeySerializer> {
+        Box::new(Ed25519PrivateKeySerializer { seed: self.seed })
+    }
+}
+
+/// Ed25519 public key verifying Ed25519ctx or Ed25519ph signatures.
+pub struct Ed25519CtxVerifyingKey {
+    point: EdwardsPoint,
+    public_key: [u8; 32],
+    algorithm: Algorithm,
+}
+
+impl Ed25519CtxVerifyingKey {
+    /// Loads an Ed25519 public key.
+    ///
+    /// `algorithm` must be [`Ed25519Ctx`] or [`Ed25519Ph`].
+    ///
+    /// [`Ed25519Ctx`]: Algorithm::Ed25519Ctx
+    /// [`Ed25519Ph`]: Algorithm::Ed25519Ph
+    pub fn from_bytes(algorithm: Algorithm, public_key: &[u8]) -> Result<Self, Error> {
+        if !matches!(algorithm, Algorithm::Ed25519Ctx | Algorithm::Ed25519Ph) {
+            return Err(Error::UnsupportedSignatureAlgorithm);
+        }
+
+        let public_key: [u8; 32] = public_key.try_into().map_err(|_| Error::KeyRejected)?;
+        let point = CompressedEdwardsY(public_key)
+            .decompress()
+            .filter(|point| point.compress().to_bytes() == public_key)
+            .ok_or(Error::KeyRejected)?;
+
+        Ok(Self {
+            point,
+            public_key,
+            algorithm,
+        })
+    }
+
+    /// Loads an Ed25519 public key from X.509 `SubjectPublicKeyInfo` DER.
+    pub fn from_x509_der(algorithm: Algorithm, der: &[u8]) -> Result<Self, Error> {
+        match der.strip_prefix(&SPKI_PREFIX[..]) {
+            Some(public_key) => Self::from_bytes(algorithm, public_key),
+            None => Err(Error::KeyRejected),
+        }
+    }
+
+    /// Verifies a signature of `PH(M)` with the cofactorless equation,
+    /// `[S] B == R + [k] A`.
+    fn verify_prehashed(&self, context: &[u8], ph: &[u8], signature: &[u8]) -> Result<(), Error> {
+        if signature.len() != SIGNATURE_LEN {
+            return Err(Error::BadSignature);
+        }
+
+        let (r_bytes, s_bytes) = signature.split_at(32);
+        let Some(s) = Scalar::from_canonical_bytes(s_bytes.try_into().unwrap()).into_option()
+        else {
+            return Err(Error::NonCanonicalSignature);
+        };
+        let k = hash_dom2(self.algorithm, context, &[r_bytes, &self.public_key, ph])?;
+
+        if EdwardsPoint::vartime_double_scalar_mul_basepoint(&-k, &self.point, &s)
+            .compress()
+            .as_bytes()
+            == r_bytes
+        {
+            Ok(())
+        } else {
+            Err(Error::BadSignature)
+        }
+    }
+}
+
+impl VerifyingKey for Ed25519CtxVerifyingKey {
+    /// Verifies with an empty context, which only Ed25519ph allows.
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), Error> {
+        self.verify_with_context(b"", message, signature)
+    }
+
+    fn verify_with_context(
+        &self,
+        context: &[u8],
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Error> {
+        self.verify_prehashed(context, &prehash(self.algorithm, message), signature)
+    }
+
+    fn verify_digest(&self, digest: &digest::Digest, signature: &[u8]) -> Result<(), Error> {
+        check_digest(self.algorithm, digest)?;
+
+        self.verify_prehashed(b"", digest.as_ref(), signature)
+    }
+
+    fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    fn to_serializer(self: Box<Self>) -> Box<dyn PublicKeySerializer> {
+        Box::new(Ed25519PublicKeySerializer {
+            public_key: self.public_key,
+        })
+    }
+}
+
+struct Ed25519PublicKeySerializer {
+    public_key: [u8; 32],
+}
+
+impl PublicKeySerializer for Ed25519PublicKeySerializer {
+    fn as_x509_der(&self) -> Result<PublicKeyDer, Error> {
+        Ok(PublicKeyDer::X509Key(
+            [&SPKI_PREFIX[..], &self.public_key].concat(),
+        ))
+    }
+
+    fn as_rfc_5915_public_key_der(&self) -> Result<PublicKeyDer, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_be_bytes(&self) -> Result<PublicKeyBin, Error> {
+        Ok(PublicKeyBin::Uncompreessed(self.public_key.to_vec()))
+    }
+
+    fn as_compressed_be_bytes(&self) -> Result<PublicKeyBin, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+}
+
+struct Ed25519PrivateKeySerializer {
+    seed: [u8; 32],
+}
+
+impl Drop for Ed25519PrivateKeySerializer {
+    fn drop(&mut self) {
+        self.seed.zeroize();
+    }
+}
+
+impl PrivateKeySerializer for Ed25519PrivateKeySerializer {
+    fn as_pkcs8v1_der(&self) -> Result<PrivateKeyDer, Error> {
+        Ok(PrivateKeyDer::Pkcs8V1Key(
+            [&PKCS8_PREFIX[..], &self.seed].concat(),
+        ))
+    }
+
+    fn as_pkcs8v2_der(&self) -> Result<PrivateKeyDer, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_rfc_5915_private_key_der(&self) -> Result<PrivateKeyDer, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_ed_ec_be_bytes(&self) -> Result<PrivateKeyBin, Error> {
+        Ok(PrivateKeyBin::EdEcSeed(self.seed.to_vec()))
+    }
+
+    fn as_ec_be_bytes(&self) -> Result<PrivateKeyBin, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_raw_bytes(&self) -> Result<PrivateKeyBin, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn test() -> Result<(), Error> {
+    // RFC 8032, sections 7.2 (context "foo") and 7.3.
+    let key = Ed25519CtxSigningKey::from_seed(
+        Algorithm::Ed25519Ctx,
+        &hex::decode("0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6").unwrap(),
+    )?;
+    let message = hex::decode("f726936d19c800494e3fdaff20b276a8").unwrap();
+    let signature = key.sign_with_context(b"foo", &message)?;
+
+    assert_eq!(
+        key.public_key().to_vec(),
+        hex::decode("dfc9425e4f968f7f0c29f0259cf5f9aed6851c2bb4ad8bfb860cfee0ab248292").unwrap()
+    );
+    assert_eq!(
+        signature,
+        hex::decode(
+            "55a4cc2f70a54e04288c5f4cd1e45a7bb520b36292911876cada7323198dd87a\
+             8b36950b95130022907a7fb7c4e9b2d5f6cca685a587b4b21f4b888e4e7edb0d"
+        )
+        .unwrap()
+    );
+
+    let verifying_key =
+        Ed25519CtxVerifyingKey::from_bytes(Algorithm::Ed25519Ctx, &key.public_key())?;
+    verifying_key.verify_with_context(b"foo", &message, &signature)?;
+    assert!(
+        verifying_key
+            .verify_with_context(b"bar", &message, &signature)
+            .is_err()
+    );
+    assert!(verifying_key.verify(&message, &signature).is_err());
+    assert!(key.sign(&message).is_err());
+    assert!(key.sign_with_context(&[0; 256], &message).is_err());
+
+    let key = Ed25519CtxSigningKey::from_seed(
+        Algorithm::Ed25519Ph,
+        &hex::decode("833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42").unwrap(),
+    )?;
+    let signature = key.sign(b"abc")?;
+
+    assert_eq!(
+        signature,
+        hex::decode(
+            "98a70222f0b8121aa9d30f813d683f809e462b469c7ff87639499bb94e6dae41\
+             31f85042463c2a355a2003d062adf5aaa10b8c61e636062aaad11c2a26083406"
+        )
+        .unwrap()
+    );
+
+    let digest = digest::Digest::new(digest::Algorithm::Sha512, &Sha512::digest(b"abc"))?;
+    let verifying_key = Ed25519CtxVerifyingKey::from_x509_der(
+        Algorithm::Ed25519Ph,
+        key.compute_public_key()?.as_x509_der()?.as_ref(),
+    )?;
+
+    assert_eq!(key.sign_digest(&digest)?, signature);
+    verifying_key.verify_digest(&digest, &signature)?;
+    verifying_key.verify_with_context(b"", b"abc", &signature)?;
+    assert!(
+        verifying_key
+            .verify_with_context(b"foo", b"abc", &signature)
+            .is_err()
+    );
+
+    // Ed25519ph signatures do not verify as Ed25519ctx ones and vice versa.
+    let signature = key.sign_with_context(b"foo", b"abc")?;
+    let verifying_key =
+        Ed25519CtxVerifyingKey::from_bytes(Algorithm::Ed25519Ctx, &key.public_key())?;
+    assert!(
+        verifying_key
+            .verify_with_context(b"foo", b"abc", &signature)
+            .is_err()
+    );
+
+    Ok(())
+}
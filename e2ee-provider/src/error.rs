@@ -13,14 +13,26 @@ pub enum Error {
     UnsupportedAgreementAlgorithm,
     /// The digital signature algorithm is not supported by the provider.
     UnsupportedSignatureAlgorithm,
+    /// The key encapsulation mechanism is not supported by the provider.
+    UnsupportedKemAlgorithm,
     /// The digest function is not supported by the provider.
     UnsupportedDigestFunction,
     /// The key derivation function is not supported by the provider.
     UnsupportedHkdf,
+    /// The key wrapping algorithm is not supported by the provider.
+    UnsupportedKeyWrapAlgorithm,
+    /// The AEAD algorithm is not supported by the provider.
+    UnsupportedAeadAlgorithm,
     /// The elliptic curve is not supported by the provider.
     UnsupportedCurve,
     /// An error parsing or validating a key.
     KeyRejected,
+    /// The signature does not match the message and the key.
+    BadSignature,
+    /// The signature, or the key it is checked with, has an encoding the
+    /// verification strictness does not accept, such as a high ECDSA `s` or a
+    /// non-canonical Ed25519 point.
+    NonCanonicalSignature,
 }
 
 #[cfg(feature = "aws_lc_rs")]
@@ -0,0 +1,340 @@
+use crate::{
+    Error, aead,
+    digest::{self, Digest},
+    ec::{
+        agreement,
+        encoding::{PrivateKeyBin, PrivateKeyDer, PublicKeyBin, PublicKeyDer},
+    },
+    hkdf::{self, MAX_OKM_LEN, Okm},
+    key_wrap,
+    provider::{AeadProvider, HashProvider, HkdfProvider, KeyWrapProvider, Provider},
+};
+use alloc::boxed::Box;
+
+/// A cryptographic hash function with a concrete context type.
+pub trait Hash {
+    /// Incremental hash computation of the function.
+    type Context: Context + 'static;
+
+    /// Starts an incremental hash computation.
+    fn start(&self) -> Self::Context;
+
+    /// Computes and returns the digest of the given data.
+    fn hash(&self, data: &[u8]) -> Digest;
+
+    /// Computes the digest of the concatenation of `parts`.
+    fn hash_parts(&self, parts: &[&[u8]]) -> Digest {
+        let mut context = self.start();
+        for part in parts {
+            context.update(part);
+        }
+
+        context.finish()
+    }
+
+    /// Returns the digest of data.
+    fn algorithm(&self) -> digest::Algorithm;
+}
+
+/// Incremental hash computation context, finished by value.
+pub trait Context {
+    /// Adds data to the current hash computation.
+    fn update(&mut self, data: &[u8]);
+
+    /// Finalizes the computation and returns the resulting digest.
+    fn finish(self) -> Digest;
+
+    /// Algorithm used in this hash computation.
+    fn algorithm(&self) -> digest::Algorithm;
+}
+
+/// A HKDF implementation with a concrete expander type.
+pub trait Hkdf {
+    /// Expander holding the pseudorandom key.
+    type Expander: Expander + 'static;
+
+    /// `HKDF-Extract(salt, secret)`
+    fn extract(&self, salt: &[u8], secret: &[u8]) -> Self::Expander;
+
+    /// `HKDF-Extract(salt, secret)`, returning the pseudorandom key itself.
+    fn extract_prk(&self, salt: &[u8], secret: &[u8]) -> Okm;
+
+    /// Returns an [`Expander`] using `prk` as the pseudorandom key.
+    fn expander(&self, prk: &[u8]) -> Self::Expander;
+}
+
+/// Implementation of `HKDF-Expand` using an internally stored pseudorandom key
+/// (PRK).
+pub trait Expander {
+    /// `HKDF-Expand(PRK, info, L)` where L is `len`
+    ///
+    /// Returns [`Error::Unspecified`] if L is larger than [`MAX_OKM_LEN`].
+    fn expand(&self, info: &[&[u8]], len: usize) -> Result<Okm, Error> {
+        if len > MAX_OKM_LEN {
+            return Err(Error::Unspecified);
+        }
+
+        let mut okm = Okm {
+            buf: [0; MAX_OKM_LEN],
+            len,
+        };
+        self.expand_into(info, &mut okm.buf[..len])?;
+
+        Ok(okm)
+    }
+
+    /// `HKDF-Expand(PRK, info, L)` into `output`, where L is `output.len()`
+    ///
+    /// Returns [`Error::Unspecified`] if L is larger than `255*HashLen`.
+    fn expand_into(&self, info: &[&[u8]], output: &mut [u8]) -> Result<(), Error>;
+}
+
+/// Cryptographic functions of a backend, known at compile time.
+///
+/// Protocol code generic over a `CryptoProvider` is monomorphized for the
+/// backend, so hash contexts, expanders and key agreement keys live on the
+/// stack and calls can be inlined. Every implementation is also a dyn
+/// [`crate::CryptoProvider`] member, see
+/// [`crate::CryptoProvider::from_generic`].
+///
+/// Only hashing, HKDF, key wrapping, AEAD and key agreement have concrete
+/// types. Signing keys and KEMs span many algorithm-specific key types per
+/// backend, so they stay behind the boxed [`KeyProvider`] and KEM interfaces,
+/// as does the peer public key passed to [`agreement::PrivateKey::agree`].
+///
+/// [`KeyProvider`]: crate::ec::KeyProvider
+pub trait CryptoProvider: Send + Sync {
+    /// Hash functions of the provider.
+    type Hash: Hash + 'static;
+    /// HKDF implementations of the provider.
+    type Hkdf: Hkdf + 'static;
+    /// Key wrapping algorithms of the provider.
+    type KeyWrap: key_wrap::KeyWrap + 'static;
+    /// AEAD algorithms of the provider.
+    type Aead: aead::Aead + 'static;
+    /// Private keys for key agreement.
+    type PrivateKey: agreement::PrivateKey + 'static;
+    /// Ephemeral private keys for key agreement.
+    type EphemeralPrivateKey: agreement::EphemeralPrivateKey + 'static;
+    /// Public keys for key agreement.
+    type PublicKey: agreement::PublicKey + 'static;
+
+    /// Returns the hash function, if supported.
+    fn hash(&self, algorithm: digest::Algorithm) -> Option<&'static Self::Hash>;
+
+    /// Returns the HKDF, if supported.
+    fn hkdf(&self, algorithm: hkdf::Algorithm) -> Option<&'static Self::Hkdf>;
+
+    /// Returns the key wrapping algorithm, if supported.
+    fn key_wrap(&self, algorithm: key_wrap::Algorithm) -> Option<&'static Self::KeyWrap>;
+
+    /// Returns the AEAD, if supported.
+    fn aead(&self, algorithm: aead::Algorithm) -> Option<&'static Self::Aead>;
+
+    /// Returns all supported hash functions, in order of preference.
+    fn supported_hash_algorithms(&self) -> &'static [digest::Algorithm];
+
+    /// Returns all supported HKDF algorithms, in order of preference.
+    fn supported_hkdf_algorithms(&self) -> &'static [hkdf::Algorithm];
+
+    /// Returns all supported key wrapping algorithms, in order of preference.
+    fn supported_key_wrap_algorithms(&self) -> &'static [key_wrap::Algorithm];
+
+    /// Returns all supported AEAD algorithms, in order of preference.
+    fn supported_aead_algorithms(&self) -> &'static [aead::Algorithm];
+
+    /// Loads a private (agreement) key from DER.
+    fn load_private_key_der(
+        &self,
+        algorithm: agreement::Algorithm,
+        der: &PrivateKeyDer,
+    ) -> Result<Self::PrivateKey, Error>;
+
+    /// Loads a private (agreement) key from big-endian bytes.
+    fn load_private_key_bin(
+        &self,
+        algorithm: agreement::Algorithm,
+        bin: &PrivateKeyBin,
+    ) -> Result<Self::PrivateKey, Error>;
+
+    /// Loads a public (agreement) key from DER.
+    fn load_public_key_der(
+        &self,
+        algorithm: agreement::Algorithm,
+        der: &PublicKeyDer,
+    ) -> Result<Self::PublicKey, Error>;
+
+    /// Loads a public (agreement) key from big-endian bytes.
+    fn load_public_key_bin(
+        &self,
+        algorithm: agreement::Algorithm,
+        bin: &PublicKeyBin,
+    ) -> Result<Self::PublicKey, Error>;
+
+    /// Generates a new ephemeral private key.
+    fn generate_ephemeral_private_key(
+        &self,
+        algorithm: agreement::Algorithm,
+    ) -> Result<Self::EphemeralPrivateKey, Error>;
+}
+
+impl<C: Context> digest::Context for C {
+    fn update(&mut self, data: &[u8]) {
+        Context::update(self, data);
+    }
+
+    fn finish(self: Box<Self>) -> Digest {
+        Context::finish(*self)
+    }
+
+    fn algorithm(&self) -> digest::Algorithm {
+        Context::algorithm(self)
+    }
+}
+
+impl<H: Hash> digest::Hash for H {
+    fn start(&self) -> Box<dyn digest::Context> {
+        Box::new(Hash::start(self))
+    }
+
+    fn hash(&self, data: &[u8]) -> Digest {
+        Hash::hash(self, data)
+    }
+
+    fn hash_parts(&self, parts: &[&[u8]]) -> Digest {
+        Hash::hash_parts(self, parts)
+    }
+
+    fn algorithm(&self) -> digest::Algorithm {
+        Hash::algorithm(self)
+    }
+}
+
+impl<E: Expander> hkdf::Expander for E {
+    fn expand(&self, info: &[&[u8]], len: usize) -> Result<Okm, Error> {
+        Expander::expand(self, info, len)
+    }
+
+    fn expand_into(&self, info: &[&[u8]], output: &mut [u8]) -> Result<(), Error> {
+        Expander::expand_into(self, info, output)
+    }
+}
+
+impl<H: Hkdf> hkdf::Hkdf for H {
+    fn extract(&self, salt: &[u8], secret: &[u8]) -> Box<dyn hkdf::Expander> {
+        Box::new(Hkdf::extract(self, salt, secret))
+    }
+
+    fn extract_prk(&self, salt: &[u8], secret: &[u8]) -> Okm {
+        Hkdf::extract_prk(self, salt, secret)
+    }
+
+    fn expander(&self, prk: &[u8]) -> Box<dyn hkdf::Expander> {
+        Box::new(Hkdf::expander(self, prk))
+    }
+
+    fn expand_prk_into(&self, prk: &[u8], info: &[&[u8]], output: &mut [u8]) -> Result<(), Error> {
+        Hkdf::expander(self, prk).expand_into(info, output)
+    }
+}
+
+macro_rules! impl_dyn_providers {
+    ($($module:ident, $trait:ident, $get:ident, $supported:ident, $dyn_provider:ident);*) => {
+        $(
+            impl<P: CryptoProvider> Provider<$module::Algorithm, &'static dyn $module::$trait>
+                for P
+            {
+                fn get(&self, algorithm: $module::Algorithm) -> Option<&'static dyn $module::$trait> {
+                    self.$get(algorithm).map(|handler| handler as &'static dyn $module::$trait)
+                }
+
+                fn supported_algorithms(&self) -> &'static [$module::Algorithm] {
+                    self.$supported()
+                }
+
+                fn is_algorithm_supported(&self, algorithm: $module::Algorithm) -> bool {
+                    self.$get(algorithm).is_some()
+                }
+            }
+
+            impl<P: CryptoProvider> $dyn_provider for P {}
+        )*
+    };
+}
+
+impl_dyn_providers!(
+    digest, Hash, hash, supported_hash_algorithms, HashProvider;
+    hkdf, Hkdf, hkdf, supported_hkdf_algorithms, HkdfProvider;
+    key_wrap, KeyWrap, key_wrap, supported_key_wrap_algorithms, KeyWrapProvider;
+    aead, Aead, aead, supported_aead_algorithms, AeadProvider
+);
+
+#[test]
+#[cfg(all(test, feature = "aws_lc_rs"))]
+fn test() -> Result<(), Error> {
+    use crate::aws_lc_rs::AwsLcRs;
+
+    fn derive<P: CryptoProvider>(provider: &P, secret: &[u8]) -> Result<Okm, Error> {
+        let hash = provider.hash(digest::Algorithm::Sha256).unwrap();
+        let hkdf = provider.hkdf(hkdf::Algorithm::Sha256).unwrap();
+
+        let mut context = hash.start();
+        context.update(b"generic ");
+        context.update(secret);
+        let digest = context.finish();
+
+        hkdf.extract(digest.as_ref(), secret).expand(&[b"info"], 42)
+    }
+
+    fn agree<P: CryptoProvider>(provider: &P) -> Result<[agreement::SharedSecret; 2], Error> {
+        use agreement::{EphemeralPrivateKey, PrivateKey};
+
+        let algorithm = agreement::Algorithm::X25519;
+        let private_key = provider
+            .load_private_key_bin(algorithm, &PrivateKeyBin::EdEcSeed(alloc::vec![0x42; 32]))?;
+        let ephemeral_key = provider.generate_ephemeral_private_key(algorithm)?;
+        let public_key = provider
+            .load_public_key_bin(algorithm, &private_key.compute_public_key()?.as_be_bytes()?)?;
+        let ephemeral_public_key = provider.load_public_key_bin(
+            algorithm,
+            &ephemeral_key.compute_public_key()?.as_be_bytes()?,
+        )?;
+
+        Ok([
+            private_key.agree(Box::new(ephemeral_public_key))?,
+            Box::new(ephemeral_key).agree_ephemeral(Box::new(public_key))?,
+        ])
+    }
+
+    static PROVIDER: AwsLcRs = AwsLcRs;
+    let dyn_provider = crate::CryptoProvider::from_generic(&PROVIDER);
+
+    let digest = dyn_provider
+        .hash
+        .get(digest::Algorithm::Sha256)
+        .unwrap()
+        .hash_parts(&[b"generic ", b"secret"]);
+    let expected = dyn_provider
+        .hkdf
+        .get(hkdf::Algorithm::Sha256)
+        .unwrap()
+        .extract(digest.as_ref(), b"secret")
+        .expand(&[b"info"], 42)?;
+
+    assert_eq!(derive(&AwsLcRs, b"secret")?.as_ref(), expected.as_ref());
+
+    let [secret, ephemeral_secret] = agree(&AwsLcRs)?;
+    assert_eq!(secret.as_ref(), ephemeral_secret.as_ref());
+    assert_eq!(
+        PROVIDER.supported_aead_algorithms(),
+        dyn_provider.aead.supported_algorithms()
+    );
+    assert!(PROVIDER.hash(digest::Algorithm::Sha3_224).is_none());
+    assert!(
+        !dyn_provider
+            .hash
+            .is_algorithm_supported(digest::Algorithm::Sha3_224)
+    );
+
+    Ok(())
+}
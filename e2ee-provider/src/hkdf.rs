@@ -1,7 +1,12 @@
 use crate::Error;
-use alloc::{boxed::Box, vec::Vec};
+use crate::digest::MAX_OUTPUT_LEN;
+use alloc::boxed::Box;
 use zeroize::Zeroize;
 
+/// Length of the longest [`Okm`], enough for two keys of the largest hash
+/// output. Longer outputs are written with [`Expander::expand_into`].
+pub const MAX_OKM_LEN: usize = 2 * MAX_OUTPUT_LEN;
+
 /// Supported HKDF algorithms.
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -33,20 +38,105 @@ pub trait Hkdf {
     ///
     /// Returns an [`Expander`] that can be used to derive keying material.
     fn extract(&self, salt: &[u8], secret: &[u8]) -> Box<dyn Expander>;
+
+    /// `HKDF-Extract(salt, secret)`
+    ///
+    /// Returns the pseudorandom key itself, for protocols that use it as
+    /// input rather than only expanding it, e.g. HPKE's `psk_id_hash`.
+    fn extract_prk(&self, salt: &[u8], secret: &[u8]) -> Okm;
+
+    /// Returns an [`Expander`] using `prk` as the pseudorandom key.
+    ///
+    /// `prk` should be the output of `HKDF-Extract` or `HKDF-Expand` with the
+    /// same hash function.
+    fn expander(&self, prk: &[u8]) -> Box<dyn Expander>;
+
+    /// `HKDF-Expand(prk, info, L)` into `output`, where L is `output.len()`
+    ///
+    /// With [`extract_prk`], derives keys without boxing an [`Expander`].
+    /// Providers override this default, which boxes one.
+    ///
+    /// [`extract_prk`]: Hkdf::extract_prk
+    fn expand_prk_into(&self, prk: &[u8], info: &[&[u8]], output: &mut [u8]) -> Result<(), Error> {
+        self.expander(prk).expand_into(info, output)
+    }
+
+    /// `HKDF-Expand(prk, info, L)` where L is `len`, see
+    /// [`expand_prk_into`].
+    ///
+    /// Returns [`Error::Unspecified`] if L is larger than [`MAX_OKM_LEN`].
+    ///
+    /// [`expand_prk_into`]: Hkdf::expand_prk_into
+    fn expand_prk(&self, prk: &[u8], info: &[&[u8]], len: usize) -> Result<Okm, Error> {
+        if len > MAX_OKM_LEN {
+            return Err(Error::Unspecified);
+        }
+
+        let mut okm = Okm {
+            buf: [0; MAX_OKM_LEN],
+            len,
+        };
+        self.expand_prk_into(prk, info, &mut okm.buf[..len])?;
+
+        Ok(okm)
+    }
 }
 
 /// Implementation of `HKDF-Expand` using an internally stored pseudorandom key
 /// (PRK).
 pub trait Expander {
-    /// `HKDF-Expand(PRK, info, L)` where L is output.len()
+    /// `HKDF-Expand(PRK, info, L)` where L is `len`
+    ///
+    /// Returns [`Error::Unspecified`] if L is larger than [`MAX_OKM_LEN`],
+    /// even though `HKDF-Expand` allows up to `255*HashLen`; use
+    /// [`expand_into`] for longer outputs.
+    ///
+    /// [`expand_into`]: Expander::expand_into
+    fn expand(&self, info: &[&[u8]], len: usize) -> Result<Okm, Error> {
+        if len > MAX_OKM_LEN {
+            return Err(Error::Unspecified);
+        }
+
+        let mut okm = Okm {
+            buf: [0; MAX_OKM_LEN],
+            len,
+        };
+        self.expand_into(info, &mut okm.buf[..len])?;
+
+        Ok(okm)
+    }
+
+    /// `HKDF-Expand(PRK, info, L)` into `output`, where L is `output.len()`
     ///
     /// Returns [`Error::Unspecified`] if L is larger than `255*HashLen`.
-    fn expand(&self, info: &[&[u8]], len: usize) -> Result<Okm, Error>;
+    fn expand_into(&self, info: &[&[u8]], output: &mut [u8]) -> Result<(), Error>;
 }
 
 /// Output keying material (OKM).
+///
+/// Kept in a buffer of [`MAX_OKM_LEN`] bytes, so no heap allocation is
+/// needed.
 pub struct Okm {
-    pub(crate) buf: Vec<u8>,
+    pub(crate) buf: [u8; MAX_OKM_LEN],
+    pub(crate) len: usize,
+}
+
+impl Okm {
+    /// Wraps derived key material.
+    ///
+    /// Returns [`Error::Unspecified`] if `bytes` is longer than
+    /// [`MAX_OKM_LEN`].
+    pub fn new(bytes: &[u8]) -> Result<Self, Error> {
+        let mut buf = [0; MAX_OKM_LEN];
+        buf.get_mut(..bytes.len())
+            .ok_or(Error::Unspecified)?
+            .copy_from_slice(bytes);
+
+        Ok(Self {
+            buf,
+            len: bytes.len(),
+        })
+    }
 }
 
 impl Drop for Okm {
@@ -57,6 +147,6 @@ impl Drop for Okm {
 
 impl AsRef<[u8]> for Okm {
     fn as_ref(&self) -> &[u8] {
-        &self.buf
+        &self.buf[..self.len]
     }
 }
@@ -0,0 +1,138 @@
+use super::{labeled_expand, labeled_expand_into};
+use crate::{
+    Error,
+    aead::{Aead, Plaintext},
+    hkdf::{Expander, Okm},
+};
+use alloc::{boxed::Box, vec::Vec};
+
+/// State shared by the sender and the recipient contexts.
+pub(super) struct Context {
+    pub(super) aead: Option<&'static dyn Aead>,
+    pub(super) key: Okm,
+    pub(super) base_nonce: Okm,
+    pub(super) seq: u64,
+    pub(super) exporter: Box<dyn Expander>,
+    pub(super) suite_id: [u8; 10],
+}
+
+impl Context {
+    /// `ComputeNonce(seq)`, failing if the sequence number would overflow.
+    fn next_nonce(&self) -> Result<Vec<u8>, Error> {
+        if self.seq == u64::MAX {
+            return Err(Error::Unspecified);
+        }
+
+        let mut nonce = self.base_nonce.as_ref().to_vec();
+        let offset = nonce.len() - 8;
+        nonce[offset..]
+            .iter_mut()
+            .zip(self.seq.to_be_bytes())
+            .for_each(|(nonce, seq)| *nonce ^= seq);
+
+        Ok(nonce)
+    }
+
+    fn aead(&self) -> Result<&'static dyn Aead, Error> {
+        self.aead.ok_or(Error::UnsupportedAeadAlgorithm)
+    }
+
+    fn export(&self, exporter_context: &[u8], len: usize) -> Result<Okm, Error> {
+        labeled_expand(
+            &*self.exporter,
+            &self.suite_id,
+            b"sec",
+            exporter_context,
+            len,
+        )
+    }
+
+    fn export_into(&self, exporter_context: &[u8], output: &mut [u8]) -> Result<(), Error> {
+        labeled_expand_into(
+            &*self.exporter,
+            &self.suite_id,
+            b"sec",
+            exporter_context,
+            output,
+        )
+    }
+}
+
+/// Encryption context of the sender, created by one of the `setup_*_sender`
+/// functions of [`Hpke`].
+///
+/// [`Hpke`]: super::Hpke
+pub struct SenderContext(pub(super) Context);
+
+impl SenderContext {
+    /// Encrypts `plaintext` and authenticates `aad` with the next nonce of
+    /// the context.
+    ///
+    /// Returns [`Error::UnsupportedAeadAlgorithm`] for export-only suites and
+    /// [`Error::Unspecified`] once the sequence number is exhausted.
+    pub fn seal(&mut self, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+        let aead = self.0.aead()?;
+        let nonce = self.0.next_nonce()?;
+        let ciphertext = aead.seal(self.0.key.as_ref(), &nonce, aad, plaintext)?;
+        self.0.seq += 1;
+
+        Ok(ciphertext)
+    }
+
+    /// Derives `len` bytes of secret from the context, bound to
+    /// `exporter_context`.
+    ///
+    /// `len` is at most [`MAX_OKM_LEN`]; use [`export_into`] for longer
+    /// secrets.
+    ///
+    /// [`MAX_OKM_LEN`]: crate::hkdf::MAX_OKM_LEN
+    /// [`export_into`]: Self::export_into
+    pub fn export(&self, exporter_context: &[u8], len: usize) -> Result<Okm, Error> {
+        self.0.export(exporter_context, len)
+    }
+
+    /// Derives a secret filling `output` from the context, bound to
+    /// `exporter_context`.
+    pub fn export_into(&self, exporter_context: &[u8], output: &mut [u8]) -> Result<(), Error> {
+        self.0.export_into(exporter_context, output)
+    }
+}
+
+/// Decryption context of the recipient, created by one of the
+/// `setup_*_recipient` functions of [`Hpke`].
+///
+/// [`Hpke`]: super::Hpke
+pub struct RecipientContext(pub(super) Context);
+
+impl RecipientContext {
+    /// Decrypts `ciphertext` and authenticates `aad` with the next nonce of
+    /// the context.
+    ///
+    /// The sequence number is only advanced if authentication succeeds.
+    pub fn open(&mut self, aad: &[u8], ciphertext: &[u8]) -> Result<Plaintext, Error> {
+        let aead = self.0.aead()?;
+        let nonce = self.0.next_nonce()?;
+        let plaintext = aead.open(self.0.key.as_ref(), &nonce, aad, ciphertext)?;
+        self.0.seq += 1;
+
+        Ok(plaintext)
+    }
+
+    /// Derives `len` bytes of secret from the context, bound to
+    /// `exporter_context`.
+    ///
+    /// `len` is at most [`MAX_OKM_LEN`]; use [`export_into`] for longer
+    /// secrets.
+    ///
+    /// [`MAX_OKM_LEN`]: crate::hkdf::MAX_OKM_LEN
+    /// [`export_into`]: Self::export_into
+    pub fn export(&self, exporter_context: &[u8], len: usize) -> Result<Okm, Error> {
+        self.0.export(exporter_context, len)
+    }
+
+    /// Derives a secret filling `output` from the context, bound to
+    /// `exporter_context`.
+    pub fn export_into(&self, exporter_context: &[u8], output: &mut [u8]) -> Result<(), Error> {
+        self.0.export_into(exporter_context, output)
+    }
+}
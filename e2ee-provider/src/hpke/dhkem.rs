@@ -0,0 +1,156 @@
+//! DH-based KEM (DHKEM), RFC 9180 section 4.1.
+
+use super::{Hpke, Kem, labeled_expand, labeled_extract};
+use crate::{
+    Error,
+    ec::{
+        KeyProvider,
+        agreement::{PrivateKey, PublicKey, SharedSecret},
+        encoding::{PrivateKeyBin, PublicKeyBin, PublicKeySerializer},
+    },
+    hkdf::Okm,
+};
+use alloc::{boxed::Box, vec::Vec};
+use zeroize::Zeroizing;
+
+/// `SerializePublicKey(pk)`
+pub(super) fn serialize_public_key(pk: Box<dyn PublicKeySerializer>) -> Result<Vec<u8>, Error> {
+    Ok(pk.as_be_bytes()?.as_ref().to_vec())
+}
+
+impl Kem {
+    fn suite_id(&self) -> [u8; 5] {
+        let [hi, lo] = (*self as u16).to_be_bytes();
+
+        [b'K', b'E', b'M', hi, lo]
+    }
+
+    /// Bitmask applied to the first byte of candidate NIST private keys.
+    fn bitmask(&self) -> u8 {
+        match self {
+            Self::DhKemP521HkdfSha512 => 0x01,
+            _ => 0xff,
+        }
+    }
+}
+
+impl<K: KeyProvider> Hpke<K> {
+    /// `DeserializePublicKey(pkXm)`
+    pub(super) fn deserialize_public_key(&self, pk: &[u8]) -> Result<Box<dyn PublicKey>, Error> {
+        K::load_public_key_bin(
+            self.suite.kem.agreement_algorithm(),
+            &PublicKeyBin::Uncompreessed(pk.to_vec()),
+        )
+    }
+
+    /// `DeriveKeyPair(ikm)`
+    ///
+    /// Deterministically derives a private key of the KEM from `ikm`, which
+    /// must have at least as many bytes of entropy as the private key.
+    pub fn derive_key_pair(&self, ikm: &[u8]) -> Result<Box<dyn PrivateKey>, Error> {
+        let kem = self.suite.kem;
+        let suite_id = kem.suite_id();
+        let algorithm = kem.agreement_algorithm();
+        let dkp_prk = labeled_extract(self.kem_hkdf, &suite_id, b"", b"dkp_prk", ikm);
+        let expander = self.kem_hkdf.expander(dkp_prk.as_ref());
+
+        match kem {
+            Kem::DhKemX25519HkdfSha256 | Kem::DhKemX448HkdfSha512 => {
+                let sk = labeled_expand(&*expander, &suite_id, b"sk", b"", kem.private_key_len())?;
+
+                K::load_private_key_bin(algorithm, &PrivateKeyBin::EdEcSeed(sk.as_ref().to_vec()))
+            }
+            Kem::DhKemP256HkdfSha256 | Kem::DhKemP384HkdfSha384 | Kem::DhKemP521HkdfSha512 => {
+                for counter in 0..=255u8 {
+                    let mut bytes = labeled_expand(
+                        &*expander,
+                        &suite_id,
+                        b"candidate",
+                        &[counter],
+                        kem.private_key_len(),
+                    )?
+                    .as_ref()
+                    .to_vec();
+                    bytes[0] &= kem.bitmask();
+
+                    if let Ok(sk) = K::load_private_key_bin(algorithm, &PrivateKeyBin::Ec(bytes)) {
+                        return Ok(sk);
+                    }
+                }
+
+                Err(Error::KeyRejected)
+            }
+        }
+    }
+
+    /// `ExtractAndExpand(dh, kem_context)`
+    fn extract_and_expand(&self, dh: &[u8], kem_context: &[u8]) -> Result<Okm, Error> {
+        let suite_id = self.suite.kem.suite_id();
+        let eae_prk = labeled_extract(self.kem_hkdf, &suite_id, b"", b"eae_prk", dh);
+
+        labeled_expand(
+            &*self.kem_hkdf.expander(eae_prk.as_ref()),
+            &suite_id,
+            b"shared_secret",
+            kem_context,
+            self.suite.kem.shared_secret_len(),
+        )
+    }
+
+    /// `Encap(pkR)` and `AuthEncap(pkR, skS)`
+    ///
+    /// `agree_e` performs the key agreement of the ephemeral key whose
+    /// serialized public key is `enc`.
+    pub(super) fn encap(
+        &self,
+        pk_r: &[u8],
+        sk_s: Option<&dyn PrivateKey>,
+        enc: &[u8],
+        agree_e: impl FnOnce(Box<dyn PublicKey>) -> Result<SharedSecret, Error>,
+    ) -> Result<Okm, Error> {
+        // Zeroized on every return, including errors of the second agreement.
+        let mut dh = Zeroizing::new(
+            agree_e(self.deserialize_public_key(pk_r)?)?
+                .as_ref()
+                .to_vec(),
+        );
+        let mut kem_context = [enc, pk_r].concat();
+
+        if let Some(sk_s) = sk_s {
+            if sk_s.algorithm() != self.suite.kem.agreement_algorithm() {
+                return Err(Error::KeyRejected);
+            }
+
+            dh.extend_from_slice(sk_s.agree(self.deserialize_public_key(pk_r)?)?.as_ref());
+            kem_context.extend(serialize_public_key(sk_s.compute_public_key()?)?);
+        }
+
+        self.extract_and_expand(&dh, &kem_context)
+    }
+
+    /// `Decap(enc, skR)` and `AuthDecap(enc, skR, pkS)`
+    pub(super) fn decap(
+        &self,
+        enc: &[u8],
+        sk_r: &dyn PrivateKey,
+        pk_s: Option<&[u8]>,
+    ) -> Result<Okm, Error> {
+        if sk_r.algorithm() != self.suite.kem.agreement_algorithm() {
+            return Err(Error::KeyRejected);
+        }
+
+        let mut dh = Zeroizing::new(
+            sk_r.agree(self.deserialize_public_key(enc)?)?
+                .as_ref()
+                .to_vec(),
+        );
+        let mut kem_context = [enc, &serialize_public_key(sk_r.compute_public_key()?)?].concat();
+
+        if let Some(pk_s) = pk_s {
+            dh.extend_from_slice(sk_r.agree(self.deserialize_public_key(pk_s)?)?.as_ref());
+            kem_context.extend_from_slice(pk_s);
+        }
+
+        self.extract_and_expand(&dh, &kem_context)
+    }
+}
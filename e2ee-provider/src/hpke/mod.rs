@@ -0,0 +1,867 @@
+use crate::{
+    CryptoProvider, Error,
+    aead::{self, Plaintext},
+    ec::{
+        KeyProvider,
+        agreement::{self, SharedSecret},
+    },
+    hkdf::{self, Expander, Hkdf, Okm},
+};
+use alloc::{boxed::Box, vec::Vec};
+use context::Context;
+use core::marker::PhantomData;
+use dhkem::serialize_public_key;
+use zeroize::Zeroize;
+
+pub use context::{RecipientContext, SenderContext};
+
+/// Encryption contexts established by the `setup_*` functions.
+mod context;
+
+/// DH-based key encapsulation mechanisms.
+mod dhkem;
+
+/// Key encapsulation mechanisms (KEMs) of HPKE.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kem {
+    /// DHKEM(P-256, HKDF-SHA256).
+    DhKemP256HkdfSha256 = 0x0010,
+    /// DHKEM(P-384, HKDF-SHA384).
+    DhKemP384HkdfSha384 = 0x0011,
+    /// DHKEM(P-521, HKDF-SHA512).
+    DhKemP521HkdfSha512 = 0x0012,
+    /// DHKEM(X25519, HKDF-SHA256).
+    DhKemX25519HkdfSha256 = 0x0020,
+    /// DHKEM(X448, HKDF-SHA512).
+    DhKemX448HkdfSha512 = 0x0021,
+}
+
+impl TryFrom<u16> for Kem {
+    type Error = Error;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        match value {
+            0x0010 => Ok(Self::DhKemP256HkdfSha256),
+            0x0011 => Ok(Self::DhKemP384HkdfSha384),
+            0x0012 => Ok(Self::DhKemP521HkdfSha512),
+            0x0020 => Ok(Self::DhKemX25519HkdfSha256),
+            0x0021 => Ok(Self::DhKemX448HkdfSha512),
+            _ => Err(Error::UnsupportedKemAlgorithm),
+        }
+    }
+}
+
+impl Kem {
+    /// Returns the key agreement algorithm of the KEM.
+    pub fn agreement_algorithm(&self) -> agreement::Algorithm {
+        match self {
+            Self::DhKemP256HkdfSha256 => agreement::Algorithm::EcdhP256,
+            Self::DhKemP384HkdfSha384 => agreement::Algorithm::EcdhP384,
+            Self::DhKemP521HkdfSha512 => agreement::Algorithm::EcdhP521,
+            Self::DhKemX25519HkdfSha256 => agreement::Algorithm::X25519,
+            Self::DhKemX448HkdfSha512 => agreement::Algorithm::X448,
+        }
+    }
+
+    /// Returns the KDF used internally by the KEM.
+    pub fn kdf(&self) -> Kdf {
+        match self {
+            Self::DhKemP256HkdfSha256 | Self::DhKemX25519HkdfSha256 => Kdf::HkdfSha256,
+            Self::DhKemP384HkdfSha384 => Kdf::HkdfSha384,
+            Self::DhKemP521HkdfSha512 | Self::DhKemX448HkdfSha512 => Kdf::HkdfSha512,
+        }
+    }
+
+    /// Returns length of the KEM shared secret (`Nsecret`).
+    pub fn shared_secret_len(&self) -> usize {
+        match self {
+            Self::DhKemP256HkdfSha256 | Self::DhKemX25519HkdfSha256 => 32,
+            Self::DhKemP384HkdfSha384 => 48,
+            Self::DhKemP521HkdfSha512 | Self::DhKemX448HkdfSha512 => 64,
+        }
+    }
+
+    /// Returns length of the encapsulated key and the serialized public key
+    /// (`Nenc`, `Npk`).
+    pub fn public_key_len(&self) -> usize {
+        match self {
+            Self::DhKemP256HkdfSha256 => 65,
+            Self::DhKemP384HkdfSha384 => 97,
+            Self::DhKemP521HkdfSha512 => 133,
+            Self::DhKemX25519HkdfSha256 => 32,
+            Self::DhKemX448HkdfSha512 => 56,
+        }
+    }
+
+    /// Returns length of the serialized private key (`Nsk`).
+    pub fn private_key_len(&self) -> usize {
+        match self {
+            Self::DhKemP256HkdfSha256 | Self::DhKemX25519HkdfSha256 => 32,
+            Self::DhKemP384HkdfSha384 => 48,
+            Self::DhKemP521HkdfSha512 => 66,
+            Self::DhKemX448HkdfSha512 => 56,
+        }
+    }
+}
+
+/// Key derivation functions (KDFs) of HPKE.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kdf {
+    /// HKDF-SHA256.
+    HkdfSha256 = 0x0001,
+    /// HKDF-SHA384.
+    HkdfSha384 = 0x0002,
+    /// HKDF-SHA512.
+    HkdfSha512 = 0x0003,
+}
+
+impl TryFrom<u16> for Kdf {
+    type Error = Error;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        match value {
+            0x0001 => Ok(Self::HkdfSha256),
+            0x0002 => Ok(Self::HkdfSha384),
+            0x0003 => Ok(Self::HkdfSha512),
+            _ => Err(Error::UnsupportedHkdf),
+        }
+    }
+}
+
+impl Kdf {
+    /// Returns the HKDF algorithm of the KDF.
+    pub fn hkdf_algorithm(&self) -> hkdf::Algorithm {
+        match self {
+            Self::HkdfSha256 => hkdf::Algorithm::Sha256,
+            Self::HkdfSha384 => hkdf::Algorithm::Sha384,
+            Self::HkdfSha512 => hkdf::Algorithm::Sha512,
+        }
+    }
+
+    /// Returns output length of the underlying hash function (`Nh`).
+    pub fn hash_len(&self) -> usize {
+        match self {
+            Self::HkdfSha256 => 32,
+            Self::HkdfSha384 => 48,
+            Self::HkdfSha512 => 64,
+        }
+    }
+}
+
+/// AEAD algorithms of HPKE.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aead {
+    /// AES-128-GCM.
+    Aes128Gcm = 0x0001,
+    /// AES-256-GCM.
+    Aes256Gcm = 0x0002,
+    /// ChaCha20Poly1305.
+    ChaCha20Poly1305 = 0x0003,
+    /// No encryption; contexts can only be used for exporting secrets.
+    ExportOnly = 0xffff,
+}
+
+impl TryFrom<u16> for Aead {
+    type Error = Error;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        match value {
+            0x0001 => Ok(Self::Aes128Gcm),
+            0x0002 => Ok(Self::Aes256Gcm),
+            0x0003 => Ok(Self::ChaCha20Poly1305),
+            0xffff => Ok(Self::ExportOnly),
+            _ => Err(Error::UnsupportedAeadAlgorithm),
+        }
+    }
+}
+
+impl Aead {
+    /// Returns the AEAD algorithm, or `None` for [`ExportOnly`].
+    ///
+    /// [`ExportOnly`]: Aead::ExportOnly
+    pub fn aead_algorithm(&self) -> Option<aead::Algorithm> {
+        match self {
+            Self::Aes128Gcm => Some(aead::Algorithm::Aes128Gcm),
+            Self::Aes256Gcm => Some(aead::Algorithm::Aes256Gcm),
+            Self::ChaCha20Poly1305 => Some(aead::Algorithm::ChaCha20Poly1305),
+            Self::ExportOnly => None,
+        }
+    }
+}
+
+/// HPKE cipher suite: a combination of KEM, KDF and AEAD.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Suite {
+    /// Key encapsulation mechanism.
+    pub kem: Kem,
+    /// Key derivation function of the key schedule.
+    pub kdf: Kdf,
+    /// AEAD of the encryption context.
+    pub aead: Aead,
+}
+
+impl Suite {
+    /// `suite_id = concat("HPKE", I2OSP(kem_id, 2), I2OSP(kdf_id, 2),
+    /// I2OSP(aead_id, 2))`
+    fn id(&self) -> [u8; 10] {
+        let mut id = *b"HPKE\0\0\0\0\0\0";
+        id[4..6].copy_from_slice(&(self.kem as u16).to_be_bytes());
+        id[6..8].copy_from_slice(&(self.kdf as u16).to_be_bytes());
+        id[8..].copy_from_slice(&(self.aead as u16).to_be_bytes());
+
+        id
+    }
+}
+
+/// HPKE modes, RFC 9180 section 5.
+#[derive(Clone, Copy)]
+enum Mode {
+    Base = 0x00,
+    Psk = 0x01,
+    Auth = 0x02,
+    AuthPsk = 0x03,
+}
+
+/// `LabeledExtract(salt, label, ikm)`
+fn labeled_extract(hkdf: &dyn Hkdf, suite_id: &[u8], salt: &[u8], label: &[u8], ikm: &[u8]) -> Okm {
+    let mut labeled_ikm = [b"HPKE-v1", suite_id, label, ikm].concat();
+    let prk = hkdf.extract_prk(salt, &labeled_ikm);
+    labeled_ikm.zeroize();
+
+    prk
+}
+
+/// `LabeledExpand(prk, label, info, L)`
+fn labeled_expand(
+    prk: &dyn Expander,
+    suite_id: &[u8],
+    label: &[u8],
+    info: &[u8],
+    len: usize,
+) -> Result<Okm, Error> {
+    let len_bytes = u16::try_from(len)
+        .map_err(|_| Error::Unspecified)?
+        .to_be_bytes();
+
+    prk.expand(&[&len_bytes, b"HPKE-v1", suite_id, label, info], len)
+}
+
+/// `LabeledExpand(prk, label, info, L)` into `output`, where L is
+/// `output.len()`
+fn labeled_expand_into(
+    prk: &dyn Expander,
+    suite_id: &[u8],
+    label: &[u8],
+    info: &[u8],
+    output: &mut [u8],
+) -> Result<(), Error> {
+    let len_bytes = u16::try_from(output.len())
+        .map_err(|_| Error::Unspecified)?
+        .to_be_bytes();
+
+    prk.expand_into(&[&len_bytes, b"HPKE-v1", suite_id, label, info], output)
+}
+
+/// Hybrid public key encryption (HPKE), as specified in
+/// [RFC 9180](https://datatracker.ietf.org/doc/html/rfc9180).
+///
+/// Key agreement is performed with keys of the [`KeyProvider`] `K`, while the
+/// KDF and the AEAD of the [`Suite`] are taken from a [`CryptoProvider`].
+/// Public keys (including the encapsulated key `enc`) are passed in their
+/// serialized form: uncompressed points for NIST curves and raw bytes for
+/// X25519 and X448.
+pub struct Hpke<K> {
+    suite: Suite,
+    kem_hkdf: &'static dyn Hkdf,
+    hkdf: &'static dyn Hkdf,
+    aead: Option<&'static dyn aead::Aead>,
+    _key_provider: PhantomData<fn() -> K>,
+}
+
+impl<K: KeyProvider> Hpke<K> {
+    /// Resolves the algorithms of `suite` from `provider`.
+    pub fn new(provider: &CryptoProvider, suite: Suite) -> Result<Self, Error> {
+        let hkdf = |kdf: Kdf| {
+            provider
+                .hkdf
+                .get(kdf.hkdf_algorithm())
+                .ok_or(Error::UnsupportedHkdf)
+        };
+        let aead = match suite.aead.aead_algorithm() {
+            Some(algorithm) => Some(
+                provider
+                    .aead
+                    .get(algorithm)
+                    .ok_or(Error::UnsupportedAeadAlgorithm)?,
+            ),
+            None => None,
+        };
+
+        Ok(Self {
+            suite,
+            kem_hkdf: hkdf(suite.kem.kdf())?,
+            hkdf: hkdf(suite.kdf)?,
+            aead,
+            _key_provider: PhantomData,
+        })
+    }
+
+    /// Returns the cipher suite.
+    pub fn suite(&self) -> Suite {
+        self.suite
+    }
+
+    /// `SetupBaseS(pkR, info)`
+    ///
+    /// Returns the encapsulated key `enc` along with the sender context.
+    pub fn setup_base_sender(
+        &self,
+        pk_r: &[u8],
+        info: &[u8],
+    ) -> Result<(Vec<u8>, SenderContext), Error> {
+        self.setup_sender(Mode::Base, pk_r, info, b"", b"", None)
+    }
+
+    /// `SetupBaseR(enc, skR, info)`
+    pub fn setup_base_recipient(
+        &self,
+        enc: &[u8],
+        sk_r: &dyn agreement::PrivateKey,
+        info: &[u8],
+    ) -> Result<RecipientContext, Error> {
+        self.setup_recipient(Mode::Base, enc, sk_r, info, b"", b"", None)
+    }
+
+    /// `SetupPSKS(pkR, info, psk, psk_id)`
+    pub fn setup_psk_sender(
+        &self,
+        pk_r: &[u8],
+        info: &[u8],
+        psk: &[u8],
+        psk_id: &[u8],
+    ) -> Result<(Vec<u8>, SenderContext), Error> {
+        self.setup_sender(Mode::Psk, pk_r, info, psk, psk_id, None)
+    }
+
+    /// `SetupPSKR(enc, skR, info, psk, psk_id)`
+    pub fn setup_psk_recipient(
+        &self,
+        enc: &[u8],
+        sk_r: &dyn agreement::PrivateKey,
+        info: &[u8],
+        psk: &[u8],
+        psk_id: &[u8],
+    ) -> Result<RecipientContext, Error> {
+        self.setup_recipient(Mode::Psk, enc, sk_r, info, psk, psk_id, None)
+    }
+
+    /// `SetupAuthS(pkR, info, skS)`
+    pub fn setup_auth_sender(
+        &self,
+        pk_r: &[u8],
+        info: &[u8],
+        sk_s: &dyn agreement::PrivateKey,
+    ) -> Result<(Vec<u8>, SenderContext), Error> {
+        self.setup_sender(Mode::Auth, pk_r, info, b"", b"", Some(sk_s))
+    }
+
+    /// `SetupAuthR(enc, skR, info, pkS)`
+    pub fn setup_auth_recipient(
+        &self,
+        enc: &[u8],
+        sk_r: &dyn agreement::PrivateKey,
+        info: &[u8],
+        pk_s: &[u8],
+    ) -> Result<RecipientContext, Error> {
+        self.setup_recipient(Mode::Auth, enc, sk_r, info, b"", b"", Some(pk_s))
+    }
+
+    /// `SetupAuthPSKS(pkR, info, psk, psk_id, skS)`
+    pub fn setup_auth_psk_sender(
+        &self,
+        pk_r: &[u8],
+        info: &[u8],
+        psk: &[u8],
+        psk_id: &[u8],
+        sk_s: &dyn agreement::PrivateKey,
+    ) -> Result<(Vec<u8>, SenderContext), Error> {
+        self.setup_sender(Mode::AuthPsk, pk_r, info, psk, psk_id, Some(sk_s))
+    }
+
+    /// `SetupAuthPSKR(enc, skR, info, psk, psk_id, pkS)`
+    #[allow(clippy::too_many_arguments)]
+    pub fn setup_auth_psk_recipient(
+        &self,
+        enc: &[u8],
+        sk_r: &dyn agreement::PrivateKey,
+        info: &[u8],
+        psk: &[u8],
+        psk_id: &[u8],
+        pk_s: &[u8],
+    ) -> Result<RecipientContext, Error> {
+        self.setup_recipient(Mode::AuthPsk, enc, sk_r, info, psk, psk_id, Some(pk_s))
+    }
+
+    /// Single-shot base mode encryption, `Seal<MODE_BASE>`.
+    ///
+    /// Returns the encapsulated key `enc` and the ciphertext.
+    pub fn seal(
+        &self,
+        pk_r: &[u8],
+        info: &[u8],
+        aad: &[u8],
+        plaintext: &[u8],
+    ) -> Result<(Vec<u8>, Vec<u8>), Error> {
+        let (enc, mut context) = self.setup_base_sender(pk_r, info)?;
+
+        Ok((enc, context.seal(aad, plaintext)?))
+    }
+
+    /// Single-shot base mode decryption, `Open<MODE_BASE>`.
+    pub fn open(
+        &self,
+        enc: &[u8],
+        sk_r: &dyn agreement::PrivateKey,
+        info: &[u8],
+        aad: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Plaintext, Error> {
+        self.setup_base_recipient(enc, sk_r, info)?
+            .open(aad, ciphertext)
+    }
+
+    fn setup_sender(
+        &self,
+        mode: Mode,
+        pk_r: &[u8],
+        info: &[u8],
+        psk: &[u8],
+        psk_id: &[u8],
+        sk_s: Option<&dyn agreement::PrivateKey>,
+    ) -> Result<(Vec<u8>, SenderContext), Error> {
+        let sk_e = K::generate_ephemeral_private_key(self.suite.kem.agreement_algorithm())?;
+        let enc = serialize_public_key(sk_e.compute_public_key()?)?;
+        let context =
+            self.setup_sender_with(mode, pk_r, info, psk, psk_id, sk_s, &enc, |pk_r| {
+                sk_e.agree_ephemeral(pk_r)
+            })?;
+
+        Ok((enc, context))
+    }
+
+    /// Sender setup with the ephemeral key agreement `agree_e` of `enc`,
+    /// allowing a fixed ephemeral key in tests.
+    #[allow(clippy::too_many_arguments)]
+    fn setup_sender_with(
+        &self,
+        mode: Mode,
+        pk_r: &[u8],
+        info: &[u8],
+        psk: &[u8],
+        psk_id: &[u8],
+        sk_s: Option<&dyn agreement::PrivateKey>,
+        enc: &[u8],
+        agree_e: impl FnOnce(Box<dyn agreement::PublicKey>) -> Result<SharedSecret, Error>,
+    ) -> Result<SenderContext, Error> {
+        let shared_secret = self.encap(pk_r, sk_s, enc, agree_e)?;
+        let context = self.key_schedule(mode, shared_secret.as_ref(), info, psk, psk_id)?;
+
+        Ok(SenderContext(context))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn setup_recipient(
+        &self,
+        mode: Mode,
+        enc: &[u8],
+        sk_r: &dyn agreement::PrivateKey,
+        info: &[u8],
+        psk: &[u8],
+        psk_id: &[u8],
+        pk_s: Option<&[u8]>,
+    ) -> Result<RecipientContext, Error> {
+        let shared_secret = self.decap(enc, sk_r, pk_s)?;
+        let context = self.key_schedule(mode, shared_secret.as_ref(), info, psk, psk_id)?;
+
+        Ok(RecipientContext(context))
+    }
+
+    /// `KeySchedule<ROLE>(mode, shared_secret, info, psk, psk_id)`
+    fn key_schedule(
+        &self,
+        mode: Mode,
+        shared_secret: &[u8],
+        info: &[u8],
+        psk: &[u8],
+        psk_id: &[u8],
+    ) -> Result<Context, Error> {
+        let has_psk = matches!(mode, Mode::Psk | Mode::AuthPsk);
+        if psk.is_empty() == has_psk || psk_id.is_empty() == has_psk {
+            return Err(Error::Unspecified);
+        }
+
+        let suite_id = self.suite.id();
+        let psk_id_hash = labeled_extract(self.hkdf, &suite_id, b"", b"psk_id_hash", psk_id);
+        let info_hash = labeled_extract(self.hkdf, &suite_id, b"", b"info_hash", info);
+        let key_schedule_context =
+            [&[mode as u8], psk_id_hash.as_ref(), info_hash.as_ref()].concat();
+
+        let secret = labeled_extract(self.hkdf, &suite_id, shared_secret, b"secret", psk);
+        let secret = self.hkdf.expander(secret.as_ref());
+        let expand = |label: &[u8], len| {
+            labeled_expand(&*secret, &suite_id, label, &key_schedule_context, len)
+        };
+
+        let (key, base_nonce) = match self.aead {
+            Some(aead) => {
+                let algorithm = aead.algorithm();
+
+                (
+                    expand(b"key", algorithm.key_len())?,
+                    expand(b"base_nonce", algorithm.nonce_len())?,
+                )
+            }
+            None => (Okm::new(&[])?, Okm::new(&[])?),
+        };
+        let exporter_secret = expand(b"exp", self.suite.kdf.hash_len())?;
+
+        Ok(Context {
+            aead: self.aead,
+            key,
+            base_nonce,
+            seq: 0,
+            exporter: self.hkdf.expander(exporter_secret.as_ref()),
+            suite_id,
+        })
+    }
+}
+
+#[test]
+#[cfg(all(test, feature = "aws_lc_rs"))]
+fn test() -> Result<(), Error> {
+    use crate::{
+        aws_lc_rs::AwsLcRs,
+        ec::{KeyProvider, encoding::PrivateKeyBin},
+    };
+
+    let provider = CryptoProvider {
+        hkdf: &AwsLcRs,
+        hash: &AwsLcRs,
+        key_wrap: &AwsLcRs,
+        aead: &AwsLcRs,
+    };
+    let hex = |s: &str| hex::decode(s).unwrap();
+    let x25519 = |sk: &str| {
+        AwsLcRs::load_private_key_bin(
+            agreement::Algorithm::X25519,
+            &PrivateKeyBin::EdEcSeed(hex(sk)),
+        )
+    };
+
+    // Base mode DHKEM(X25519) vectors of the RFC 9180 test vector set, as
+    // shipped with BoringSSL (appendix A.1 and A.2, plus AES-256-GCM).
+    let info = hex("4f6465206f6e2061204772656369616e2055726e");
+    let pt = hex("4265617574792069732074727574682c20747275746820626561757479");
+    for (aead, sk_r, sk_e, enc, cts, exports) in [
+        (
+            Aead::Aes128Gcm,
+            "4612c550263fc8ad58375df3f557aac531d26850903e55a9f23f21d8534e8ac8",
+            "52c4a758a802cd8b936eceea314432798d5baf2d7e9235dc084ab1b9cfa2f736",
+            "37fda3567bdbd628e88668c3c8d7e97d1d1253b6d4ea6d44c150f741f1bf4431",
+            &[
+                "f938558b5d72f1a23810b4be2ab4f84331acc02fc97babc53a52ae8218a355a96d8770ac83d07bea87e13c512a",
+                "af2d7e9ac9ae7e270f46ba1f975be53c09f8d875bdc8535458c2494e8a6eab251c03d0c22a56b8ca42c2063b84",
+            ][..],
+            [
+                "3853fe2b4035195a573ffc53856e77058e15d9ea064de3e59f4961d0095250ee",
+                "2e8f0b54673c7029649d4eb9d5e33bf1872cf76d623ff164ac185da9e88c21a5",
+                "e9e43065102c3836401bed8c3c3c75ae46be1639869391d62c61f1ec7af54931",
+            ],
+        ),
+        (
+            Aead::Aes256Gcm,
+            "497b4502664cfea5d5af0b39934dac72242a74f8480451e1aee7d6a53320333d",
+            "179d4b53b6365c45b600c4163b61d95cbc2f4d9e36f1695558dce265ab8bab11",
+            "6c93e09869df3402d7bf231bf540fadd35cd56be14f97178f0954db94b7fc256",
+            &[
+                "e5d84cd531cfb583096e7cfa9641bd3079cf3a91cda813c52deb5f512be9931980a41de125a925cdad859d5b7a",
+            ][..],
+            [
+                "ded6cffafaea6b812cbf3e241e88332adbc077aca81512914213810ee291770a",
+                "04d3cb6cc116b28ffd22ad5bc276c60d31fec71ceb87ae24db811c64b7507339",
+                "7c5ded445732c14fe09727d29b4251c0fd38455fe8440571e687f0886aac94d2",
+            ],
+        ),
+        (
+            Aead::ChaCha20Poly1305,
+            "8057991eef8f1f1af18f4a9491d16a1ce333f695d4db8e38da75975c4478e0fb",
+            "f4ec9b33b792c372c1d2c2063507b684ef925b8c75a42dbcbf57d63ccd381600",
+            "1afa08d3dec047a643885163f1180476fa7ddb54c6a8029ea33f95796bf2ac4a",
+            &[
+                "1c5250d8034ec2b784ba2cfd69dbdb8af406cfe3ff938e131f0def8c8b60b4db21993c62ce81883d2dd1b51a28",
+            ][..],
+            [
+                "4bbd6243b8bb54cec311fac9df81841b6fd61f56538a775e7c80a9f40160606e",
+                "8c1df14732580e5501b00f82b10a1647b40713191b7c1240ac80e2b68808ba69",
+                "5acb09211139c43b3090489a9da433e8a30ee7188ba8b0a9a1ccf0c229283e53",
+            ],
+        ),
+    ] {
+        let hpke = Hpke::<AwsLcRs>::new(
+            &provider,
+            Suite {
+                kem: Kem::DhKemX25519HkdfSha256,
+                kdf: Kdf::HkdfSha256,
+                aead,
+            },
+        )?;
+        let (sk_r, sk_e, enc) = (x25519(sk_r)?, x25519(sk_e)?, hex(enc));
+        let pk_r = serialize_public_key(sk_r.compute_public_key()?)?;
+
+        let mut sender =
+            hpke.setup_sender_with(Mode::Base, &pk_r, &info, b"", b"", None, &enc, |pk| {
+                sk_e.agree(pk)
+            })?;
+        let mut recipient = hpke.setup_base_recipient(&enc, &*sk_r, &info)?;
+
+        for (seq, ct) in cts.iter().enumerate() {
+            let aad = alloc::format!("Count-{seq}");
+            assert_eq!(sender.seal(aad.as_bytes(), &pt)?, hex(ct));
+            assert_eq!(recipient.open(aad.as_bytes(), &hex(ct))?.as_ref(), pt);
+        }
+
+        for (exporter_context, exported) in [&b""[..], b"\x00", b"TestContext"].iter().zip(exports)
+        {
+            assert_eq!(sender.export(exporter_context, 32)?.as_ref(), hex(exported));
+            assert_eq!(
+                recipient.export(exporter_context, 32)?.as_ref(),
+                hex(exported)
+            );
+        }
+    }
+
+    // PSK, auth and auth-PSK modes of DHKEM(X25519) and all modes of
+    // DHKEM(P-256), with HKDF-SHA256 and AES-128-GCM: RFC 9180, appendix
+    // A.1.2 to A.1.4 and A.3.1 to A.3.4. The first ciphertext and the
+    // exported values of each.
+    let (psk, psk_id) = (
+        hex("0247fd33b913760fa1fa51e1892d9f307fbe65eb171e8132c2af18555a738b82"),
+        hex("456e6e796e20447572696e206172616e204d6f726961"),
+    );
+    for (kem, mode, ikm_e, ikm_r, ikm_s, enc, ct, exports) in [
+        (
+            Kem::DhKemX25519HkdfSha256,
+            Mode::Psk,
+            "78628c354e46f3e169bd231be7b2ff1c77aa302460a26dbfa15515684c00130b",
+            "d4a09d09f575fef425905d2ab396c1449141463f698f8efdb7accfaff8995098",
+            None,
+            "0ad0950d9fb9588e59690b74f1237ecdf1d775cd60be2eca57af5a4b0471c91b",
+            "e52c6fed7f758d0cf7145689f21bc1be6ec9ea097fef4e959440012f4feb73fb611b946199e681f4cfc34db8ea",
+            [
+                "dff17af354c8b41673567db6259fd6029967b4e1aad13023c2ae5df8f4f43bf6",
+                "6a847261d8207fe596befb52928463881ab493da345b10e1dcc645e3b94e2d95",
+                "8aff52b45a1be3a734bc7a41e20b4e055ad4c4d22104b0c20285a7c4302401cd",
+            ],
+        ),
+        (
+            Kem::DhKemX25519HkdfSha256,
+            Mode::Auth,
+            "6e6d8f200ea2fb20c30b003a8b4f433d2f4ed4c2658d5bc8ce2fef718059c9f7",
+            "f1d4a30a4cef8d6d4e3b016e6fd3799ea057db4f345472ed302a67ce1c20cdec",
+            Some("94b020ce91d73fca4649006c7e7329a67b40c55e9e93cc907d282bbbff386f58"),
+            "23fb952571a14a25e3d678140cd0e5eb47a0961bb18afcf85896e5453c312e76",
+            "5fd92cc9d46dbf8943e72a07e42f363ed5f721212cd90bcfd072bfd9f44e06b80fd17824947496e21b680c141b",
+            [
+                "28c70088017d70c896a8420f04702c5a321d9cbf0279fba899b59e51bac72c85",
+                "25dfc004b0892be1888c3914977aa9c9bbaf2c7471708a49e1195af48a6f29ce",
+                "5a0131813abc9a522cad678eb6bafaabc43389934adb8097d23c5ff68059eb64",
+            ],
+        ),
+        (
+            Kem::DhKemX25519HkdfSha256,
+            Mode::AuthPsk,
+            "4303619085a20ebcf18edd22782952b8a7161e1dbae6e46e143a52a96127cf84",
+            "4b16221f3b269a88e207270b5e1de28cb01f847841b344b8314d6a622fe5ee90",
+            Some("62f77dcf5df0dd7eac54eac9f654f426d4161ec850cc65c54f8b65d2e0b4e345"),
+            "820818d3c23993492cc5623ab437a48a0a7ca3e9639c140fe1e33811eb844b7c",
+            "a84c64df1e11d8fd11450039d4fe64ff0c8a99fca0bd72c2d4c3e0400bc14a40f27e45e141a24001697737533e",
+            [
+                "08f7e20644bb9b8af54ad66d2067457c5f9fcb2a23d9f6cb4445c0797b330067",
+                "52e51ff7d436557ced5265ff8b94ce69cf7583f49cdb374e6aad801fc063b010",
+                "a30c20370c026bbea4dca51cb63761695132d342bae33a6a11527d3e7679436d",
+            ],
+        ),
+        (
+            Kem::DhKemP256HkdfSha256,
+            Mode::Base,
+            "4270e54ffd08d79d5928020af4686d8f6b7d35dbe470265f1f5aa22816ce860e",
+            "668b37171f1072f3cf12ea8a236a45df23fc13b82af3609ad1e354f6ef817550",
+            None,
+            "04a92719c6195d5085104f469a8b9814d5838ff72b60501e2c4466e5e67b325ac98536d7b61a1af4b78e5b7f951c0900be863c403ce65c9bfcb9382657222d18c4",
+            "5ad590bb8baa577f8619db35a36311226a896e7342a6d836d8b7bcd2f20b6c7f9076ac232e3ab2523f39513434",
+            [
+                "5e9bc3d236e1911d95e65b576a8a86d478fb827e8bdfe77b741b289890490d4d",
+                "6cff87658931bda83dc857e6353efe4987a201b849658d9b047aab4cf216e796",
+                "d8f1ea7942adbba7412c6d431c62d01371ea476b823eb697e1f6e6cae1dab85a",
+            ],
+        ),
+        (
+            Kem::DhKemP256HkdfSha256,
+            Mode::Psk,
+            "2afa611d8b1a7b321c761b483b6a053579afa4f767450d3ad0f84a39fda587a6",
+            "d42ef874c1913d9568c9405407c805baddaffd0898a00f1e84e154fa787b2429",
+            None,
+            "04305d35563527bce037773d79a13deabed0e8e7cde61eecee403496959e89e4d0ca701726696d1485137ccb5341b3c1c7aaee90a4a02449725e744b1193b53b5f",
+            "90c4deb5b75318530194e4bb62f890b019b1397bbf9d0d6eb918890e1fb2be1ac2603193b60a49c2126b75d0eb",
+            [
+                "a115a59bf4dd8dc49332d6a0093af8efca1bcbfd3627d850173f5c4a55d0c185",
+                "4517eaede0669b16aac7c92d5762dd459c301fa10e02237cd5aeb9be969430c4",
+                "164e02144d44b607a7722e58b0f4156e67c0c2874d74cf71da6ca48a4cbdc5e0",
+            ],
+        ),
+        (
+            Kem::DhKemP256HkdfSha256,
+            Mode::Auth,
+            "798d82a8d9ea19dbc7f2c6dfa54e8a6706f7cdc119db0813dacf8440ab37c857",
+            "7bc93bde8890d1fb55220e7f3b0c107ae7e6eda35ca4040bb6651284bf0747ee",
+            Some("874baa0dcf93595a24a45a7f042e0d22d368747daaa7e19f80a802af19204ba8"),
+            "042224f3ea800f7ec55c03f29fc9865f6ee27004f818fcbdc6dc68932c1e52e15b79e264a98f2c535ef06745f3d308624414153b22c7332bc1e691cb4af4d53454",
+            "82ffc8c44760db691a07c5627e5fc2c08e7a86979ee79b494a17cc3405446ac2bdb8f265db4a099ed3289ffe19",
+            [
+                "837e49c3ff629250c8d80d3c3fb957725ed481e59e2feb57afd9fe9a8c7c4497",
+                "594213f9018d614b82007a7021c3135bda7b380da4acd9ab27165c508640dbda",
+                "14fe634f95ca0d86e15247cca7de7ba9b73c9b9deb6437e1c832daf7291b79d5",
+            ],
+        ),
+        (
+            Kem::DhKemP256HkdfSha256,
+            Mode::AuthPsk,
+            "3c1fceb477ec954c8d58ef3249e4bb4c38241b5925b95f7486e4d9f1d0d35fbb",
+            "abcc2da5b3fa81d8aabd91f7f800a8ccf60ec37b1b585a5d1d1ac77f258b6cca",
+            Some("6262031f040a9db853edd6f91d2272596eabbc78a2ed2bd643f770ecd0f19b82"),
+            "046a1de3fc26a3d43f4e4ba97dbe24f7e99181136129c48fbe872d4743e2b131357ed4f29a7b317dc22509c7b00991ae990bf65f8b236700c82ab7c11a84511401",
+            "b9f36d58d9eb101629a3e5a7b63d2ee4af42b3644209ab37e0a272d44365407db8e655c72e4fa46f4ff81b9246",
+            [
+                "595ce0eff405d4b3bb1d08308d70a4e77226ce11766e0a94c4fdb5d90025c978",
+                "110472ee0ae328f57ef7332a9886a1992d2c45b9b8d5abc9424ff68630f7d38d",
+                "18ee4d001a9d83a4c67e76f88dd747766576cac438723bad0700a910a4d717e6",
+            ],
+        ),
+    ] {
+        let hpke = Hpke::<AwsLcRs>::new(
+            &provider,
+            Suite {
+                kem,
+                kdf: Kdf::HkdfSha256,
+                aead: Aead::Aes128Gcm,
+            },
+        )?;
+        let sk_e = hpke.derive_key_pair(&hex(ikm_e))?;
+        let sk_r = hpke.derive_key_pair(&hex(ikm_r))?;
+        let sk_s = ikm_s
+            .map(|ikm_s| hpke.derive_key_pair(&hex(ikm_s)))
+            .transpose()?;
+        let pk_r = serialize_public_key(sk_r.compute_public_key()?)?;
+        let enc = hex(enc);
+        assert_eq!(serialize_public_key(sk_e.compute_public_key()?)?, enc);
+
+        let (psk, psk_id) = match mode {
+            Mode::Psk | Mode::AuthPsk => (&psk[..], &psk_id[..]),
+            Mode::Base | Mode::Auth => (&b""[..], &b""[..]),
+        };
+        let mut sender = hpke.setup_sender_with(
+            mode,
+            &pk_r,
+            &info,
+            psk,
+            psk_id,
+            sk_s.as_deref(),
+            &enc,
+            |pk| sk_e.agree(pk),
+        )?;
+        let pk_s = match &sk_s {
+            Some(sk_s) => serialize_public_key(sk_s.compute_public_key()?)?,
+            None => Vec::new(),
+        };
+        let mut recipient = match mode {
+            Mode::Base => hpke.setup_base_recipient(&enc, &*sk_r, &info)?,
+            Mode::Psk => hpke.setup_psk_recipient(&enc, &*sk_r, &info, psk, psk_id)?,
+            Mode::Auth => hpke.setup_auth_recipient(&enc, &*sk_r, &info, &pk_s)?,
+            Mode::AuthPsk => {
+                hpke.setup_auth_psk_recipient(&enc, &*sk_r, &info, psk, psk_id, &pk_s)?
+            }
+        };
+
+        assert_eq!(sender.seal(b"Count-0", &pt)?, hex(ct));
+        assert_eq!(recipient.open(b"Count-0", &hex(ct))?.as_ref(), pt);
+
+        for (exporter_context, exported) in [&b""[..], b"\x00", b"TestContext"].iter().zip(exports)
+        {
+            assert_eq!(sender.export(exporter_context, 32)?.as_ref(), hex(exported));
+            assert_eq!(
+                recipient.export(exporter_context, 32)?.as_ref(),
+                hex(exported)
+            );
+        }
+
+        // Single-shot API with a fresh ephemeral key; the wrong PSK or
+        // sender key must not authenticate.
+        let (enc, ct) = hpke.seal(&pk_r, &info, b"aad", b"sealed message")?;
+        assert_eq!(
+            hpke.open(&enc, &*sk_r, &info, b"aad", &ct)?.as_ref(),
+            b"sealed message"
+        );
+        assert!(hpke.open(&enc, &*sk_r, b"other info", b"aad", &ct).is_err());
+
+        if let (Mode::AuthPsk, Some(sk_s)) = (mode, &sk_s) {
+            let (enc, mut sender) =
+                hpke.setup_auth_psk_sender(&pk_r, &info, psk, psk_id, &**sk_s)?;
+            let ct = sender.seal(b"", b"sealed message")?;
+            assert!(
+                hpke.setup_auth_psk_recipient(&enc, &*sk_r, &info, &psk[1..], psk_id, &pk_s)?
+                    .open(b"", &ct)
+                    .is_err()
+            );
+            assert!(
+                hpke.setup_auth_psk_recipient(&enc, &*sk_r, &info, psk, psk_id, &pk_r)?
+                    .open(b"", &ct)
+                    .is_err()
+            );
+        }
+    }
+
+    // Export-only suites establish a context that cannot encrypt.
+    let hpke = Hpke::<AwsLcRs>::new(
+        &provider,
+        Suite {
+            kem: Kem::DhKemX25519HkdfSha256,
+            kdf: Kdf::HkdfSha512,
+            aead: Aead::ExportOnly,
+        },
+    )?;
+    let sk_r = hpke.derive_key_pair(&[0x30; 32])?;
+    let pk_r = serialize_public_key(sk_r.compute_public_key()?)?;
+    let (enc, mut sender) = hpke.setup_base_sender(&pk_r, &info)?;
+    let recipient = hpke.setup_base_recipient(&enc, &*sk_r, &info)?;
+    assert!(matches!(
+        sender.seal(b"", b""),
+        Err(Error::UnsupportedAeadAlgorithm)
+    ));
+    assert_eq!(
+        sender.export(b"", 64)?.as_ref(),
+        recipient.export(b"", 64)?.as_ref()
+    );
+
+    // Secrets longer than an `Okm` are exported in place.
+    let mut sent = [0; 1024];
+    let mut received = [0; 1024];
+    sender.export_into(b"", &mut sent)?;
+    recipient.export_into(b"", &mut received)?;
+    assert_eq!(sent, received);
+    assert!(sender.export(b"", 1024).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use crate::Error;
+use alloc::vec::Vec;
+use zeroize::Zeroize;
+
+/// Serialized decapsulation key bytes.
+#[derive(Clone)]
+pub enum DecapsulationKeyBin {
+    /// Decapsulation key, encoded as specified by the algorithm (e.g. the
+    /// expanded `ML-KEM.KeyGen` output of FIPS 203, or the 32-byte seed of
+    /// X-Wing).
+    Raw(Vec<u8>),
+}
+
+/// Serialized encapsulation key bytes.
+#[derive(Clone)]
+pub enum EncapsulationKeyBin {
+    /// Encapsulation key, encoded as specified by the algorithm.
+    Raw(Vec<u8>),
+}
+
+/// Methods for serializing decapsulation keys into binary formats.
+pub trait DecapsulationKeySerializer {
+    /// Exposes the decapsulation key in the encoding of its algorithm.
+    fn as_raw_bytes(&self) -> Result<DecapsulationKeyBin, Error>;
+}
+
+/// Methods for serializing encapsulation keys into binary formats.
+pub trait EncapsulationKeySerializer {
+    /// Serializes the encapsulation key.
+    fn as_raw_bytes(&self) -> Result<EncapsulationKeyBin, Error>;
+}
+
+impl Drop for DecapsulationKeyBin {
+    fn drop(&mut self) {
+        match self {
+            Self::Raw(bytes) => bytes.zeroize(),
+        }
+    }
+}
+
+impl AsRef<[u8]> for DecapsulationKeyBin {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Self::Raw(bytes) => bytes,
+        }
+    }
+}
+
+impl Drop for EncapsulationKeyBin {
+    fn drop(&mut self) {
+        match self {
+            Self::Raw(bytes) => bytes.zeroize(),
+        }
+    }
+}
+
+impl AsRef<[u8]> for EncapsulationKeyBin {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Self::Raw(bytes) => bytes,
+        }
+    }
+}
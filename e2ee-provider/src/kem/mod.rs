@@ -0,0 +1,147 @@
+use crate::Error;
+use alloc::{boxed::Box, vec::Vec};
+use encoding::{
+    DecapsulationKeyBin, DecapsulationKeySerializer, EncapsulationKeyBin,
+    EncapsulationKeySerializer,
+};
+use zeroize::Zeroize;
+
+/// Encapsulation and decapsulation key encoding formats.
+pub mod encoding;
+
+/// Supported key encapsulation mechanisms.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    /// ML-KEM-512, as specified in FIPS 203.
+    MlKem512 = 1454,
+    /// ML-KEM-768, as specified in FIPS 203.
+    MlKem768 = 1455,
+    /// ML-KEM-1024, as specified in FIPS 203.
+    MlKem1024 = 1456,
+    /// X-Wing hybrid KEM, combining [`MlKem768`] with X25519 as described
+    /// in [draft-connolly-cfrg-xwing-kem](https://datatracker.ietf.org/doc/draft-connolly-cfrg-xwing-kem/).
+    ///
+    /// Encapsulation keys and ciphertexts are the concatenation of their
+    /// ML-KEM-768 and X25519 parts, in that order. The decapsulation key is
+    /// the 32-byte seed both parts are derived from, as in the draft. The
+    /// value is its HPKE KEM identifier.
+    ///
+    /// [`MlKem768`]: Algorithm::MlKem768
+    XWing = 0x647a,
+}
+
+impl TryFrom<i32> for Algorithm {
+    type Error = Error;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            1454 => Ok(Self::MlKem512),
+            1455 => Ok(Self::MlKem768),
+            1456 => Ok(Self::MlKem1024),
+            0x647a => Ok(Self::XWing),
+            _ => Err(Error::UnsupportedKemAlgorithm),
+        }
+    }
+}
+
+impl Algorithm {
+    /// Returns length of the serialized encapsulation key.
+    pub fn encapsulation_key_len(&self) -> usize {
+        match self {
+            Self::MlKem512 => 800,
+            Self::MlKem768 => 1184,
+            Self::MlKem1024 => 1568,
+            Self::XWing => 1216,
+        }
+    }
+
+    /// Returns length of the serialized decapsulation key.
+    pub fn decapsulation_key_len(&self) -> usize {
+        match self {
+            Self::MlKem512 => 1632,
+            Self::MlKem768 => 2400,
+            Self::MlKem1024 => 3168,
+            Self::XWing => 32,
+        }
+    }
+
+    /// Returns length of the ciphertext.
+    pub fn ciphertext_len(&self) -> usize {
+        match self {
+            Self::MlKem512 => 768,
+            Self::MlKem768 => 1088,
+            Self::MlKem1024 => 1568,
+            Self::XWing => 1120,
+        }
+    }
+
+    /// Returns length of the shared secret.
+    pub fn shared_secret_len(&self) -> usize {
+        32
+    }
+}
+
+/// Mechanism for loading or generating KEM keys.
+pub trait KeyProvider {
+    /// Loads a decapsulation (private) key from bytes.
+    fn load_decapsulation_key_bin(
+        algorithm: Algorithm,
+        bin: &DecapsulationKeyBin,
+    ) -> Result<Box<dyn DecapsulationKey>, Error>;
+
+    /// Loads an encapsulation (public) key from bytes.
+    fn load_encapsulation_key_bin(
+        algorithm: Algorithm,
+        bin: &EncapsulationKeyBin,
+    ) -> Result<Box<dyn EncapsulationKey>, Error>;
+
+    /// Generates a new decapsulation key.
+    fn generate_decapsulation_key(algorithm: Algorithm)
+    -> Result<Box<dyn DecapsulationKey>, Error>;
+}
+
+/// A private key for key decapsulation.
+pub trait DecapsulationKey {
+    /// Recovers the shared secret from the `ciphertext`.
+    fn decapsulate(&self, ciphertext: &[u8]) -> Result<SharedSecret, Error>;
+
+    /// Computes the encapsulation key of the decapsulation key.
+    fn encapsulation_key(&self) -> Result<Box<dyn EncapsulationKey>, Error>;
+
+    /// Returns the algorithm associated with this key.
+    fn algorithm(&self) -> Algorithm;
+
+    /// Interface for serializing the key into binary formats.
+    fn to_serializer(self: Box<Self>) -> Box<dyn DecapsulationKeySerializer>;
+}
+
+/// A public key for key encapsulation.
+pub trait EncapsulationKey {
+    /// Generates a fresh shared secret, returning it along with the
+    /// ciphertext to be sent to the holder of the decapsulation key.
+    fn encapsulate(&self) -> Result<(Vec<u8>, SharedSecret), Error>;
+
+    /// Returns the algorithm associated with this key.
+    fn algorithm(&self) -> Algorithm;
+
+    /// Interface for serializing the key into binary formats.
+    fn to_serializer(self: Box<Self>) -> Box<dyn EncapsulationKeySerializer>;
+}
+
+/// Result of a key encapsulation.
+pub struct SharedSecret {
+    pub(crate) buf: Vec<u8>,
+}
+
+impl Drop for SharedSecret {
+    fn drop(&mut self) {
+        self.buf.zeroize();
+    }
+}
+
+impl AsRef<[u8]> for SharedSecret {
+    fn as_ref(&self) -> &[u8] {
+        &self.buf
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{
+    Error,
+    ec::encoding::{PrivateKeyBin, PrivateKeyDer},
+};
+use alloc::vec::Vec;
+use zeroize::Zeroize;
+
+/// Supported key wrapping algorithms.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    /// AES-128 key wrap (AES-KW), as specified in
+    /// [RFC 3394](https://datatracker.ietf.org/doc/html/rfc3394).
+    Aes128Kw = 788,
+    /// AES-256 key wrap (AES-KW), as specified in
+    /// [RFC 3394](https://datatracker.ietf.org/doc/html/rfc3394).
+    Aes256Kw = 790,
+    /// AES-128 key wrap with padding (AES-KWP), as specified in
+    /// [RFC 5649](https://datatracker.ietf.org/doc/html/rfc5649).
+    Aes128Kwp = 897,
+    /// AES-256 key wrap with padding (AES-KWP), as specified in
+    /// [RFC 5649](https://datatracker.ietf.org/doc/html/rfc5649).
+    Aes256Kwp = 903,
+}
+
+impl TryFrom<i32> for Algorithm {
+    type Error = Error;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            788 => Ok(Self::Aes128Kw),
+            790 => Ok(Self::Aes256Kw),
+            897 => Ok(Self::Aes128Kwp),
+            903 => Ok(Self::Aes256Kwp),
+            _ => Err(Error::UnsupportedKeyWrapAlgorithm),
+        }
+    }
+}
+
+impl Algorithm {
+    /// Returns length of the key-encryption key in bytes.
+    pub fn kek_len(&self) -> usize {
+        match self {
+            Self::Aes128Kw | Self::Aes128Kwp => 16,
+            Self::Aes256Kw | Self::Aes256Kwp => 32,
+        }
+    }
+
+    /// Whether the algorithm accepts plaintexts of arbitrary length.
+    ///
+    /// Unpadded key wrap only accepts plaintexts that are a multiple of 8
+    /// bytes and at least 16 bytes long.
+    pub fn is_padded(&self) -> bool {
+        matches!(self, Self::Aes128Kwp | Self::Aes256Kwp)
+    }
+}
+
+/// A key wrapping algorithm.
+pub trait KeyWrap {
+    /// Encrypts `plaintext` under the key-encryption key `kek`.
+    fn wrap(&self, kek: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, Error>;
+
+    /// Decrypts and authenticates `ciphertext` under the key-encryption key
+    /// `kek`.
+    ///
+    /// Returns [`Error::Unspecified`] if the integrity check fails.
+    fn unwrap(&self, kek: &[u8], ciphertext: &[u8]) -> Result<UnwrappedKey, Error>;
+
+    /// Returns the algorithm of the key wrap.
+    fn algorithm(&self) -> Algorithm;
+
+    /// Wraps a serialized private key.
+    ///
+    /// DER documents are rarely a multiple of 8 bytes long, so a padded
+    /// algorithm should be used.
+    fn wrap_private_key_der(&self, kek: &[u8], key: &PrivateKeyDer) -> Result<Vec<u8>, Error> {
+        self.wrap(kek, key.as_ref())
+    }
+
+    /// Unwraps a serialized private key, e.g.
+    /// `unwrap_private_key_der(kek, wrapped, PrivateKeyDer::Pkcs8V1Key)`.
+    fn unwrap_private_key_der(
+        &self,
+        kek: &[u8],
+        ciphertext: &[u8],
+        format: fn(Vec<u8>) -> PrivateKeyDer,
+    ) -> Result<PrivateKeyDer, Error> {
+        Ok(format(self.unwrap(kek, ciphertext)?.into_vec()))
+    }
+
+    /// Wraps private key bytes.
+    fn wrap_private_key_bin(&self, kek: &[u8], key: &PrivateKeyBin) -> Result<Vec<u8>, Error> {
+        self.wrap(kek, key.as_ref())
+    }
+
+    /// Unwraps private key bytes, e.g.
+    /// `unwrap_private_key_bin(kek, wrapped, PrivateKeyBin::EdEcSeed)`.
+    fn unwrap_private_key_bin(
+        &self,
+        kek: &[u8],
+        ciphertext: &[u8],
+        format: fn(Vec<u8>) -> PrivateKeyBin,
+    ) -> Result<PrivateKeyBin, Error> {
+        Ok(format(self.unwrap(kek, ciphertext)?.into_vec()))
+    }
+}
+
+/// Plaintext recovered by [`KeyWrap::unwrap`].
+pub struct UnwrappedKey {
+    pub(crate) buf: Vec<u8>,
+}
+
+impl UnwrappedKey {
+    /// Moves the plaintext out without copying it.
+    fn into_vec(mut self) -> Vec<u8> {
+        core::mem::take(&mut self.buf)
+    }
+}
+
+impl Drop for UnwrappedKey {
+    fn drop(&mut self) {
+        self.buf.zeroize();
+    }
+}
+
+impl AsRef<[u8]> for UnwrappedKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.buf
+    }
+}
@@ -15,12 +15,47 @@ pub mod digest;
 /// HMAC-based key derivation funciton (HKDF) interface.
 pub mod hkdf;
 
+/// Authenticated encryption with associated data (AEAD) interface.
+pub mod aead;
+
+/// Key wrapping (key encryption) interface.
+pub mod key_wrap;
+
 /// Elliptic curve cryptography.
 pub mod ec;
 
+/// Key encapsulation mechanism (KEM) interface.
+pub mod kem;
+
+/// Hybrid public key encryption (HPKE), as specified in RFC 9180.
+pub mod hpke;
+
+/// Pure-Rust SLH-DSA (FIPS 205) stateless hash-based signatures.
+#[cfg(feature = "slh_dsa")]
+pub mod slh_dsa;
+
+/// XEdDSA and VXEdDSA signatures with X25519 keys.
+#[cfg(feature = "xeddsa")]
+pub mod xeddsa;
+
+/// Ed25519ctx and Ed25519ph signatures with context strings (RFC 8032).
+#[cfg(feature = "ed25519ctx")]
+pub mod ed25519ctx;
+
+/// Batch verification of Ed25519 signatures, for the backends that lack it.
+#[cfg(all(feature = "ed25519_batch", feature = "aws_lc_rs"))]
+mod ed25519_batch;
+
+/// ZIP-215 verification of Ed25519 signatures, for the backends that lack it.
+#[cfg(all(feature = "zip215", feature = "aws_lc_rs"))]
+mod zip215;
+
 /// General provider interface.
 pub mod provider;
 
+/// Static-dispatch provider interface with associated types.
+pub mod generic;
+
 /// Error reporting.
 mod error;
 
@@ -29,7 +64,7 @@ mod error;
 pub mod aws_lc_rs;
 
 pub use error::Error;
-pub use provider::{HashProvider, HkdfProvider};
+pub use provider::{AeadProvider, HashProvider, HkdfProvider, KeyWrapProvider};
 
 /// Cryptographic functions used by e2ee.
 pub struct CryptoProvider {
@@ -37,6 +72,23 @@ pub struct CryptoProvider {
     pub hkdf: &'static dyn HkdfProvider,
     /// Hashing functions.
     pub hash: &'static dyn HashProvider,
+    /// Key wrapping functions.
+    pub key_wrap: &'static dyn KeyWrapProvider,
+    /// Authenticated encryption functions.
+    pub aead: &'static dyn AeadProvider,
     // /// Key provider.
     // pub key: &'static dyn KeyProvider,
 }
+
+impl CryptoProvider {
+    /// Uses the functions of a [`generic::CryptoProvider`] through trait
+    /// objects, for runtime-selected use.
+    pub fn from_generic<P: generic::CryptoProvider>(provider: &'static P) -> Self {
+        Self {
+            hkdf: provider,
+            hash: provider,
+            key_wrap: provider,
+            aead: provider,
+        }
+    }
+}
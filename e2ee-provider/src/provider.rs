@@ -1,4 +1,4 @@
-use crate::{digest, hkdf};
+use crate::{aead, digest, hkdf, key_wrap};
 
 /// A provider that maps algorithms to their corresponding cryptographic
 /// handlers.
@@ -17,7 +17,19 @@ pub trait Provider<A, T> {
 }
 
 /// Provides hash functions required by e2ee.
-pub trait HashProvider: Provider<digest::Algorithm, &'static dyn digest::Hash> {}
+pub trait HashProvider:
+    Provider<digest::Algorithm, &'static dyn digest::Hash> + Send + Sync
+{
+}
 
 /// Provides key derivation functions required by e2ee.
-pub trait HkdfProvider: Provider<hkdf::Algorithm, &'static dyn hkdf::Hkdf> {}
+pub trait HkdfProvider: Provider<hkdf::Algorithm, &'static dyn hkdf::Hkdf> + Send + Sync {}
+
+/// Provides key wrapping algorithms required by e2ee.
+pub trait KeyWrapProvider:
+    Provider<key_wrap::Algorithm, &'static dyn key_wrap::KeyWrap> + Send + Sync
+{
+}
+
+/// Provides authenticated encryption algorithms required by e2ee.
+pub trait AeadProvider: Provider<aead::Algorithm, &'static dyn aead::Aead> + Send + Sync {}
@@ -0,0 +1,87 @@
+/// Address types, FIPS 205, section 4.2.
+#[derive(Clone, Copy)]
+pub(super) enum AddressType {
+    WotsHash = 0,
+    WotsPk = 1,
+    Tree = 2,
+    ForsTree = 3,
+    ForsRoots = 4,
+    WotsPrf = 5,
+    ForsPrf = 6,
+}
+
+/// 32-byte hash function address (ADRS).
+///
+/// Layout: layer (4) | tree (12) | type (4) | key pair (4) | chain or tree
+/// height (4) | hash or tree index (4).
+#[derive(Clone, Copy, Default)]
+pub(super) struct Address([u8; 32]);
+
+impl Address {
+    fn set_word(&mut self, offset: usize, value: u32) {
+        self.0[offset..offset + 4].copy_from_slice(&value.to_be_bytes());
+    }
+
+    fn word(&self, offset: usize) -> u32 {
+        u32::from_be_bytes(self.0[offset..offset + 4].try_into().unwrap())
+    }
+
+    pub(super) fn set_layer(&mut self, layer: u32) {
+        self.set_word(0, layer);
+    }
+
+    /// Tree indices never exceed 64 bits, so the upper 4 bytes of the 12-byte
+    /// field stay zero.
+    pub(super) fn set_tree(&mut self, tree: u64) {
+        self.0[8..16].copy_from_slice(&tree.to_be_bytes());
+    }
+
+    pub(super) fn set_type_and_clear(&mut self, kind: AddressType) {
+        self.set_word(16, kind as u32);
+        self.0[20..].fill(0);
+    }
+
+    pub(super) fn set_key_pair(&mut self, key_pair: u32) {
+        self.set_word(20, key_pair);
+    }
+
+    pub(super) fn key_pair(&self) -> u32 {
+        self.word(20)
+    }
+
+    pub(super) fn set_chain(&mut self, chain: u32) {
+        self.set_word(24, chain);
+    }
+
+    pub(super) fn set_tree_height(&mut self, height: u32) {
+        self.set_word(24, height);
+    }
+
+    pub(super) fn set_hash(&mut self, hash: u32) {
+        self.set_word(28, hash);
+    }
+
+    pub(super) fn set_tree_index(&mut self, index: u32) {
+        self.set_word(28, index);
+    }
+
+    pub(super) fn tree_index(&self) -> u32 {
+        self.word(28)
+    }
+
+    pub(super) fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// 22-byte compressed address used by the SHA2 parameter sets.
+    pub(super) fn compressed(&self) -> [u8; 22] {
+        let mut adrs = [0; 22];
+
+        adrs[0] = self.0[3];
+        adrs[1..9].copy_from_slice(&self.0[8..16]);
+        adrs[9] = self.0[19];
+        adrs[10..].copy_from_slice(&self.0[20..]);
+
+        adrs
+    }
+}
@@ -0,0 +1,185 @@
+use super::{Family, Node, Params, address::Address};
+use sha2::{
+    Sha256, Sha512,
+    digest::{ExtendableOutput, FixedOutput, Update},
+};
+use sha3::Shake256;
+use zeroize::Zeroize;
+
+enum Hasher {
+    Sha2 { sha256: Sha256, sha512: Sha512 },
+    Shake(Shake256),
+}
+
+/// Keyed hash functions `PRF`, `F`, `H` and `T_l` of a parameter set.
+///
+/// The state after absorbing `PK.seed` (and its padding to a full block for
+/// SHA2) is computed once and cloned for every call.
+pub(super) struct Context {
+    pub(super) params: &'static Params,
+    sk_seed: Node,
+    hasher: Hasher,
+}
+
+impl Context {
+    pub(super) fn new(params: &'static Params, pk_seed: &[u8], sk_seed: &[u8]) -> Self {
+        let mut seed = [0; 32];
+        seed[..sk_seed.len()].copy_from_slice(sk_seed);
+
+        let hasher = match params.family {
+            Family::Sha2 => {
+                let zeros = [0; 128];
+
+                Hasher::Sha2 {
+                    sha256: Sha256::default()
+                        .chain(pk_seed)
+                        .chain(&zeros[..64 - params.n]),
+                    sha512: Sha512::default()
+                        .chain(pk_seed)
+                        .chain(&zeros[..128 - params.n]),
+                }
+            }
+            Family::Shake => Hasher::Shake(Shake256::default().chain(pk_seed)),
+        };
+
+        Self {
+            params,
+            sk_seed: seed,
+            hasher,
+        }
+    }
+
+    /// Tweakable hash of `n`-byte blocks. SHA2 parameter sets of security
+    /// categories 3 and 5 use SHA-512 for `H` and `T_l`, which are `wide`.
+    fn hash(&self, adrs: &Address, input: &[Node], wide: bool) -> Node {
+        let n = self.params.n;
+        let mut out = [0; 32];
+
+        match &self.hasher {
+            Hasher::Sha2 { sha256, sha512 } => {
+                if wide && n > 16 {
+                    let mut hasher = sha512.clone().chain(adrs.compressed());
+                    input.iter().for_each(|block| hasher.update(&block[..n]));
+                    out[..n].copy_from_slice(&hasher.finalize_fixed()[..n]);
+                } else {
+                    let mut hasher = sha256.clone().chain(adrs.compressed());
+                    input.iter().for_each(|block| hasher.update(&block[..n]));
+                    out[..n].copy_from_slice(&hasher.finalize_fixed()[..n]);
+                }
+            }
+            Hasher::Shake(shake) => {
+                let mut hasher = shake.clone().chain(adrs.as_bytes());
+                input.iter().for_each(|block| hasher.update(&block[..n]));
+                hasher.finalize_xof_into(&mut out[..n]);
+            }
+        }
+
+        out
+    }
+
+    pub(super) fn prf(&self, adrs: &Address) -> Node {
+        self.hash(adrs, core::slice::from_ref(&self.sk_seed), false)
+    }
+
+    pub(super) fn f(&self, adrs: &Address, block: &Node) -> Node {
+        self.hash(adrs, core::slice::from_ref(block), false)
+    }
+
+    pub(super) fn h(&self, adrs: &Address, left: &Node, right: &Node) -> Node {
+        self.hash(adrs, &[*left, *right], true)
+    }
+
+    pub(super) fn t(&self, adrs: &Address, blocks: &[Node]) -> Node {
+        self.hash(adrs, blocks, true)
+    }
+}
+
+impl Drop for Context {
+    fn drop(&mut self) {
+        self.sk_seed.zeroize();
+    }
+}
+
+/// `PRF_msg(SK.prf, opt_rand, M)`, generates the signature randomizer.
+pub(super) fn prf_msg(params: &Params, sk_prf: &[u8], opt_rand: &[u8], message: &[&[u8]]) -> Node {
+    let n = params.n;
+    let mut out = [0; 32];
+
+    match params.family {
+        Family::Sha2 if n == 16 => {
+            out[..n].copy_from_slice(&hmac::<Sha256, 64>(sk_prf, opt_rand, message)[..n])
+        }
+        Family::Sha2 => {
+            out[..n].copy_from_slice(&hmac::<Sha512, 128>(sk_prf, opt_rand, message)[..n])
+        }
+        Family::Shake => {
+            let mut hasher = Shake256::default().chain(sk_prf).chain(opt_rand);
+            message.iter().for_each(|part| hasher.update(part));
+            hasher.finalize_xof_into(&mut out[..n]);
+        }
+    }
+
+    out
+}
+
+/// `H_msg(R, PK.seed, PK.root, M)`, writes the `m`-byte message digest into
+/// `out`.
+pub(super) fn h_msg(params: &Params, r: &[u8], pk: &[u8], message: &[&[u8]], out: &mut [u8]) {
+    let pk_seed = &pk[..params.n];
+
+    match params.family {
+        Family::Sha2 if params.n == 16 => {
+            let mut hasher = Sha256::default().chain(r).chain(pk);
+            message.iter().for_each(|part| hasher.update(part));
+            mgf1::<Sha256>(&[r, pk_seed, &hasher.finalize_fixed()], out);
+        }
+        Family::Sha2 => {
+            let mut hasher = Sha512::default().chain(r).chain(pk);
+            message.iter().for_each(|part| hasher.update(part));
+            mgf1::<Sha512>(&[r, pk_seed, &hasher.finalize_fixed()], out);
+        }
+        Family::Shake => {
+            let mut hasher = Shake256::default().chain(r).chain(pk);
+            message.iter().for_each(|part| hasher.update(part));
+            hasher.finalize_xof_into(out);
+        }
+    }
+}
+
+fn hmac<D, const BLOCK: usize>(
+    key: &[u8],
+    opt_rand: &[u8],
+    message: &[&[u8]],
+) -> sha2::digest::Output<D>
+where
+    D: Default + Update + FixedOutput,
+{
+    let mut pad = [0x36; BLOCK];
+    pad.iter_mut().zip(key).for_each(|(pad, key)| *pad ^= key);
+
+    let mut inner = D::default().chain(pad).chain(opt_rand);
+    message.iter().for_each(|part| inner.update(part));
+    let inner = inner.finalize_fixed();
+
+    pad.iter_mut().for_each(|pad| *pad ^= 0x36 ^ 0x5c);
+    let outer = D::default().chain(pad).chain(inner).finalize_fixed();
+
+    pad.zeroize();
+
+    outer
+}
+
+fn mgf1<D>(seed: &[&[u8]], out: &mut [u8])
+where
+    D: Default + Update + FixedOutput + Clone,
+{
+    let mut hasher = D::default();
+    seed.iter().for_each(|part| hasher.update(part));
+
+    for (counter, chunk) in
+        (0u32..).zip(out.chunks_mut(<D as sha2::digest::OutputSizeUser>::output_size()))
+    {
+        let block = hasher.clone().chain(counter.to_be_bytes()).finalize_fixed();
+        chunk.copy_from_slice(&block[..chunk.len()]);
+    }
+}
@@ -0,0 +1,654 @@
+use crate::{
+    Error,
+    ec::{
+        encoding::{
+            PrivateKeyBin, PrivateKeyDer, PrivateKeySerializer, PublicKeyBin, PublicKeyDer,
+            PublicKeySerializer,
+        },
+        signature::{Algorithm, SigningKey, VerifyingKey},
+    },
+};
+use alloc::{boxed::Box, vec::Vec};
+use hash::Context;
+use zeroize::Zeroize;
+
+mod address;
+mod hash;
+mod tree;
+
+/// `n`-byte hash output, padded to the largest `n`.
+type Node = [u8; 32];
+
+/// Maximum WOTS+ chain count `len = 2n + 3`.
+const MAX_LEN: usize = 67;
+
+/// Maximum message digest length `m`.
+const MAX_M: usize = 49;
+
+enum Family {
+    Sha2,
+    Shake,
+}
+
+/// SLH-DSA parameter set, FIPS 205, section 11.
+struct Params {
+    n: usize,
+    h: usize,
+    d: usize,
+    hp: usize,
+    a: usize,
+    k: usize,
+    m: usize,
+    family: Family,
+}
+
+macro_rules! params {
+    ($family:ident, $n:literal, $h:literal, $d:literal, $hp:literal, $a:literal, $k:literal, $m:literal) => {
+        &Params {
+            n: $n,
+            h: $h,
+            d: $d,
+            hp: $hp,
+            a: $a,
+            k: $k,
+            m: $m,
+            family: Family::$family,
+        }
+    };
+}
+
+impl Params {
+    fn get(algorithm: Algorithm) -> Result<&'static Self, Error> {
+        Ok(match algorithm {
+            Algorithm::SlhDsaSha2_128s => params!(Sha2, 16, 63, 7, 9, 12, 14, 30),
+            Algorithm::SlhDsaSha2_128f => params!(Sha2, 16, 66, 22, 3, 6, 33, 34),
+            Algorithm::SlhDsaSha2_192s => params!(Sha2, 24, 63, 7, 9, 14, 17, 39),
+            Algorithm::SlhDsaSha2_192f => params!(Sha2, 24, 66, 22, 3, 8, 33, 42),
+            Algorithm::SlhDsaSha2_256s => params!(Sha2, 32, 64, 8, 8, 14, 22, 47),
+            Algorithm::SlhDsaSha2_256f => params!(Sha2, 32, 68, 17, 4, 9, 35, 49),
+            Algorithm::SlhDsaShake128s => params!(Shake, 16, 63, 7, 9, 12, 14, 30),
+            Algorithm::SlhDsaShake128f => params!(Shake, 16, 66, 22, 3, 6, 33, 34),
+            Algorithm::SlhDsaShake192s => params!(Shake, 24, 63, 7, 9, 14, 17, 39),
+            Algorithm::SlhDsaShake192f => params!(Shake, 24, 66, 22, 3, 8, 33, 42),
+            Algorithm::SlhDsaShake256s => params!(Shake, 32, 64, 8, 8, 14, 22, 47),
+            Algorithm::SlhDsaShake256f => params!(Shake, 32, 68, 17, 4, 9, 35, 49),
+            _ => return Err(Error::UnsupportedSignatureAlgorithm),
+        })
+    }
+
+    /// WOTS+ chain count.
+    fn len(&self) -> usize {
+        2 * self.n + 3
+    }
+
+    fn signature_len(&self) -> usize {
+        self.n * (1 + self.k * (1 + self.a) + self.h + self.d * self.len())
+    }
+
+    /// Splits the message digest into the FORS message and the hypertree
+    /// indices, section 9.2.
+    fn split_digest<'a>(&self, digest: &'a [u8]) -> (&'a [u8], u64, u32) {
+        let md_len = (self.k * self.a).div_ceil(8);
+        let tree_bits = self.h - self.hp;
+        let tree_len = tree_bits.div_ceil(8);
+        let leaf_len = self.hp.div_ceil(8);
+
+        let (md, rest) = digest.split_at(md_len);
+        let (tree, leaf) = rest.split_at(tree_len);
+
+        let idx_tree = to_int(tree) & (u64::MAX >> (64 - tree_bits));
+        let idx_leaf = to_int(&leaf[..leaf_len]) & ((1 << self.hp) - 1);
+
+        (md, idx_tree, idx_leaf as u32)
+    }
+}
+
+fn to_int(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(0, |int, &byte| (int << 8) | u64::from(byte))
+}
+
+/// `slh_keygen_internal`, returns `SK.seed || SK.prf || PK.seed || PK.root`.
+fn keygen_internal(
+    params: &'static Params,
+    sk_seed: &[u8],
+    sk_prf: &[u8],
+    pk_seed: &[u8],
+) -> Vec<u8> {
+    let ctx = Context::new(params, pk_seed, sk_seed);
+    let mut adrs = address::Address::default();
+    adrs.set_layer(params.d as u32 - 1);
+
+    let pk_root = tree::xmss_node(&ctx, 0, params.hp as u32, &mut adrs);
+
+    [sk_seed, sk_prf, pk_seed, &pk_root[..params.n]].concat()
+}
+
+/// `slh_sign_internal`. `opt_rand` is `PK.seed` for the deterministic
+/// variant.
+fn sign_internal(
+    params: &'static Params,
+    sk: &[u8],
+    message: &[&[u8]],
+    opt_rand: &[u8],
+) -> Vec<u8> {
+    let n = params.n;
+    let (sk_seed, rest) = sk.split_at(n);
+    let (sk_prf, pk) = rest.split_at(n);
+    let ctx = Context::new(params, &pk[..n], sk_seed);
+
+    let mut signature = Vec::with_capacity(params.signature_len());
+    let r = hash::prf_msg(params, sk_prf, opt_rand, message);
+    signature.extend_from_slice(&r[..n]);
+
+    let mut digest = [0; MAX_M];
+    hash::h_msg(params, &r[..n], pk, message, &mut digest[..params.m]);
+    let (md, idx_tree, idx_leaf) = params.split_digest(&digest);
+
+    let mut adrs = address::Address::default();
+    adrs.set_tree(idx_tree);
+    adrs.set_type_and_clear(address::AddressType::ForsTree);
+    adrs.set_key_pair(idx_leaf);
+
+    let fors_start = signature.len();
+    tree::fors_sign(&ctx, md, &mut adrs, &mut signature);
+    let pk_fors = tree::fors_pk_from_sig(&ctx, &signature[fors_start..], md, &mut adrs);
+
+    tree::ht_sign(&ctx, &pk_fors, idx_tree, idx_leaf, &mut signature);
+
+    signature
+}
+
+/// `slh_verify_internal`.
+fn verify_internal(
+    params: &'static Params,
+    pk: &[u8],
+    message: &[&[u8]],
+    signature: &[u8],
+) -> bool {
+    let n = params.n;
+
+    if signature.len() != params.signature_len() {
+        return false;
+    }
+
+    let ctx = Context::new(params, &pk[..n], &[]);
+    let (r, signature) = signature.split_at(n);
+    let (fors, ht) = signature.split_at(params.k * (1 + params.a) * n);
+
+    let mut digest = [0; MAX_M];
+    hash::h_msg(params, r, pk, message, &mut digest[..params.m]);
+    let (md, idx_tree, idx_leaf) = params.split_digest(&digest);
+
+    let mut adrs = address::Address::default();
+    adrs.set_tree(idx_tree);
+    adrs.set_type_and_clear(address::AddressType::ForsTree);
+    adrs.set_key_pair(idx_leaf);
+
+    let pk_fors = tree::fors_pk_from_sig(&ctx, fors, md, &mut adrs);
+
+    tree::ht_verify(&ctx, &pk_fors, ht, idx_tree, idx_leaf, &pk[n..])
+}
+
+/// Prefix of the pure SLH-DSA message `M' = 0 || |ctx| || ctx || M`.
+fn message_prefix(context: &[u8]) -> Result<[u8; 2], Error> {
+    Ok([
+        0,
+        u8::try_from(context.len()).map_err(|_| Error::Unspecified)?,
+    ])
+}
+
+/// DER `AlgorithmIdentifier` of the parameter set, RFC 9909.
+fn algorithm_identifier(algorithm: Algorithm) -> Result<[u8; 13], Error> {
+    let arc = match algorithm {
+        Algorithm::SlhDsaSha2_128s => 20,
+        Algorithm::SlhDsaSha2_128f => 21,
+        Algorithm::SlhDsaSha2_192s => 22,
+        Algorithm::SlhDsaSha2_192f => 23,
+        Algorithm::SlhDsaSha2_256s => 24,
+        Algorithm::SlhDsaSha2_256f => 25,
+        Algorithm::SlhDsaShake128s => 26,
+        Algorithm::SlhDsaShake128f => 27,
+        Algorithm::SlhDsaShake192s => 28,
+        Algorithm::SlhDsaShake192f => 29,
+        Algorithm::SlhDsaShake256s => 30,
+        Algorithm::SlhDsaShake256f => 31,
+        _ => return Err(Error::UnsupportedSignatureAlgorithm),
+    };
+
+    Ok([
+        0x30, 0x0b, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x03, arc,
+    ])
+}
+
+fn der_length(len: usize) -> Vec<u8> {
+    if len < 0x80 {
+        alloc::vec![len as u8]
+    } else {
+        alloc::vec![0x81, len as u8]
+    }
+}
+
+fn pkcs8_prefix(algorithm: Algorithm, sk_len: usize) -> Result<Vec<u8>, Error> {
+    let key_len = der_length(sk_len);
+    let body_len = 3 + 13 + 1 + key_len.len() + sk_len;
+
+    Ok([
+        &[0x30][..],
+        &der_length(body_len),
+        &[0x02, 0x01, 0x00],
+        &algorithm_identifier(algorithm)?,
+        &[0x04],
+        &key_len,
+    ]
+    .concat())
+}
+
+fn x509_prefix(algorithm: Algorithm, pk_len: usize) -> Result<Vec<u8>, Error> {
+    Ok([
+        &[0x30, (13 + 3 + pk_len) as u8][..],
+        &algorithm_identifier(algorithm)?,
+        &[0x03, (1 + pk_len) as u8, 0x00],
+    ]
+    .concat())
+}
+
+/// Fills the buffer with cryptographically secure random bytes.
+pub type FillRandom = fn(&mut [u8]) -> Result<(), Error>;
+
+/// Stateless hash-based signing key (SLH-DSA, FIPS 205).
+///
+/// Signs deterministically unless a randomness source is given with
+/// [`SlhDsaSigningKey::hedged`].
+pub struct SlhDsaSigningKey {
+    /// `SK.seed || SK.prf || PK.seed || PK.root`.
+    sk: Vec<u8>,
+    algorithm: Algorithm,
+    fill_random: Option<FillRandom>,
+}
+
+impl SlhDsaSigningKey {
+    /// Derives a key pair from its three `n`-byte seeds.
+    pub fn from_seeds(
+        algorithm: Algorithm,
+        sk_seed: &[u8],
+        sk_prf: &[u8],
+        pk_seed: &[u8],
+    ) -> Result<Self, Error> {
+        let params = Params::get(algorithm)?;
+
+        if [sk_seed, sk_prf, pk_seed]
+            .iter()
+            .any(|seed| seed.len() != params.n)
+        {
+            return Err(Error::KeyRejected);
+        }
+
+        Ok(Self {
+            sk: keygen_internal(params, sk_seed, sk_prf, pk_seed),
+            algorithm,
+            fill_random: None,
+        })
+    }
+
+    /// Generates a new key pair using `fill_random`, which is also used for
+    /// hedged signing.
+    pub fn generate(algorithm: Algorithm, fill_random: FillRandom) -> Result<Self, Error> {
+        let n = Params::get(algorithm)?.n;
+        let mut seeds = alloc::vec![0; 3 * n];
+        fill_random(&mut seeds)?;
+
+        let key = Self::from_seeds(algorithm, &seeds[..n], &seeds[n..2 * n], &seeds[2 * n..]);
+        seeds.zeroize();
+
+        Ok(key?.hedged(fill_random))
+    }
+
+    /// Loads a key from its raw `SK.seed || SK.prf || PK.seed || PK.root`
+    /// encoding.
+    pub fn from_raw(algorithm: Algorithm, sk: &[u8]) -> Result<Self, Error> {
+        if sk.len() != 4 * Params::get(algorithm)?.n {
+            return Err(Error::KeyRejected);
+        }
+
+        Ok(Self {
+            sk: sk.to_vec(),
+            algorithm,
+            fill_random: None,
+        })
+    }
+
+    /// Loads a key from a PKCS#8 v1 document.
+    pub fn from_pkcs8(algorithm: Algorithm, der: &[u8]) -> Result<Self, Error> {
+        let sk_len = 4 * Params::get(algorithm)?.n;
+
+        match der.strip_prefix(pkcs8_prefix(algorithm, sk_len)?.as_slice()) {
+            Some(sk) => Self::from_raw(algorithm, sk),
+            None => Err(Error::KeyRejected),
+        }
+    }
+
+    /// Uses `fill_random` for the per-signature randomizer `addrnd`.
+    pub fn hedged(mut self, fill_random: FillRandom) -> Self {
+        self.fill_random = Some(fill_random);
+
+        self
+    }
+
+    /// Signs `message` under the given context string of at most 255 bytes.
+    pub fn sign_with_context(&self, context: &[u8], message: &[u8]) -> Result<Vec<u8>, Error> {
+        let params = Params::get(self.algorithm)?;
+        let n = params.n;
+        let prefix = message_prefix(context)?;
+
+        let mut opt_rand = [0; 32];
+        match self.fill_random {
+            Some(fill_random) => fill_random(&mut opt_rand[..n])?,
+            None => opt_rand[..n].copy_from_slice(&self.sk[2 * n..3 * n]),
+        }
+
+        Ok(sign_internal(
+            params,
+            &self.sk,
+            &[&prefix, context, message],
+            &opt_rand[..n],
+        ))
+    }
+}
+
+impl SigningKey for SlhDsaSigningKey {
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        self.sign_with_context(&[], message)
+    }
+
+    fn compute_public_key(&self) -> Result<Box<dyn PublicKeySerializer>, Error> {
+        let n = Params::get(self.algorithm)?.n;
+
+        Ok(Box::new(SlhDsaVerifyingKey {
+            pk: self.sk[2 * n..].to_vec(),
+            algorithm: self.algorithm,
+        }))
+    }
+
+    fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    fn to_serializer(self: Box<Self>) -> Box<dyn PrivateKeySerializer> {
+        self
+    }
+}
+
+impl PrivateKeySerializer for SlhDsaSigningKey {
+    fn as_pkcs8v1_der(&self) -> Result<PrivateKeyDer, Error> {
+        Ok(PrivateKeyDer::Pkcs8V1Key(
+            [
+                &pkcs8_prefix(self.algorithm, self.sk.len())?,
+                self.sk.as_slice(),
+            ]
+            .concat(),
+        ))
+    }
+
+    fn as_pkcs8v2_der(&self) -> Result<PrivateKeyDer, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_rfc_5915_private_key_der(&self) -> Result<PrivateKeyDer, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_ed_ec_be_bytes(&self) -> Result<PrivateKeyBin, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_ec_be_bytes(&self) -> Result<PrivateKeyBin, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_raw_bytes(&self) -> Result<PrivateKeyBin, Error> {
+        Ok(PrivateKeyBin::Raw(self.sk.clone()))
+    }
+}
+
+impl Drop for SlhDsaSigningKey {
+    fn drop(&mut self) {
+        self.sk.zeroize();
+    }
+}
+
+/// Stateless hash-based signature verification key (SLH-DSA, FIPS 205).
+pub struct SlhDsaVerifyingKey {
+    /// `PK.seed || PK.root`.
+    pk: Vec<u8>,
+    algorithm: Algorithm,
+}
+
+impl SlhDsaVerifyingKey {
+    /// Loads a key from its raw `PK.seed || PK.root` encoding.
+    pub fn from_raw(algorithm: Algorithm, pk: &[u8]) -> Result<Self, Error> {
+        if pk.len() != 2 * Params::get(algorithm)?.n {
+            return Err(Error::KeyRejected);
+        }
+
+        Ok(Self {
+            pk: pk.to_vec(),
+            algorithm,
+        })
+    }
+
+    /// Loads a key from an X.509 `SubjectPublicKeyInfo` document.
+    pub fn from_x509_der(algorithm: Algorithm, der: &[u8]) -> Result<Self, Error> {
+        let pk_len = 2 * Params::get(algorithm)?.n;
+
+        match der.strip_prefix(x509_prefix(algorithm, pk_len)?.as_slice()) {
+            Some(pk) => Self::from_raw(algorithm, pk),
+            None => Err(Error::KeyRejected),
+        }
+    }
+
+    /// Verifies the signature of `message` under the given context string.
+    pub fn verify_with_context(
+        &self,
+        context: &[u8],
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Error> {
+        let params = Params::get(self.algorithm)?;
+        let prefix = message_prefix(context)?;
+
+        if verify_internal(params, &self.pk, &[&prefix, context, message], signature) {
+            Ok(())
+        } else {
+            Err(Error::BadSignature)
+        }
+    }
+}
+
+impl VerifyingKey for SlhDsaVerifyingKey {
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), Error> {
+        self.verify_with_context(&[], message, signature)
+    }
+
+    fn verify_with_context(
+        &self,
+        context: &[u8],
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Error> {
+        SlhDsaVerifyingKey::verify_with_context(self, context, message, signature)
+    }
+
+    fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    fn to_serializer(self: Box<Self>) -> Box<dyn PublicKeySerializer> {
+        self
+    }
+}
+
+impl PublicKeySerializer for SlhDsaVerifyingKey {
+    fn as_x509_der(&self) -> Result<PublicKeyDer, Error> {
+        Ok(PublicKeyDer::X509Key(
+            [
+                &x509_prefix(self.algorithm, self.pk.len())?,
+                self.pk.as_slice(),
+            ]
+            .concat(),
+        ))
+    }
+
+    fn as_rfc_5915_public_key_der(&self) -> Result<PublicKeyDer, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_be_bytes(&self) -> Result<PublicKeyBin, Error> {
+        Ok(PublicKeyBin::Uncompreessed(self.pk.clone()))
+    }
+
+    fn as_compressed_be_bytes(&self) -> Result<PublicKeyBin, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn test() -> Result<(), Error> {
+    use sha2::{Digest, Sha256};
+
+    // An SLH-DSA-SHA2-128s case of the NIST ACVP keyGen vectors
+    // (SLH-DSA-keyGen-FIPS205): SK.seed, SK.prf, PK.seed and the public key.
+    let key = Box::new(SlhDsaSigningKey::from_seeds(
+        Algorithm::SlhDsaSha2_128s,
+        &hex::decode("ac379f047faab2004f3ae32350ac9a3d").unwrap(),
+        &hex::decode("829fff0aa59e956a87f3971c4d58e710").unwrap(),
+        &hex::decode("0566d240cc519834322eafbcc73c79f5").unwrap(),
+    )?);
+    assert_eq!(
+        hex::encode(key.compute_public_key()?.as_be_bytes()?),
+        "0566d240cc519834322eafbcc73c79f5a4b84f02e8bf0cbd54017b2d3c494b57"
+    );
+
+    // Deterministic signatures of "root identity key rotation" with seeds
+    // `SK.seed || SK.prf || PK.seed = 00 01 02 .. (3n - 1)`, cross-checked
+    // against OpenSSL 3.5. Signatures are compared by their SHA-256.
+    let vectors = [
+        (
+            Algorithm::SlhDsaSha2_128s,
+            "202122232425262728292a2b2c2d2e2f990ce6298792b128846a8e4a3a68954c",
+            "cfb11e3c6b02ba9c0b34c1ee8ffa170c5c0b6d8db448e0f9c839121eb9d70044",
+        ),
+        (
+            Algorithm::SlhDsaSha2_128f,
+            "202122232425262728292a2b2c2d2e2f3b56e816847f000386aeec2e2bb9e1b5",
+            "2439439cc6dc4cb62d5c7f0b81c00eeab1b33248d2ad099974ce6d86ad302cab",
+        ),
+        (
+            Algorithm::SlhDsaSha2_192s,
+            "303132333435363738393a3b3c3d3e3f4041424344454647b6f282ce116ff59bce2d9fc4a67c6031dabdce326c34f541",
+            "a94e2c4fc4094222696a96c6706a020fb4e43a6174fcdc5043fec8f8bf442c40",
+        ),
+        (
+            Algorithm::SlhDsaSha2_192f,
+            "303132333435363738393a3b3c3d3e3f40414243444546479236ccebbb3a90ac2452dd89de49dab1340ec02419a2870e",
+            "8a61397c0b9e68d4c13df71fbadc695bca430cbf470d24f249babcd7718e6ad8",
+        ),
+        (
+            Algorithm::SlhDsaSha2_256s,
+            "404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d5e5fda7163e601352515bc0f06f9f4f44be71a5a65ee9dca5575cf4a7b6d4a87d6e2",
+            "248530818f96e30ff1c36d86304c29694935b77a4c939661d3464f8b72e9ef3e",
+        ),
+        (
+            Algorithm::SlhDsaSha2_256f,
+            "404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d5e5f42cffe64ddbd6731063752684df77c8b58c225dc6b491208916b654ea1393176",
+            "dc9825da9bbe24ee8c42c56bf9936923ec2f390700e44247a7c320612e26188d",
+        ),
+        (
+            Algorithm::SlhDsaShake128s,
+            "202122232425262728292a2b2c2d2e2f89fd81fdbb5b94129b14761bdc6bf682",
+            "2cd967c909758ddafa5723193abf009bee28a05f50e539def151ceef0a1d8e17",
+        ),
+        (
+            Algorithm::SlhDsaShake128f,
+            "202122232425262728292a2b2c2d2e2fa90e4715b9a925c332801767fd786371",
+            "dcc1905a91f637213b7e16ff1e0cc85052e9fc209814ee1929aca970172ef8f4",
+        ),
+        (
+            Algorithm::SlhDsaShake192s,
+            "303132333435363738393a3b3c3d3e3f4041424344454647eb247f955d8eca24a5860536c56b2c4d1e8d8e835eb27d2d",
+            "0b1d94eaa0a35645f575dc69269de45f2f56f9d31344c67b6d3f745d0f8fc1a4",
+        ),
+        (
+            Algorithm::SlhDsaShake192f,
+            "303132333435363738393a3b3c3d3e3f40414243444546473f01b06bebed020a459696868d115fe8507ded8dc08e825d",
+            "99be0c5ce1b0068aa854b6ed903ad3371d33f1fd4109ec2e471a2196c896b109",
+        ),
+        (
+            Algorithm::SlhDsaShake256s,
+            "404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d5e5f27ea444dbc8ca9c169fd484b9e977eb77a4f233550757e025cf180ede7e8839f",
+            "0d320fcf3d4fb484a21c00632dd2efd5fe10d2e0fd6b9dac7bc350dd1ed86586",
+        ),
+        (
+            Algorithm::SlhDsaShake256f,
+            "404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d5e5f818d7e76beef979b5bbf9161fdefa21bd0fe0bfe19157a5711a8de8a8f6878e6",
+            "35f150a90da349a74badd22983faadb774f8f105e61a67879875700c4754d455",
+        ),
+    ];
+    let message = b"root identity key rotation";
+
+    for (algorithm, pk, signature_hash) in vectors {
+        let n = Params::get(algorithm)?.n;
+        let seeds: Vec<u8> = (0..3 * n as u8).collect();
+        let key = Box::new(SlhDsaSigningKey::from_seeds(
+            algorithm,
+            &seeds[..n],
+            &seeds[n..2 * n],
+            &seeds[2 * n..],
+        )?);
+
+        let public_key = key.compute_public_key()?.as_be_bytes()?;
+        assert_eq!(hex::encode(&public_key), pk);
+
+        let mut signature = key.sign(message)?;
+        assert_eq!(hex::encode(Sha256::digest(&signature)), signature_hash);
+
+        let verifying_key = SlhDsaVerifyingKey::from_raw(algorithm, public_key.as_ref())?;
+        verifying_key.verify(message, &signature)?;
+        assert!(
+            verifying_key
+                .verify_with_context(b"ctx", message, &signature)
+                .is_err()
+        );
+
+        signature[n] ^= 1;
+        assert!(verifying_key.verify(message, &signature).is_err());
+    }
+
+    // The same keys signing under the context string "ctx".
+    for (algorithm, signature_hash) in [
+        (
+            Algorithm::SlhDsaSha2_128s,
+            "6040e5e5ecd928e0814d366ce11314cb034aec75fadcb93447d2a67f02810ef1",
+        ),
+        (
+            Algorithm::SlhDsaShake128s,
+            "86c4d99e8099a4bbcc93065baaeae552217e4c3a7d011e8ddab6fdf4266c7dfd",
+        ),
+    ] {
+        let seeds: Vec<u8> = (0..48).collect();
+        let key =
+            SlhDsaSigningKey::from_seeds(algorithm, &seeds[..16], &seeds[16..32], &seeds[32..])?;
+
+        let signature = key.sign_with_context(b"ctx", message)?;
+        assert_eq!(hex::encode(Sha256::digest(&signature)), signature_hash);
+    }
+
+    assert!(matches!(
+        algorithm_identifier(Algorithm::Ed25519),
+        Err(Error::UnsupportedSignatureAlgorithm)
+    ));
+
+    Ok(())
+}
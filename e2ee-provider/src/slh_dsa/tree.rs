@@ -0,0 +1,342 @@
+//! WOTS+ one-time signatures, XMSS, the hypertree and FORS (FIPS 205,
+//! sections 5 to 8).
+
+use super::{
+    MAX_LEN, Node,
+    address::{Address, AddressType},
+    hash::Context,
+};
+use alloc::vec::Vec;
+
+/// Winternitz parameter `w = 2^lg_w` with `lg_w = 4`.
+const W: u32 = 16;
+
+/// Maximum FORS tree count `k`.
+const MAX_K: usize = 35;
+
+/// Maximum XMSS tree height `h'`.
+const MAX_HP: usize = 9;
+
+/// Splits `x` into `out.len()` integers of `b` bits each, most significant
+/// first.
+pub(super) fn base_2b(x: &[u8], b: u32, out: &mut [u32]) {
+    let mut bytes = x.iter();
+    let mut bits = 0;
+    let mut total = 0u64;
+
+    for digit in out {
+        while bits < b {
+            total = (total << 8) | u64::from(*bytes.next().unwrap());
+            bits += 8;
+        }
+
+        bits -= b;
+        *digit = ((total >> bits) & ((1 << b) - 1)) as u32;
+    }
+}
+
+/// Message digits followed by checksum digits, section 5.2.
+fn wots_digits(ctx: &Context, message: &Node) -> [u32; MAX_LEN] {
+    let n = ctx.params.n;
+    let len1 = 2 * n;
+    let mut digits = [0; MAX_LEN];
+
+    base_2b(&message[..n], 4, &mut digits[..len1]);
+
+    let checksum: u32 = digits[..len1].iter().map(|digit| W - 1 - digit).sum();
+    base_2b(
+        &((checksum << 4) as u16).to_be_bytes(),
+        4,
+        &mut digits[len1..len1 + 3],
+    );
+
+    digits
+}
+
+fn chain(ctx: &Context, mut node: Node, start: u32, steps: u32, adrs: &mut Address) -> Node {
+    for j in start..start + steps {
+        adrs.set_hash(j);
+        node = ctx.f(adrs, &node);
+    }
+
+    node
+}
+
+fn wots_secret(ctx: &Context, adrs: &Address, chain: u32) -> Node {
+    let mut sk_adrs = *adrs;
+    sk_adrs.set_type_and_clear(AddressType::WotsPrf);
+    sk_adrs.set_key_pair(adrs.key_pair());
+    sk_adrs.set_chain(chain);
+
+    ctx.prf(&sk_adrs)
+}
+
+fn wots_compress(ctx: &Context, adrs: &Address, nodes: &[Node]) -> Node {
+    let mut pk_adrs = *adrs;
+    pk_adrs.set_type_and_clear(AddressType::WotsPk);
+    pk_adrs.set_key_pair(adrs.key_pair());
+
+    ctx.t(&pk_adrs, nodes)
+}
+
+fn wots_pk_gen(ctx: &Context, adrs: &mut Address) -> Node {
+    let len = ctx.params.len();
+    let mut nodes = [[0; 32]; MAX_LEN];
+
+    for (i, node) in (0..).zip(&mut nodes[..len]) {
+        let sk = wots_secret(ctx, adrs, i);
+        adrs.set_chain(i);
+        *node = chain(ctx, sk, 0, W - 1, adrs);
+    }
+
+    wots_compress(ctx, adrs, &nodes[..len])
+}
+
+fn wots_sign(ctx: &Context, message: &Node, adrs: &mut Address, signature: &mut Vec<u8>) {
+    let n = ctx.params.n;
+    let digits = wots_digits(ctx, message);
+
+    for (i, &digit) in (0..).zip(&digits[..ctx.params.len()]) {
+        let sk = wots_secret(ctx, adrs, i);
+        adrs.set_chain(i);
+        signature.extend_from_slice(&chain(ctx, sk, 0, digit, adrs)[..n]);
+    }
+}
+
+fn wots_pk_from_sig(ctx: &Context, signature: &[u8], message: &Node, adrs: &mut Address) -> Node {
+    let n = ctx.params.n;
+    let len = ctx.params.len();
+    let digits = wots_digits(ctx, message);
+    let mut nodes = [[0; 32]; MAX_LEN];
+
+    for (i, (node, sig)) in (0..).zip(nodes[..len].iter_mut().zip(signature.chunks_exact(n))) {
+        let digit = digits[i as usize];
+        adrs.set_chain(i);
+        *node = chain(ctx, to_node(sig), digit, W - 1 - digit, adrs);
+    }
+
+    wots_compress(ctx, adrs, &nodes[..len])
+}
+
+/// Computes the root of the subtree of height `z` at index `i`.
+pub(super) fn xmss_node(ctx: &Context, i: u32, z: u32, adrs: &mut Address) -> Node {
+    if z == 0 {
+        adrs.set_type_and_clear(AddressType::WotsHash);
+        adrs.set_key_pair(i);
+
+        return wots_pk_gen(ctx, adrs);
+    }
+
+    let left = xmss_node(ctx, 2 * i, z - 1, adrs);
+    let right = xmss_node(ctx, 2 * i + 1, z - 1, adrs);
+
+    adrs.set_type_and_clear(AddressType::Tree);
+    adrs.set_tree_height(z);
+    adrs.set_tree_index(i);
+
+    ctx.h(adrs, &left, &right)
+}
+
+fn xmss_sign(ctx: &Context, message: &Node, idx: u32, adrs: &mut Address, signature: &mut Vec<u8>) {
+    let hp = ctx.params.hp;
+    let mut auth = [[0; 32]; MAX_HP];
+
+    for (j, node) in (0..).zip(&mut auth[..hp]) {
+        *node = xmss_node(ctx, (idx >> j) ^ 1, j, adrs);
+    }
+
+    adrs.set_type_and_clear(AddressType::WotsHash);
+    adrs.set_key_pair(idx);
+    wots_sign(ctx, message, adrs, signature);
+
+    auth[..hp]
+        .iter()
+        .for_each(|node| signature.extend_from_slice(&node[..ctx.params.n]));
+}
+
+fn xmss_pk_from_sig(
+    ctx: &Context,
+    idx: u32,
+    signature: &[u8],
+    message: &Node,
+    adrs: &mut Address,
+) -> Node {
+    let n = ctx.params.n;
+    let (wots, auth) = signature.split_at(ctx.params.len() * n);
+
+    adrs.set_type_and_clear(AddressType::WotsHash);
+    adrs.set_key_pair(idx);
+    let mut node = wots_pk_from_sig(ctx, wots, message, adrs);
+
+    adrs.set_type_and_clear(AddressType::Tree);
+    adrs.set_tree_index(idx);
+
+    for (k, sibling) in (0..).zip(auth.chunks_exact(n)) {
+        adrs.set_tree_height(k + 1);
+        adrs.set_tree_index(adrs.tree_index() / 2);
+
+        node = if (idx >> k) & 1 == 0 {
+            ctx.h(adrs, &node, &to_node(sibling))
+        } else {
+            ctx.h(adrs, &to_node(sibling), &node)
+        };
+    }
+
+    node
+}
+
+pub(super) fn ht_sign(
+    ctx: &Context,
+    message: &Node,
+    mut idx_tree: u64,
+    mut idx_leaf: u32,
+    signature: &mut Vec<u8>,
+) {
+    let hp = ctx.params.hp;
+    let mut adrs = Address::default();
+    let mut root = *message;
+
+    for layer in 0..ctx.params.d as u32 {
+        if layer > 0 {
+            idx_leaf = (idx_tree & ((1 << hp) - 1)) as u32;
+            idx_tree >>= hp;
+        }
+
+        adrs.set_layer(layer);
+        adrs.set_tree(idx_tree);
+
+        let start = signature.len();
+        xmss_sign(ctx, &root, idx_leaf, &mut adrs, signature);
+
+        if layer + 1 < ctx.params.d as u32 {
+            root = xmss_pk_from_sig(ctx, idx_leaf, &signature[start..], &root, &mut adrs);
+        }
+    }
+}
+
+pub(super) fn ht_verify(
+    ctx: &Context,
+    message: &Node,
+    signature: &[u8],
+    mut idx_tree: u64,
+    mut idx_leaf: u32,
+    pk_root: &[u8],
+) -> bool {
+    let hp = ctx.params.hp;
+    let mut adrs = Address::default();
+    let mut node = *message;
+
+    for (layer, xmss) in (0..).zip(signature.chunks_exact((ctx.params.len() + hp) * ctx.params.n)) {
+        if layer > 0 {
+            idx_leaf = (idx_tree & ((1 << hp) - 1)) as u32;
+            idx_tree >>= hp;
+        }
+
+        adrs.set_layer(layer);
+        adrs.set_tree(idx_tree);
+        node = xmss_pk_from_sig(ctx, idx_leaf, xmss, &node, &mut adrs);
+    }
+
+    node[..ctx.params.n] == *pk_root
+}
+
+fn fors_secret(ctx: &Context, adrs: &Address, idx: u32) -> Node {
+    let mut sk_adrs = *adrs;
+    sk_adrs.set_type_and_clear(AddressType::ForsPrf);
+    sk_adrs.set_key_pair(adrs.key_pair());
+    sk_adrs.set_tree_index(idx);
+
+    ctx.prf(&sk_adrs)
+}
+
+fn fors_node(ctx: &Context, i: u32, z: u32, adrs: &mut Address) -> Node {
+    if z == 0 {
+        let sk = fors_secret(ctx, adrs, i);
+        adrs.set_tree_height(0);
+        adrs.set_tree_index(i);
+
+        return ctx.f(adrs, &sk);
+    }
+
+    let left = fors_node(ctx, 2 * i, z - 1, adrs);
+    let right = fors_node(ctx, 2 * i + 1, z - 1, adrs);
+
+    adrs.set_tree_height(z);
+    adrs.set_tree_index(i);
+
+    ctx.h(adrs, &left, &right)
+}
+
+fn fors_indices(ctx: &Context, md: &[u8]) -> [u32; MAX_K] {
+    let mut indices = [0; MAX_K];
+    base_2b(md, ctx.params.a as u32, &mut indices[..ctx.params.k]);
+
+    indices
+}
+
+pub(super) fn fors_sign(ctx: &Context, md: &[u8], adrs: &mut Address, signature: &mut Vec<u8>) {
+    let n = ctx.params.n;
+    let a = ctx.params.a as u32;
+    let indices = fors_indices(ctx, md);
+
+    for (i, &index) in (0..).zip(&indices[..ctx.params.k]) {
+        signature.extend_from_slice(&fors_secret(ctx, adrs, (i << a) + index)[..n]);
+
+        for j in 0..a {
+            let sibling = ((index >> j) ^ 1) + (i << (a - j));
+            signature.extend_from_slice(&fors_node(ctx, sibling, j, adrs)[..n]);
+        }
+    }
+}
+
+pub(super) fn fors_pk_from_sig(
+    ctx: &Context,
+    signature: &[u8],
+    md: &[u8],
+    adrs: &mut Address,
+) -> Node {
+    let n = ctx.params.n;
+    let a = ctx.params.a as u32;
+    let k = ctx.params.k;
+    let indices = fors_indices(ctx, md);
+    let mut roots = [[0; 32]; MAX_K];
+
+    for (i, (root, tree)) in (0..).zip(
+        roots[..k]
+            .iter_mut()
+            .zip(signature.chunks_exact((1 + a as usize) * n)),
+    ) {
+        let index = indices[i as usize];
+        let (sk, auth) = tree.split_at(n);
+
+        adrs.set_tree_height(0);
+        adrs.set_tree_index((i << a) + index);
+        let mut node = ctx.f(adrs, &to_node(sk));
+
+        for (j, sibling) in (0..).zip(auth.chunks_exact(n)) {
+            adrs.set_tree_height(j + 1);
+            adrs.set_tree_index(adrs.tree_index() / 2);
+
+            node = if (index >> j) & 1 == 0 {
+                ctx.h(adrs, &node, &to_node(sibling))
+            } else {
+                ctx.h(adrs, &to_node(sibling), &node)
+            };
+        }
+
+        *root = node;
+    }
+
+    let mut pk_adrs = *adrs;
+    pk_adrs.set_type_and_clear(AddressType::ForsRoots);
+    pk_adrs.set_key_pair(adrs.key_pair());
+
+    ctx.t(&pk_adrs, &roots[..k])
+}
+
+fn to_node(bytes: &[u8]) -> Node {
+    let mut node = [0; 32];
+    node[..bytes.len()].copy_from_slice(bytes);
+
+    node
+}
@@ -0,0 +1,167 @@
+//! Arithmetic modulo `p = 2^255 - 19`, only as much as Elligator 2 needs.
+//! curve25519-dalek keeps its field elements private.
+
+const MASK: u64 = (1 << 51) - 1;
+
+/// Field element in radix 2^51.
+#[derive(Clone, Copy)]
+pub(super) struct Fe([u64; 5]);
+
+impl Fe {
+    pub(super) const ONE: Self = Self([1, 0, 0, 0, 0]);
+
+    /// Montgomery curve constant `A = 486662`.
+    pub(super) const A: Self = Self([486662, 0, 0, 0, 0]);
+
+    /// Loads a little-endian integer, ignoring the most significant bit.
+    pub(super) fn from_bytes(bytes: &[u8; 32]) -> Self {
+        let mut limbs = [0; 5];
+        let (mut acc, mut bits) = (0u128, 0);
+        let mut bytes = bytes.iter();
+
+        for limb in &mut limbs {
+            while bits < 51 {
+                acc |= u128::from(*bytes.next().unwrap_or(&0)) << bits;
+                bits += 8;
+            }
+
+            *limb = acc as u64 & MASK;
+            acc >>= 51;
+            bits -= 51;
+        }
+
+        Self(limbs)
+    }
+
+    /// Canonical little-endian encoding.
+    pub(super) fn to_bytes(self) -> [u8; 32] {
+        let mut limbs = self.carry().0;
+
+        // q = 1 iff the value is at least p, i.e. adding 19 carries out of
+        // bit 255.
+        let q = limbs.iter().fold(19, |carry, limb| (limb + carry) >> 51);
+        limbs[0] += 19 * q;
+        for i in 0..4 {
+            limbs[i + 1] += limbs[i] >> 51;
+            limbs[i] &= MASK;
+        }
+        limbs[4] &= MASK;
+
+        let mut bytes = [0; 32];
+        let (mut acc, mut bits) = (0u128, 0);
+        let mut out = bytes.iter_mut();
+
+        for limb in limbs {
+            acc |= u128::from(limb) << bits;
+            bits += 51;
+
+            while bits >= 8 {
+                *out.next().unwrap() = acc as u8;
+                acc >>= 8;
+                bits -= 8;
+            }
+        }
+        *out.next().unwrap() = acc as u8;
+
+        bytes
+    }
+
+    /// Weak reduction of limbs below `2^52`.
+    fn carry(self) -> Self {
+        let mut limbs = self.0;
+        let carries = limbs.map(|limb| limb >> 51);
+
+        for limb in &mut limbs {
+            *limb &= MASK;
+        }
+        limbs[0] += carries[4] * 19;
+        for i in 1..5 {
+            limbs[i] += carries[i - 1];
+        }
+
+        Self(limbs)
+    }
+
+    pub(super) fn add(self, rhs: Self) -> Self {
+        Self(core::array::from_fn(|i| self.0[i] + rhs.0[i])).carry()
+    }
+
+    pub(super) fn sub(self, rhs: Self) -> Self {
+        // Adds 16p so that the limbs cannot underflow.
+        const P16: [u64; 5] = [
+            36028797018963664,
+            36028797018963952,
+            36028797018963952,
+            36028797018963952,
+            36028797018963952,
+        ];
+
+        Self(core::array::from_fn(|i| (self.0[i] + P16[i]) - rhs.0[i])).carry()
+    }
+
+    pub(super) fn neg(self) -> Self {
+        Self([0; 5]).sub(self)
+    }
+
+    pub(super) fn mul(self, rhs: Self) -> Self {
+        let (a, b) = (self.0, rhs.0);
+        let m = |x: u64, y: u64| u128::from(x) * u128::from(y);
+        let b19 = b.map(|limb| limb * 19);
+
+        let mut c = [
+            m(a[0], b[0]) + m(a[4], b19[1]) + m(a[3], b19[2]) + m(a[2], b19[3]) + m(a[1], b19[4]),
+            m(a[1], b[0]) + m(a[0], b[1]) + m(a[4], b19[2]) + m(a[3], b19[3]) + m(a[2], b19[4]),
+            m(a[2], b[0]) + m(a[1], b[1]) + m(a[0], b[2]) + m(a[4], b19[3]) + m(a[3], b19[4]),
+            m(a[3], b[0]) + m(a[2], b[1]) + m(a[1], b[2]) + m(a[0], b[3]) + m(a[4], b19[4]),
+            m(a[4], b[0]) + m(a[3], b[1]) + m(a[2], b[2]) + m(a[1], b[3]) + m(a[0], b[4]),
+        ];
+
+        for i in 0..4 {
+            c[i + 1] += c[i] >> 51;
+        }
+
+        let mut limbs = c.map(|c| c as u64 & MASK);
+        limbs[0] += (c[4] >> 51) as u64 * 19;
+        limbs[1] += limbs[0] >> 51;
+        limbs[0] &= MASK;
+
+        Self(limbs)
+    }
+
+    pub(super) fn square(self) -> Self {
+        self.mul(self)
+    }
+
+    /// Raises to a public exponent, given little-endian.
+    fn pow(self, exponent: &[u8; 32]) -> Self {
+        (0..256).rev().fold(Self::ONE, |acc, bit| {
+            let acc = acc.square();
+
+            if (exponent[bit / 8] >> (bit % 8)) & 1 == 1 {
+                acc.mul(self)
+            } else {
+                acc
+            }
+        })
+    }
+
+    /// Multiplicative inverse, `x^(p - 2)`; zero maps to zero.
+    pub(super) fn invert(self) -> Self {
+        let mut exponent = [0xff; 32];
+        exponent[0] = 0xeb;
+        exponent[31] = 0x7f;
+
+        self.pow(&exponent)
+    }
+
+    /// Whether the element is a square, `x^((p - 1) / 2) ∈ {0, 1}`.
+    pub(super) fn is_square(self) -> bool {
+        let mut exponent = [0xff; 32];
+        exponent[0] = 0xf6;
+        exponent[31] = 0x3f;
+
+        let legendre = self.pow(&exponent).to_bytes();
+
+        legendre == [0; 32] || legendre == Self::ONE.to_bytes()
+    }
+}
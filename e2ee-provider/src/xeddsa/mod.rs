@@ -0,0 +1,583 @@
+use crate::{
+    Error,
+    ec::{
+        agreement::{self, SharedSecret},
+        encoding::{
+            PrivateKeyBin, PrivateKeyDer, PrivateKeySerializer, PublicKeyBin, PublicKeyDer,
+            PublicKeySerializer,
+        },
+        signature::{self, SigningKey, VerifyingKey},
+    },
+};
+use alloc::{boxed::Box, vec::Vec};
+use curve25519_dalek::{EdwardsPoint, MontgomeryPoint, Scalar, traits::IsIdentity};
+use field::Fe;
+use sha2::{Digest, Sha512};
+use zeroize::Zeroize;
+
+/// Field arithmetic for Elligator 2.
+mod field;
+
+/// Fills the buffer with cryptographically secure random bytes.
+pub type FillRandom = fn(&mut [u8]) -> Result<(), Error>;
+
+/// Length of an XEdDSA signature.
+pub const SIGNATURE_LEN: usize = 64;
+
+/// Length of a VXEdDSA signature.
+pub const VRF_SIGNATURE_LEN: usize = 96;
+
+/// `PrivateKeyInfo` of an X25519 key up to the key bytes, RFC 8410.
+const PKCS8_PREFIX: [u8; 16] = [
+    0x30, 0x2e, 0x02, 0x01, 0x00, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x6e, 0x04, 0x22, 0x04, 0x20,
+];
+
+/// `SubjectPublicKeyInfo` of an X25519 key up to the key bytes, RFC 8410.
+const SPKI_PREFIX: [u8; 12] = [
+    0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x6e, 0x03, 0x21, 0x00,
+];
+
+/// `hash(X)`, SHA-512 over the concatenation of `parts`.
+fn hash(parts: &[&[u8]]) -> [u8; 64] {
+    parts
+        .iter()
+        .fold(Sha512::new(), |hash, part| hash.chain_update(part))
+        .finalize()
+        .into()
+}
+
+/// `hash_i(X) = hash(2^256 - 1 - i || X)`
+fn hash_i(i: u8, parts: &[&[u8]]) -> [u8; 64] {
+    let mut prefix = [0xff; 32];
+    prefix[0] -= i;
+
+    let mut hash = Sha512::new_with_prefix(prefix);
+    parts.iter().for_each(|part| hash.update(part));
+
+    hash.finalize().into()
+}
+
+fn reduce(hash: [u8; 64]) -> Scalar {
+    Scalar::from_bytes_mod_order_wide(&hash)
+}
+
+/// `elligator2(r)` with the non-square `Z = 2`.
+fn elligator2(r: Fe) -> Fe {
+    let r2 = r.square();
+    let u1 = Fe::A.neg().mul(Fe::ONE.add(r2.add(r2)).invert());
+    let w1 = u1.mul(u1.square().add(Fe::A.mul(u1)).add(Fe::ONE));
+
+    if w1.is_square() {
+        u1
+    } else {
+        Fe::A.neg().sub(u1)
+    }
+}
+
+/// `hash_to_point(X)`
+fn hash_to_point(parts: &[&[u8]]) -> Result<EdwardsPoint, Error> {
+    let h = hash(parts);
+    let r: [u8; 32] = h[..32].try_into().unwrap();
+    let u = elligator2(Fe::from_bytes(&r));
+
+    MontgomeryPoint(u.to_bytes())
+        .to_edwards(r[31] >> 7)
+        .map(|point| point.mul_by_cofactor())
+        .ok_or(Error::Unspecified)
+}
+
+/// `convert_mont(u)`, rejecting `u ≥ p`.
+fn convert_mont(u: &[u8; 32]) -> Option<EdwardsPoint> {
+    if Fe::from_bytes(u).to_bytes() != *u {
+        return None;
+    }
+
+    MontgomeryPoint(*u).to_edwards(0)
+}
+
+/// Whether a little-endian scalar is below `2^253`.
+fn is_below_2_253(bytes: &[u8]) -> bool {
+    bytes[31] & 0xe0 == 0
+}
+
+/// X25519 private key that signs with XEdDSA and VXEdDSA, as specified by
+/// [Signal](https://signal.org/docs/specifications/xeddsa/).
+///
+/// The same key performs X25519 key agreement, so one identity key can both
+/// agree and sign. It implements both [`agreement::PrivateKey`] and
+/// [`SigningKey`]; clone it to box it as both.
+#[derive(Clone)]
+pub struct XEdDsaPrivateKey {
+    k: [u8; 32],
+    fill: FillRandom,
+}
+
+impl Drop for XEdDsaPrivateKey {
+    fn drop(&mut self) {
+        self.k.zeroize();
+    }
+}
+
+impl XEdDsaPrivateKey {
+    /// Loads an X25519 private key, clamping it.
+    pub fn from_x25519_bytes(k: &[u8], fill: FillRandom) -> Result<Self, Error> {
+        let mut k: [u8; 32] = k.try_into().map_err(|_| Error::KeyRejected)?;
+        k[0] &= 248;
+        k[31] &= 127;
+        k[31] |= 64;
+
+        Ok(Self { k, fill })
+    }
+
+    /// Loads an X25519 private key from PKCS#8 v1 DER.
+    pub fn from_pkcs8(der: &[u8], fill: FillRandom) -> Result<Self, Error> {
+        match der.strip_prefix(&PKCS8_PREFIX[..]) {
+            Some(k) => Self::from_x25519_bytes(k, fill),
+            None => Err(Error::KeyRejected),
+        }
+    }
+
+    /// Generates a new private key.
+    pub fn generate(fill: FillRandom) -> Result<Self, Error> {
+        let mut k = [0; 32];
+        fill(&mut k)?;
+        let key = Self::from_x25519_bytes(&k, fill);
+        k.zeroize();
+
+        key
+    }
+
+    /// X25519 public key, the Montgomery u-coordinate.
+    pub fn public_key(&self) -> [u8; 32] {
+        MontgomeryPoint::mul_base_clamped(self.k).to_bytes()
+    }
+
+    /// `calculate_key_pair(k)`
+    ///
+    /// Returns the twisted Edwards public key with its sign bit cleared and
+    /// the private scalar matching it.
+    fn calculate_key_pair(&self) -> ([u8; 32], Scalar) {
+        let mut a = EdwardsPoint::mul_base_clamped(self.k).compress().to_bytes();
+        let sign = a[31] >> 7;
+        a[31] &= 0x7f;
+
+        // k or -k, without branching on the secret sign bit.
+        let k = Scalar::from_bytes_mod_order(self.k);
+        let scalar = k - k * Scalar::from(2 * u64::from(sign));
+
+        (a, scalar)
+    }
+
+    /// `xeddsa_sign(k, M, Z)` with caller-provided 64 bytes of randomness.
+    pub fn sign_with_random(&self, message: &[u8], random: &[u8; 64]) -> [u8; SIGNATURE_LEN] {
+        let (a_point, mut a) = self.calculate_key_pair();
+        let mut r = reduce(hash_i(1, &[a.as_bytes(), message, random]));
+        let r_point = EdwardsPoint::mul_base(&r).compress().to_bytes();
+        let h = reduce(hash(&[&r_point, &a_point, message]));
+        let s = r + h * a;
+        a.zeroize();
+        r.zeroize();
+
+        let mut signature = [0; SIGNATURE_LEN];
+        signature[..32].copy_from_slice(&r_point);
+        signature[32..].copy_from_slice(s.as_bytes());
+
+        signature
+    }
+
+    /// `vxeddsa_sign(k, M, Z)` with caller-provided 64 bytes of randomness.
+    ///
+    /// Returns the signature and the VRF output.
+    pub fn vxeddsa_sign_with_random(
+        &self,
+        message: &[u8],
+        random: &[u8; 64],
+    ) -> Result<([u8; VRF_SIGNATURE_LEN], [u8; 32]), Error> {
+        let (a_point, mut a) = self.calculate_key_pair();
+        let b_v = hash_to_point(&[&a_point, message])?;
+        let v = (a * b_v).compress().to_bytes();
+        let mut r = reduce(hash_i(3, &[a.as_bytes(), &v, random]));
+        let r_point = EdwardsPoint::mul_base(&r).compress().to_bytes();
+        let r_v = (r * b_v).compress().to_bytes();
+        let h = reduce(hash_i(4, &[&a_point, &v, &r_point, &r_v, message]));
+        let s = r + h * a;
+        a.zeroize();
+        r.zeroize();
+
+        let mut signature = [0; VRF_SIGNATURE_LEN];
+        signature[..32].copy_from_slice(&v);
+        signature[32..64].copy_from_slice(h.as_bytes());
+        signature[64..].copy_from_slice(s.as_bytes());
+
+        Ok((signature, vrf_output(&v)?))
+    }
+
+    /// `vxeddsa_sign(k, M, Z)`
+    ///
+    /// Returns the signature and the VRF output.
+    pub fn vxeddsa_sign(
+        &self,
+        message: &[u8],
+    ) -> Result<([u8; VRF_SIGNATURE_LEN], [u8; 32]), Error> {
+        let mut random = [0; 64];
+        (self.fill)(&mut random)?;
+
+        self.vxeddsa_sign_with_random(message, &random)
+    }
+}
+
+/// `hash_5(cV) (mod 2^b)`
+fn vrf_output(v: &[u8; 32]) -> Result<[u8; 32], Error> {
+    let v = curve25519_dalek::edwards::CompressedEdwardsY(*v)
+        .decompress()
+        .ok_or(Error::Unspecified)?;
+
+    Ok(
+        hash_i(5, &[v.mul_by_cofactor().compress().as_bytes()])[..32]
+            .try_into()
+            .unwrap(),
+    )
+}
+
+impl SigningKey for XEdDsaPrivateKey {
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut random = [0; 64];
+        (self.fill)(&mut random)?;
+
+        Ok(self.sign_with_random(message, &random).to_vec())
+    }
+
+    fn compute_public_key(&self) -> Result<Box<dyn PublicKeySerializer>, Error> {
+        Ok(Box::new(X25519PublicKeySerializer {
+            u: self.public_key(),
+        }))
+    }
+
+    fn algorithm(&self) -> signature::Algorithm {
+        signature::Algorithm::XEdDsa25519
+    }
+
+    fn to_serializer(self: Box<Self>) -> Box<dyn PrivateKeySerializer> {
+        Box::new(X25519PrivateKeySerializer { k: self.k })
+    }
+}
+
+impl agreement::PrivateKey for XEdDsaPrivateKey {
+    fn agree(&self, peer_public_key: Box<dyn agreement::PublicKey>) -> Result<SharedSecret, Error> {
+        let serializer = peer_public_key.to_serializer();
+        let u: [u8; 32] = match serializer.as_be_bytes() {
+            Ok(bin) => bin.as_ref().try_into(),
+            Err(_) => serializer
+                .as_x509_der()?
+                .as_ref()
+                .strip_prefix(&SPKI_PREFIX[..])
+                .ok_or(Error::KeyRejected)?
+                .try_into(),
+        }
+        .map_err(|_| Error::KeyRejected)?;
+
+        let shared_secret = MontgomeryPoint(u).mul_clamped(self.k).to_bytes();
+        if shared_secret == [0; 32] {
+            return Err(Error::Unspecified);
+        }
+
+        SharedSecret::new(&shared_secret)
+    }
+
+    fn compute_public_key(&self) -> Result<Box<dyn PublicKeySerializer>, Error> {
+        SigningKey::compute_public_key(self)
+    }
+
+    fn algorithm(&self) -> agreement::Algorithm {
+        agreement::Algorithm::X25519
+    }
+
+    fn to_serializer(self: Box<Self>) -> Box<dyn PrivateKeySerializer> {
+        SigningKey::to_serializer(self)
+    }
+}
+
+/// XEdDSA verifying key: an X25519 public key.
+///
+/// Also usable as the peer key of an X25519 key agreement.
+pub struct XEdDsaVerifyingKey {
+    u: [u8; 32],
+}
+
+impl XEdDsaVerifyingKey {
+    /// Loads an X25519 public key (Montgomery u-coordinate).
+    pub fn from_x25519_bytes(u: &[u8]) -> Result<Self, Error> {
+        let u: [u8; 32] = u.try_into().map_err(|_| Error::KeyRejected)?;
+
+        match convert_mont(&u) {
+            Some(_) => Ok(Self { u }),
+            None => Err(Error::KeyRejected),
+        }
+    }
+
+    /// Loads an X25519 public key from X.509 `SubjectPublicKeyInfo` DER.
+    pub fn from_x509_der(der: &[u8]) -> Result<Self, Error> {
+        match der.strip_prefix(&SPKI_PREFIX[..]) {
+            Some(u) => Self::from_x25519_bytes(u),
+            None => Err(Error::KeyRejected),
+        }
+    }
+
+    /// `vxeddsa_verify(u, M, (V || h || s))`
+    ///
+    /// Returns the VRF output if the signature is valid.
+    pub fn vxeddsa_verify(&self, message: &[u8], signature: &[u8]) -> Option<[u8; 32]> {
+        if signature.len() != VRF_SIGNATURE_LEN {
+            return None;
+        }
+
+        let (v_bytes, rest) = signature.split_at(32);
+        let (h_bytes, s_bytes) = rest.split_at(32);
+        if !is_below_2_253(h_bytes) || !is_below_2_253(s_bytes) {
+            return None;
+        }
+
+        let a_point = convert_mont(&self.u)?;
+        let a_bytes = a_point.compress().to_bytes();
+        let v = curve25519_dalek::edwards::CompressedEdwardsY(v_bytes.try_into().unwrap())
+            .decompress()?;
+        let b_v = hash_to_point(&[&a_bytes, message]).ok()?;
+        if a_point.is_small_order() || v.is_small_order() || b_v.is_identity() {
+            return None;
+        }
+
+        let h = Scalar::from_canonical_bytes(h_bytes.try_into().unwrap()).into_option()?;
+        let s = Scalar::from_bytes_mod_order(s_bytes.try_into().unwrap());
+        let r_point = EdwardsPoint::vartime_double_scalar_mul_basepoint(&-h, &a_point, &s);
+        let r_v = s * b_v - h * v;
+        let h_check = reduce(hash_i(
+            4,
+            &[
+                &a_bytes,
+                v_bytes,
+                r_point.compress().as_bytes(),
+                r_v.compress().as_bytes(),
+                message,
+            ],
+        ));
+
+        if h_check != h {
+            return None;
+        }
+
+        vrf_output(&v_bytes.try_into().unwrap()).ok()
+    }
+}
+
+impl VerifyingKey for XEdDsaVerifyingKey {
+    /// `xeddsa_verify(u, M, (R || s))`
+    ///
+    /// libsignal signs with the Edwards key as is, not with its negation,
+    /// and carries the sign bit of the key in the top bit of `s`. That bit is
+    /// always clear in signatures made as specified, so both are accepted.
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), Error> {
+        if signature.len() != SIGNATURE_LEN {
+            return Err(Error::BadSignature);
+        }
+
+        let (r_bytes, s_bytes) = signature.split_at(32);
+        let mut s_bytes: [u8; 32] = s_bytes.try_into().unwrap();
+        let sign = s_bytes[31] >> 7;
+        s_bytes[31] &= 0x7f;
+        if !is_below_2_253(&s_bytes) {
+            return Err(Error::NonCanonicalSignature);
+        }
+
+        let mut a_point = convert_mont(&self.u).ok_or(Error::BadSignature)?;
+        if sign == 1 {
+            a_point = -a_point;
+        }
+        let h = reduce(hash(&[r_bytes, a_point.compress().as_bytes(), message]));
+        let s = Scalar::from_bytes_mod_order(s_bytes);
+
+        if EdwardsPoint::vartime_double_scalar_mul_basepoint(&-h, &a_point, &s)
+            .compress()
+            .as_bytes()
+            == r_bytes
+        {
+            Ok(())
+        } else {
+            Err(Error::BadSignature)
+        }
+    }
+
+    fn algorithm(&self) -> signature::Algorithm {
+        signature::Algorithm::XEdDsa25519
+    }
+
+    fn to_serializer(self: Box<Self>) -> Box<dyn PublicKeySerializer> {
+        Box::new(X25519PublicKeySerializer { u: self.u })
+    }
+}
+
+impl agreement::PublicKey for XEdDsaVerifyingKey {
+    fn algorithm(&self) -> agreement::Algorithm {
+        agreement::Algorithm::X25519
+    }
+
+    fn to_serializer(self: Box<Self>) -> Box<dyn PublicKeySerializer> {
+        VerifyingKey::to_serializer(self)
+    }
+}
+
+struct X25519PublicKeySerializer {
+    u: [u8; 32],
+}
+
+impl PublicKeySerializer for X25519PublicKeySerializer {
+    fn as_x509_der(&self) -> Result<PublicKeyDer, Error> {
+        Ok(PublicKeyDer::X509Key([&SPKI_PREFIX[..], &self.u].concat()))
+    }
+
+    fn as_rfc_5915_public_key_der(&self) -> Result<PublicKeyDer, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_be_bytes(&self) -> Result<PublicKeyBin, Error> {
+        Ok(PublicKeyBin::Uncompreessed(self.u.to_vec()))
+    }
+
+    fn as_compressed_be_bytes(&self) -> Result<PublicKeyBin, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+}
+
+struct X25519PrivateKeySerializer {
+    k: [u8; 32],
+}
+
+impl Drop for X25519PrivateKeySerializer {
+    fn drop(&mut self) {
+        self.k.zeroize();
+    }
+}
+
+impl PrivateKeySerializer for X25519PrivateKeySerializer {
+    fn as_pkcs8v1_der(&self) -> Result<PrivateKeyDer, Error> {
+        Ok(PrivateKeyDer::Pkcs8V1Key(
+            [&PKCS8_PREFIX[..], &self.k].concat(),
+        ))
+    }
+
+    fn as_pkcs8v2_der(&self) -> Result<PrivateKeyDer, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_rfc_5915_private_key_der(&self) -> Result<PrivateKeyDer, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_ed_ec_be_bytes(&self) -> Result<PrivateKeyBin, Error> {
+        Ok(PrivateKeyBin::EdEcSeed(self.k.to_vec()))
+    }
+
+    fn as_ec_be_bytes(&self) -> Result<PrivateKeyBin, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+
+    fn as_raw_bytes(&self) -> Result<PrivateKeyBin, Error> {
+        Err(Error::UnsupportedEncoding)
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn test() -> Result<(), Error> {
+    use agreement::PrivateKey;
+
+    fn no_random(_: &mut [u8]) -> Result<(), Error> {
+        Err(Error::Unspecified)
+    }
+
+    // Generated with an independent big-integer implementation of the Signal
+    // specification, Z = 40..7f. Public keys match OpenSSL's X25519.
+    let random: [u8; 64] = core::array::from_fn(|i| 0x40 + i as u8);
+    let message = b"signed prekey";
+    for (k, u, xeddsa, vxeddsa, vrf) in [
+        (
+            "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+            "8f40c5adb68f25624ae5b214ea767a6ec94d829d3d7b5e1ad1ba6f3e2138285f",
+            "7323f76da3def35701260c1063e74e78c4e0e1a18e41d1d45b330545553864996f4690635bb66184838bd1a768ed7362b082095d264658fba0874d882862800f",
+            "ee72a551a3cd67bbbf98fbe96cbdba4d694ac17492bf15766c2fe51dfaef4175c5afb437615f20648b8e9c0f41021642fd0b4b4aeb86e74ebae5bbc8988e5705cf94283266fd79777340c4cd4baed65192cac5d41119b86422ca0df620b28603",
+            "fba9f70f18ec669adde5a17f79cfb008d8b8807f086eb1b37bc9d52054662feb",
+        ),
+        (
+            "202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f",
+            "358072d6365880d1aeea329adf9121383851ed21a28e3b75e965d0d2cd166254",
+            "da938cde1c83516814d732640994259b25fe503db059a126d24834141c10de6f4868b588a560149519d2cf00d4387aad72c8d1b7308ec240917b3868b715f20f",
+            "4802d3ca7ba9021dd5d7859a8d62a941817576b842954e04494a04bbbc87eab8f4bacfe20569b88691361f9d0629203b6fc27f6cb60807387afde489fd15ff07ee1560cbd23f77816d1d3213723ded135921421635503b7d5e745f6ddc0be50b",
+            "f8f3dab57a1636350a8b2e34d570a7333f666e5f711ce9416cbe28230e25e31b",
+        ),
+    ] {
+        let key = XEdDsaPrivateKey::from_x25519_bytes(&hex::decode(k).unwrap(), no_random)?;
+        let verifying_key = XEdDsaVerifyingKey::from_x25519_bytes(&key.public_key())?;
+        assert_eq!(hex::encode(key.public_key()), u);
+
+        let signature = key.sign_with_random(message, &random);
+        assert_eq!(hex::encode(signature), xeddsa);
+        verifying_key.verify(message, &signature)?;
+        assert!(verifying_key.verify(b"signed prekeys", &signature).is_err());
+
+        let (signature, output) = key.vxeddsa_sign_with_random(message, &random)?;
+        assert_eq!(hex::encode(signature), vxeddsa);
+        assert_eq!(hex::encode(output), vrf);
+        assert_eq!(
+            verifying_key.vxeddsa_verify(message, &signature),
+            Some(output)
+        );
+        assert_eq!(
+            verifying_key.vxeddsa_verify(b"signed prekeys", &signature),
+            None
+        );
+
+        // XEdDSA signatures are Ed25519 signatures under the converted key.
+        #[cfg(feature = "aws_lc_rs")]
+        {
+            let (a_point, _) = key.calculate_key_pair();
+            let signature = hex::decode(xeddsa).unwrap();
+            aws_lc_rs::signature::UnparsedPublicKey::new(&aws_lc_rs::signature::ED25519, a_point)
+                .verify(message, &signature)?;
+        }
+    }
+
+    // libsignal's `Curve25519Test.testSignature`: Alice's identity key signs
+    // her serialized ephemeral key, with the sign bit in the signature.
+    let key = XEdDsaPrivateKey::from_x25519_bytes(
+        &hex::decode("c097248412e58bf05df487968205132794178e367637f5818f81e0e6ce73e865").unwrap(),
+        no_random,
+    )?;
+    let verifying_key = XEdDsaVerifyingKey::from_x25519_bytes(&key.public_key())?;
+    let message =
+        hex::decode("05edce9d9c415ca78cb7252e72c2c4a554d3eb29485a0e1d503118d1a82d99fb4a").unwrap();
+    let mut signature = hex::decode(
+        "5de88ca9a89b4a115da79109c67c9c7464a3e4180274f1cb8c63c2984e286dfbede82deb9dcd9fae0bfbb821569b3d9001bd8130cd11d486cef047bd60b86e88",
+    )
+    .unwrap();
+
+    assert_eq!(
+        hex::encode(key.public_key()),
+        "ab7e717d4a163b7d9a1d8071dfe9dcf8cdcd1cea3339b6356be84d887e322c64"
+    );
+    verifying_key.verify(&message, &signature)?;
+    verifying_key.verify(&message, &key.sign_with_random(&message, &random))?;
+
+    signature[63] ^= 0x80;
+    assert!(verifying_key.verify(&message, &signature).is_err());
+
+    // The identity key agrees as well as it signs.
+    let alice = XEdDsaPrivateKey::from_x25519_bytes(&[1; 32], no_random)?;
+    let bob = XEdDsaPrivateKey::from_x25519_bytes(&[2; 32], no_random)?;
+    let bob_public = XEdDsaVerifyingKey::from_x25519_bytes(&bob.public_key())?;
+    let alice_public = XEdDsaVerifyingKey::from_x25519_bytes(&alice.public_key())?;
+    assert_eq!(
+        alice.agree(Box::new(bob_public))?.as_ref(),
+        bob.agree(Box::new(alice_public))?.as_ref()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use crate::Error;
+use curve25519_dalek::{EdwardsPoint, Scalar, edwards::CompressedEdwardsY, traits::IsIdentity};
+use sha2::{Digest, Sha512};
+
+/// Verifies an Ed25519 signature with the ZIP-215 rules
+///
+/// `[8] ([S] B - R - [k] A) == 0`,
+///
+/// decoding `A` and `R` without rejecting non-canonical encodings. `S` has to
+/// be reduced, as in RFC 8032.
+pub(crate) fn verify(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<(), Error> {
+    let (Ok(a_bytes), Ok(signature)) = (
+        <&[u8; 32]>::try_from(public_key),
+        <&[u8; 64]>::try_from(signature),
+    ) else {
+        return Err(Error::BadSignature);
+    };
+
+    let (r_bytes, s_bytes) = signature.split_at(32);
+    let (Some(a_point), Some(r_point)) = (
+        CompressedEdwardsY(*a_bytes).decompress(),
+        CompressedEdwardsY(r_bytes.try_into().unwrap()).decompress(),
+    ) else {
+        return Err(Error::BadSignature);
+    };
+    let Some(s) = Option::<Scalar>::from(Scalar::from_canonical_bytes(s_bytes.try_into().unwrap()))
+    else {
+        return Err(Error::NonCanonicalSignature);
+    };
+
+    let k = Scalar::from_bytes_mod_order_wide(
+        &Sha512::new()
+            .chain_update(r_bytes)
+            .chain_update(a_bytes)
+            .chain_update(message)
+            .finalize()
+            .into(),
+    );
+
+    let check = EdwardsPoint::vartime_double_scalar_mul_basepoint(&-k, &a_point, &s) - r_point;
+
+    if check.mul_by_cofactor().is_identity() {
+        Ok(())
+    } else {
+        Err(Error::BadSignature)
+    }
+}
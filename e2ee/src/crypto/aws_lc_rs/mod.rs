@@ -1,21 +1,13 @@
 use super::CryptoProvider;
 
-/// A `CryptoProvider` unit type backed by aws-lc-rs.
-pub struct AwsLcRs;
+/// The aws-lc-rs backend of [`e2ee_provider`].
+#[doc(no_inline)]
+pub use e2ee_provider::aws_lc_rs::AwsLcRs;
 
-mod hkdf;
+static BACKEND: AwsLcRs = AwsLcRs;
 
-mod hash;
-
-mod key;
-
-static PROVIDER: AwsLcRs = AwsLcRs;
-
-/// A `CryptoProvider` backed by aws-lc-rs.
+/// A `CryptoProvider` backed by aws-lc-rs, using every function of
+/// [`e2ee_provider::aws_lc_rs`].
 pub fn default_provider() -> CryptoProvider {
-    CryptoProvider {
-        hkdf: &PROVIDER,
-        hash: &PROVIDER,
-        key: &PROVIDER,
-    }
+    CryptoProvider::from_backend(&BACKEND)
 }
@@ -0,0 +1,589 @@
+use super::{
+    super::{key, signature},
+    EcProvider,
+};
+use crate::Error;
+use alloc::{boxed::Box, vec::Vec};
+use core::{any::Any, marker::PhantomData};
+use e2ee_provider::ec::{
+    KeyProvider, agreement,
+    encoding::{PrivateKeyBin, PrivateKeyDer, PublicKeyBin, PublicKeyDer},
+};
+
+/// PKCS #8 encoding of an X25519 private key (RFC 8410), up to the 32 bytes of
+/// the key itself. Backends read and write X25519 private keys raw.
+const X25519_PKCS8_PREFIX: &[u8] = &[
+    0x30, 0x2e, 0x02, 0x01, 0x00, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x6e, 0x04, 0x22, 0x04, 0x20,
+];
+
+/// DER of the `id-ecPublicKey` algorithm identifier (RFC 5480).
+const ID_EC_PUBLIC_KEY: &[u8] = &[0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+
+/// `SubjectPublicKeyInfo` algorithm identifier of `algorithm` (RFC 5480,
+/// RFC 8410).
+fn spki_algorithm(algorithm: key::Algorithm) -> Result<Vec<u8>, Error> {
+    let parameters: &[u8] = match algorithm {
+        key::Algorithm::X25519 => return Ok(der(0x30, &[0x06, 0x03, 0x2b, 0x65, 0x6e])),
+        key::Algorithm::EcdhP256 => &[0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07],
+        key::Algorithm::EcdhP384 => &[0x06, 0x05, 0x2b, 0x81, 0x04, 0x00, 0x22],
+        key::Algorithm::EcdhP521 => &[0x06, 0x05, 0x2b, 0x81, 0x04, 0x00, 0x23],
+        _ => return Err(Error::UnsupportedAlgorithm),
+    };
+
+    Ok(der(0x30, &[ID_EC_PUBLIC_KEY, parameters].concat()))
+}
+
+/// DER `SubjectPublicKeyInfo` of an encoded point.
+fn spki(algorithm: key::Algorithm, point: &[u8]) -> Result<Vec<u8>, Error> {
+    Ok(der(
+        0x30,
+        &[
+            spki_algorithm(algorithm)?,
+            der(0x03, &[&[0], point].concat()),
+        ]
+        .concat(),
+    ))
+}
+
+/// DER element of `tag` around `contents`.
+fn der(tag: u8, contents: &[u8]) -> Vec<u8> {
+    let len = contents.len().to_be_bytes();
+    let len = match contents.len() {
+        0..=0x7f => &len[len.len() - 1..],
+        0x80..=0xff => &[0x81, len[len.len() - 1]],
+        _ => &[0x82, len[len.len() - 2], len[len.len() - 1]],
+    };
+
+    [&[tag], len, contents].concat()
+}
+
+fn is_curve_supported(algorithm: key::Algorithm) -> bool {
+    matches!(
+        algorithm,
+        key::Algorithm::EcdhP256
+            | key::Algorithm::EcdhP384
+            | key::Algorithm::EcdhP521
+            | key::Algorithm::X25519
+    )
+}
+
+fn check(algorithm: key::Algorithm) -> Result<(), Error> {
+    match is_curve_supported(algorithm) {
+        true => Ok(()),
+        false => Err(Error::UnsupportedAlgorithm),
+    }
+}
+
+impl<P: KeyProvider + 'static> key::Provider for EcProvider<P> {
+    fn load_private_key(
+        &self,
+        algorithm: key::Algorithm,
+        key_der: &[u8],
+    ) -> Result<Box<dyn key::PrivateKey>, Error> {
+        check(algorithm)?;
+
+        // Kept in PKCS #8, which `as_der` returns.
+        let der = match algorithm {
+            // X25519 keys are accepted raw as well as in PKCS #8.
+            key::Algorithm::X25519 => PrivateKeyDer::Pkcs8V1Key(
+                [
+                    X25519_PKCS8_PREFIX,
+                    key_der.strip_prefix(X25519_PKCS8_PREFIX).unwrap_or(key_der),
+                ]
+                .concat(),
+            ),
+            _ => P::load_private_key_der(algorithm, &PrivateKeyDer::Pkcs8V1Key(key_der.to_vec()))?
+                .to_serializer()
+                .as_pkcs8v1_der()?,
+        };
+
+        Ok(Box::new(PrivateKey::<P> {
+            key: load_private_key::<P>(algorithm, &der)?,
+            der,
+            algorithm,
+            backend: PhantomData,
+        }))
+    }
+
+    fn load_public_key(
+        &self,
+        algorithm: key::Algorithm,
+        key_der: Vec<u8>,
+    ) -> Result<Box<dyn key::PublicKey>, Error> {
+        check(algorithm)?;
+
+        Ok(Box::new(PublicKey::<P>::parse(algorithm, key_der)?))
+    }
+
+    fn generate_ephemeral_private_key(
+        &self,
+        algorithm: key::Algorithm,
+    ) -> Result<Box<dyn key::EphemeralPrivateKey>, Error> {
+        check(algorithm)?;
+
+        Ok(Box::new(EphemeralPrivateKey::<P> {
+            key: P::generate_ephemeral_private_key(algorithm)?,
+            algorithm,
+            backend: PhantomData,
+        }))
+    }
+
+    fn is_curve_supported(&self, algorithm: key::Algorithm) -> bool {
+        // Backends cannot list their curves, so a key is generated.
+        is_curve_supported(algorithm) && P::generate_ephemeral_private_key(algorithm).is_ok()
+    }
+}
+
+/// Loads a PKCS #8 private key, X25519 ones from their raw bytes.
+fn load_private_key<P: KeyProvider>(
+    algorithm: key::Algorithm,
+    der: &PrivateKeyDer,
+) -> Result<Box<dyn agreement::PrivateKey>, Error> {
+    match der.as_ref().strip_prefix(X25519_PKCS8_PREFIX) {
+        Some(seed) if algorithm == key::Algorithm::X25519 => Ok(P::load_private_key_bin(
+            algorithm,
+            &PrivateKeyBin::EdEcSeed(seed.to_vec()),
+        )?),
+        _ => Ok(P::load_private_key_der(algorithm, der)?),
+    }
+}
+
+/// Public key of a backend key, parsed by [`PublicKey::parse`].
+fn public_key<P: KeyProvider + 'static>(
+    algorithm: key::Algorithm,
+    serializer: &dyn e2ee_provider::ec::encoding::PublicKeySerializer,
+) -> Result<Box<dyn key::PublicKey>, Error> {
+    match serializer.as_be_bytes()? {
+        PublicKeyBin::Compressed(ref bytes) | PublicKeyBin::Uncompreessed(ref bytes) => {
+            Ok(Box::new(PublicKey::<P>::parse(algorithm, bytes.clone())?))
+        }
+    }
+}
+
+struct PrivateKey<P> {
+    key: Box<dyn agreement::PrivateKey>,
+    der: PrivateKeyDer,
+    algorithm: key::Algorithm,
+    backend: PhantomData<fn() -> P>,
+}
+
+impl<P: KeyProvider + 'static> key::PrivateKey for PrivateKey<P> {
+    fn agree(&self, peer_public_key: &dyn key::PublicKey) -> Result<key::SharedSecret, Error> {
+        Ok(self
+            .key
+            .agree(backend_public_key::<P>(self.algorithm, peer_public_key)?)?)
+    }
+
+    fn compute_public_key(&self) -> Result<Box<dyn key::PublicKey>, Error> {
+        public_key::<P>(self.algorithm, &*self.key.compute_public_key()?)
+    }
+
+    fn as_der(&self) -> Result<Vec<u8>, Error> {
+        Ok(self.der.as_ref().to_vec())
+    }
+
+    fn algorithm(&self) -> key::Algorithm {
+        self.algorithm
+    }
+
+    fn to_signing_key(&self) -> Result<Box<dyn signature::SigningKey>, Error> {
+        match self.algorithm {
+            key::Algorithm::X25519 => signature::Provider::load_signing_key(
+                &EcProvider::<P>::new(),
+                signature::Algorithm::XEdDsa25519,
+                self.der.as_ref(),
+            ),
+            _ => Err(Error::UnsupportedAlgorithm),
+        }
+    }
+}
+
+struct EphemeralPrivateKey<P> {
+    key: Box<dyn agreement::EphemeralPrivateKey>,
+    algorithm: key::Algorithm,
+    backend: PhantomData<fn() -> P>,
+}
+
+impl<P: KeyProvider + 'static> key::EphemeralPrivateKey for EphemeralPrivateKey<P> {
+    fn agree_ephemeral(
+        self: Box<Self>,
+        peer_public_key: &dyn key::PublicKey,
+    ) -> Result<key::SharedSecret, Error> {
+        let peer_public_key = backend_public_key::<P>(self.algorithm, peer_public_key)?;
+
+        Ok(self.key.agree_ephemeral(peer_public_key)?)
+    }
+
+    fn compute_public_key(&self) -> Result<Box<dyn key::PublicKey>, Error> {
+        public_key::<P>(self.algorithm, &*self.key.compute_public_key()?)
+    }
+
+    fn algorithm(&self) -> key::Algorithm {
+        self.algorithm
+    }
+}
+
+/// Public key parsed and validated by the backend at load time.
+struct PublicKey<P> {
+    key: Box<dyn agreement::PublicKey>,
+    raw: Vec<u8>,
+    point: Vec<u8>,
+    algorithm: key::Algorithm,
+    backend: PhantomData<fn() -> P>,
+}
+
+impl<P: KeyProvider> PublicKey<P> {
+    /// Loads `bytes`, a DER `SubjectPublicKeyInfo` or the encoded point,
+    /// with the backend.
+    fn parse(algorithm: key::Algorithm, bytes: Vec<u8>) -> Result<Self, Error> {
+        let is_der = match algorithm {
+            key::Algorithm::X25519 => bytes.len() != 32,
+            _ => bytes.first() == Some(&0x30),
+        };
+        let (key, point) = match is_der {
+            true => (
+                P::load_public_key_der(algorithm, &PublicKeyDer::X509Key(bytes.clone()))?,
+                subject_public_key(&bytes).ok_or(Error::KeyRejected)?,
+            ),
+            false => (
+                P::load_public_key_bin(algorithm, &bin(algorithm, &bytes))?,
+                &bytes[..],
+            ),
+        };
+
+        Ok(Self {
+            key,
+            raw: raw_bytes(algorithm, point).ok_or(Error::KeyRejected)?,
+            point: point.to_vec(),
+            algorithm,
+            backend: PhantomData,
+        })
+    }
+}
+
+/// Binary form of an encoded point.
+fn bin(algorithm: key::Algorithm, point: &[u8]) -> PublicKeyBin {
+    match point.first() {
+        Some(0x02 | 0x03) if algorithm != key::Algorithm::X25519 => {
+            PublicKeyBin::Compressed(point.to_vec())
+        }
+        _ => PublicKeyBin::Uncompreessed(point.to_vec()),
+    }
+}
+
+/// Canonical encoding of a validated point, see
+/// [`key::PublicKey::as_raw_bytes`].
+fn raw_bytes(algorithm: key::Algorithm, point: &[u8]) -> Option<Vec<u8>> {
+    Some(match point {
+        _ if algorithm == key::Algorithm::X25519 => {
+            let mut raw = point.to_vec();
+            // RFC 7748 ignores the most significant bit of u, and computes
+            // modulo p = 2^255 - 19.
+            *raw.last_mut()? &= 0x7f;
+            if let [low @ 0xed..=0xff, middle @ .., high @ 0x7f] = raw.as_mut_slice()
+                && middle.iter().all(|byte| *byte == 0xff)
+            {
+                // u - p, below 19
+                *low -= 0xed;
+                middle.fill(0);
+                *high = 0;
+            }
+            raw
+        }
+        // Uncompressed or hybrid point, compressed to `x` and the parity of
+        // `y`.
+        [0x04 | 0x06 | 0x07, coordinates @ ..] => {
+            let (x, y) = coordinates.split_at(coordinates.len() / 2);
+            [&[0x02 | (y.last()? & 1)], x].concat()
+        }
+        _ => point.to_vec(),
+    })
+}
+
+/// Point of a DER `SubjectPublicKeyInfo`, which the backend has validated.
+fn subject_public_key(der: &[u8]) -> Option<&[u8]> {
+    let (spki, _) = der_element(der, 0x30)?;
+    let (_algorithm, spki) = der_element(spki, 0x30)?;
+    let (bits, _) = der_element(spki, 0x03)?;
+
+    bits.strip_prefix(&[0])
+}
+
+/// Splits the contents of the leading `tag` element of `der` from the rest.
+fn der_element(der: &[u8], tag: u8) -> Option<(&[u8], &[u8])> {
+    let (&[found, len], der) = der.split_first_chunk()?;
+    if found != tag {
+        return None;
+    }
+
+    let (len, der) = match len {
+        0..=0x7f => (len as usize, der),
+        0x81 => (*der.first()? as usize, &der[1..]),
+        0x82 => {
+            let (&len, der) = der.split_first_chunk()?;
+            (u16::from_be_bytes(len) as usize, der)
+        }
+        _ => return None,
+    };
+
+    der.split_at_checked(len)
+}
+
+/// Backend key of `public_key`: the one parsed at load time for keys of the
+/// backend `P`, otherwise loaded from its canonical encoding, so that keys of
+/// any provider agree.
+fn backend_public_key<P: KeyProvider + 'static>(
+    algorithm: key::Algorithm,
+    public_key: &dyn key::PublicKey,
+) -> Result<Box<dyn agreement::PublicKey>, Error> {
+    if public_key.algorithm() != algorithm {
+        return Err(Error::KeyRejected);
+    }
+
+    let any: &dyn Any = public_key;
+    if let Some(key) = any
+        .downcast_ref::<PublicKey<P>>()
+        .and_then(|public_key| public_key.key.try_clone())
+    {
+        return Ok(key);
+    }
+
+    Ok(P::load_public_key_bin(
+        algorithm,
+        &bin(algorithm, public_key.as_raw_bytes()),
+    )?)
+}
+
+impl<P: KeyProvider + 'static> key::PublicKey for PublicKey<P> {
+    fn as_der(&self) -> Result<Vec<u8>, Error> {
+        match self.algorithm {
+            key::Algorithm::X25519 => spki(self.algorithm, &self.raw),
+            _ => spki(self.algorithm, &self.point),
+        }
+    }
+
+    fn as_raw_bytes(&self) -> &[u8] {
+        &self.raw
+    }
+
+    fn algorithm(&self) -> key::Algorithm {
+        self.algorithm
+    }
+
+    fn to_verifying_key(&self) -> Result<Box<dyn signature::VerifyingKey>, Error> {
+        match self.algorithm {
+            key::Algorithm::X25519 => signature::Provider::load_verifying_key(
+                &EcProvider::<P>::new(),
+                signature::Algorithm::XEdDsa25519,
+                &self.as_der()?,
+            ),
+            _ => Err(Error::UnsupportedAlgorithm),
+        }
+    }
+}
+
+#[test]
+#[cfg(all(test, feature = "aws_lc_rs"))]
+fn test() -> Result<(), Error> {
+    use crate::crypto::{aws_lc_rs, hash};
+    use alloc::string::ToString;
+    use e2ee_provider::aws_lc_rs::AwsLcRs;
+    use key::Provider;
+
+    let provider = EcProvider::<AwsLcRs>::new();
+
+    let der = AwsLcRs::load_private_key_bin(
+        key::Algorithm::EcdhP256,
+        &PrivateKeyBin::Ec(alloc::vec![0x42; 32]),
+    )?
+    .to_serializer()
+    .as_pkcs8v1_der()?;
+    let static_key = provider.load_private_key(key::Algorithm::EcdhP256, der.as_ref())?;
+    let ephemeral_key = provider.generate_ephemeral_private_key(key::Algorithm::EcdhP256)?;
+
+    let public_key = provider.load_public_key(
+        key::Algorithm::EcdhP256,
+        static_key.compute_public_key()?.as_der()?,
+    )?;
+    let ephemeral_public_key = ephemeral_key.compute_public_key()?;
+
+    // One loaded public key, several agreements.
+    let first = static_key.agree(ephemeral_public_key.as_ref())?;
+    let second = static_key.agree(ephemeral_public_key.as_ref())?;
+    let ephemeral = ephemeral_key.agree_ephemeral(public_key.as_ref())?;
+
+    assert_eq!(first.as_ref(), second.as_ref());
+    assert_eq!(first.as_ref(), ephemeral.as_ref());
+    assert_eq!(
+        provider
+            .load_private_key(key::Algorithm::EcdhP256, &static_key.as_der()?)?
+            .agree(ephemeral_public_key.as_ref())?
+            .as_ref(),
+        first.as_ref()
+    );
+
+    assert!(matches!(
+        provider.load_public_key(key::Algorithm::EcdhP256, alloc::vec![4; 65]),
+        Err(Error::KeyRejected)
+    ));
+    assert!(matches!(
+        provider.load_public_key(key::Algorithm::X25519, public_key.as_der()?),
+        Err(Error::KeyRejected)
+    ));
+    assert!(matches!(
+        static_key.agree(
+            provider
+                .generate_ephemeral_private_key(key::Algorithm::X25519)?
+                .compute_public_key()?
+                .as_ref()
+        ),
+        Err(Error::KeyRejected)
+    ));
+
+    // RFC 7748, section 6.1, with raw keys.
+    let alice = provider.load_private_key(
+        key::Algorithm::X25519,
+        &hex::decode("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a").unwrap(),
+    )?;
+    let bob = provider.load_public_key(
+        key::Algorithm::X25519,
+        hex::decode("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f").unwrap(),
+    )?;
+    let shared_secret =
+        hex::decode("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742").unwrap();
+
+    assert_eq!(alice.agree(bob.as_ref())?.as_ref(), shared_secret);
+    assert!(alice.compute_public_key()?.as_der()?.ends_with(
+        &hex::decode("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a").unwrap()
+    ));
+
+    let alice = provider.load_private_key(key::Algorithm::X25519, &alice.as_der()?)?;
+    assert_eq!(alice.agree(bob.as_ref())?.as_ref(), shared_secret);
+
+    let ephemeral_key = provider.generate_ephemeral_private_key(key::Algorithm::X25519)?;
+    let ephemeral_public_key = ephemeral_key.compute_public_key()?;
+    assert_eq!(
+        alice.agree(ephemeral_public_key.as_ref())?.as_ref(),
+        ephemeral_key
+            .agree_ephemeral(alice.compute_public_key()?.as_ref())?
+            .as_ref()
+    );
+
+    // Keys are equal whatever encoding they were loaded from.
+    let alice_public_key = provider.load_public_key(
+        key::Algorithm::X25519,
+        hex::decode("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a").unwrap(),
+    )?;
+    let compressed =
+        provider.load_public_key(key::Algorithm::EcdhP256, public_key.as_raw_bytes().to_vec())?;
+
+    assert!(*alice.compute_public_key()? == *alice_public_key);
+    assert!(*compressed == *public_key);
+    assert!(*compressed != *ephemeral_public_key);
+    assert_eq!(
+        std::collections::HashSet::<Box<dyn key::PublicKey>>::from([
+            alice_public_key,
+            alice.compute_public_key()?,
+            compressed,
+            public_key,
+        ])
+        .len(),
+        2
+    );
+
+    // Public keys convert into `SubjectPublicKeyInfo`, which loads back.
+    let der = alice.compute_public_key()?.as_der()?;
+    assert_eq!(
+        der,
+        hex::decode(
+            "302a300506032b656e0321008520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a"
+        )
+        .unwrap()
+    );
+    assert!(
+        *provider.load_public_key(key::Algorithm::X25519, der)? == *alice.compute_public_key()?
+    );
+
+    let public_key = static_key.compute_public_key()?;
+    let der = public_key.as_der()?;
+    assert!(der.starts_with(
+        &hex::decode("3059301306072a8648ce3d020106082a8648ce3d030107034200").unwrap()
+    ));
+    assert!(*provider.load_public_key(key::Algorithm::EcdhP256, der)? == *public_key);
+    let compressed =
+        provider.load_public_key(key::Algorithm::EcdhP256, public_key.as_raw_bytes().to_vec())?;
+    assert!(
+        *provider.load_public_key(key::Algorithm::EcdhP256, compressed.as_der()?)? == *public_key
+    );
+
+    let p521 = provider
+        .generate_ephemeral_private_key(key::Algorithm::EcdhP521)?
+        .compute_public_key()?;
+    assert!(*provider.load_public_key(key::Algorithm::EcdhP521, p521.as_der()?)? == *p521);
+
+    // u-coordinates of X25519 are canonical modulo p = 2^255 - 19.
+    let mut nine = alloc::vec![0; 32];
+    nine[0] = 9;
+    let mut p_plus_nine = alloc::vec![0xff; 32];
+    (p_plus_nine[0], p_plus_nine[31]) = (0xf6, 0x7f);
+    let base_point = provider.load_public_key(key::Algorithm::X25519, nine.clone())?;
+
+    for u in [p_plus_nine.clone(), [&nine[..31], &[0x80]].concat()] {
+        let public_key = provider.load_public_key(key::Algorithm::X25519, u)?;
+
+        assert!(*public_key == *base_point);
+        assert_eq!(public_key.as_raw_bytes(), nine);
+        assert_eq!(public_key.as_der()?, base_point.as_der()?);
+    }
+
+    // Keys of this backend agree with the key parsed at load time, those of
+    // other providers are loaded from their canonical encoding.
+    struct Foreign(Vec<u8>);
+
+    impl key::PublicKey for Foreign {
+        fn algorithm(&self) -> key::Algorithm {
+            key::Algorithm::X25519
+        }
+
+        fn as_der(&self) -> Result<Vec<u8>, Error> {
+            Err(Error::UnsupportedAlgorithm)
+        }
+
+        fn as_raw_bytes(&self) -> &[u8] {
+            &self.0
+        }
+
+        fn to_verifying_key(&self) -> Result<Box<dyn signature::VerifyingKey>, Error> {
+            Err(Error::UnsupportedAlgorithm)
+        }
+    }
+
+    let any: &dyn Any = bob.as_ref();
+    assert!(
+        any.downcast_ref::<PublicKey<AwsLcRs>>()
+            .and_then(|bob| bob.key.try_clone())
+            .is_some()
+    );
+    assert_eq!(
+        alice.agree(&Foreign(bob.as_raw_bytes().to_vec()))?.as_ref(),
+        shared_secret
+    );
+
+    let provider = aws_lc_rs::default_provider();
+    let bob = bob.as_ref();
+    let fingerprint = bob.fingerprint_with_provider(&provider, hash::Algorithm::Sha256, 16)?;
+
+    assert_eq!(fingerprint.to_string(), "f35e5616160a30bf3c6e79fa73c576d4");
+    assert_eq!(fingerprint.to_base32(), "6NPFMFQWBIYL6PDOPH5HHRLW2Q");
+    assert_eq!(
+        bob.fingerprint_with_provider(&provider, hash::Algorithm::Sha256, 13)?
+            .to_base32(),
+        "6NPFMFQWBIYL6PDOPH5HG"
+    );
+    assert!(
+        bob.fingerprint_with_provider(&provider, hash::Algorithm::Sha256, 33)
+            .is_err()
+    );
+
+    Ok(())
+}
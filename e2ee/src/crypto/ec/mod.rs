@@ -0,0 +1,32 @@
+use core::marker::PhantomData;
+
+mod key;
+
+mod signature;
+
+/// Key and signature providers of e2ee over the elliptic curve keys of an
+/// [`e2ee_provider::ec::KeyProvider`] backend.
+///
+/// Backends implement only the interface of [`e2ee_provider`]; this adapter
+/// adds what e2ee needs on top of it, such as canonical public key encodings
+/// and signature contexts. See [`CryptoProvider::from_backend`].
+///
+/// [`CryptoProvider::from_backend`]: super::CryptoProvider::from_backend
+pub struct EcProvider<P> {
+    backend: PhantomData<fn() -> P>,
+}
+
+impl<P> EcProvider<P> {
+    /// Adapter for the keys of backend `P`.
+    pub const fn new() -> Self {
+        Self {
+            backend: PhantomData,
+        }
+    }
+}
+
+impl<P> Default for EcProvider<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,256 @@
+use super::{super::signature, EcProvider};
+use crate::Error;
+use alloc::{boxed::Box, vec::Vec};
+use core::marker::PhantomData;
+use e2ee_provider::ec::{
+    KeyProvider,
+    encoding::{PrivateKeyDer, PublicKeyDer},
+};
+
+fn is_algorithm_supported(algorithm: signature::Algorithm) -> bool {
+    matches!(
+        algorithm,
+        signature::Algorithm::EcdsaP256Sha256Asn1
+            | signature::Algorithm::EcdsaP256Sha256Fixed
+            | signature::Algorithm::EcdsaP384Sha384Asn1
+            | signature::Algorithm::EcdsaP384Sha384Fixed
+            | signature::Algorithm::Ed25519
+            | signature::Algorithm::Ed25519Ctx
+            | signature::Algorithm::Ed25519Ph
+            | signature::Algorithm::XEdDsa25519
+    )
+}
+
+/// Whether the algorithm signs with a context string of its own.
+fn has_context(algorithm: signature::Algorithm) -> bool {
+    matches!(
+        algorithm,
+        signature::Algorithm::Ed25519Ctx | signature::Algorithm::Ed25519Ph
+    )
+}
+
+fn check(algorithm: signature::Algorithm) -> Result<(), Error> {
+    match is_algorithm_supported(algorithm) {
+        true => Ok(()),
+        false => Err(Error::UnsupportedAlgorithm),
+    }
+}
+
+impl<P: KeyProvider + 'static> signature::Provider for EcProvider<P> {
+    fn load_signing_key(
+        &self,
+        algorithm: signature::Algorithm,
+        key_der: &[u8],
+    ) -> Result<Box<dyn signature::SigningKey>, Error> {
+        check(algorithm)?;
+
+        let der = PrivateKeyDer::Pkcs8V1Key(key_der.to_vec());
+
+        Ok(Box::new(SigningKey::<P> {
+            key: P::load_signing_key_der(algorithm, &der)?,
+            der,
+            backend: PhantomData,
+        }))
+    }
+
+    fn generate_signing_key(
+        &self,
+        algorithm: signature::Algorithm,
+    ) -> Result<Box<dyn signature::SigningKey>, Error> {
+        check(algorithm)?;
+
+        // Loaded back from its encoding, which `as_der` returns.
+        let der = P::generate_signing_key(algorithm)?
+            .to_serializer()
+            .as_pkcs8v1_der()?;
+
+        Ok(Box::new(SigningKey::<P> {
+            key: P::load_signing_key_der(algorithm, &der)?,
+            der,
+            backend: PhantomData,
+        }))
+    }
+
+    fn load_verifying_key(
+        &self,
+        algorithm: signature::Algorithm,
+        key_der: &[u8],
+    ) -> Result<Box<dyn signature::VerifyingKey>, Error> {
+        check(algorithm)?;
+
+        let der = key_der.to_vec();
+
+        Ok(Box::new(VerifyingKey {
+            key: P::load_verifying_key_der(algorithm, &PublicKeyDer::X509Key(der.clone()))?,
+            der,
+        }))
+    }
+
+    fn is_algorithm_supported(&self, algorithm: signature::Algorithm) -> bool {
+        is_algorithm_supported(algorithm)
+    }
+}
+
+/// `message` bound to `context`, for algorithms without domain separation.
+fn bind_context(context: &[u8], message: &[u8]) -> Result<Vec<u8>, Error> {
+    let len = u8::try_from(context.len()).map_err(|_| Error::Unspecified)?;
+
+    Ok([&[len], context, message].concat())
+}
+
+struct SigningKey<P> {
+    key: Box<dyn e2ee_provider::ec::signature::SigningKey>,
+    der: PrivateKeyDer,
+    backend: PhantomData<fn() -> P>,
+}
+
+impl<P: KeyProvider + 'static> signature::SigningKey for SigningKey<P> {
+    fn sign(&self, context: &[u8], message: &[u8]) -> Result<Vec<u8>, Error> {
+        if has_context(self.algorithm()) {
+            return Ok(self.key.sign_with_context(context, message)?);
+        }
+
+        Ok(self.key.sign(&bind_context(context, message)?)?)
+    }
+
+    fn compute_verifying_key(&self) -> Result<Box<dyn signature::VerifyingKey>, Error> {
+        match self.key.compute_public_key()?.as_x509_der()? {
+            PublicKeyDer::X509Key(ref der) => signature::Provider::load_verifying_key(
+                &EcProvider::<P>::new(),
+                self.algorithm(),
+                der,
+            ),
+            _ => Err(Error::Unspecified),
+        }
+    }
+
+    fn algorithm(&self) -> signature::Algorithm {
+        self.key.algorithm()
+    }
+
+    fn as_der(&self) -> Result<Vec<u8>, Error> {
+        Ok(self.der.as_ref().to_vec())
+    }
+}
+
+struct VerifyingKey {
+    key: Box<dyn e2ee_provider::ec::signature::VerifyingKey>,
+    der: Vec<u8>,
+}
+
+impl signature::VerifyingKey for VerifyingKey {
+    fn verify(&self, context: &[u8], message: &[u8], signature: &[u8]) -> Result<(), Error> {
+        if has_context(self.algorithm()) {
+            return Ok(self.key.verify_with_context(context, message, signature)?);
+        }
+
+        Ok(self
+            .key
+            .verify(&bind_context(context, message)?, signature)?)
+    }
+
+    fn algorithm(&self) -> signature::Algorithm {
+        self.key.algorithm()
+    }
+
+    fn as_der(&self) -> Result<Vec<u8>, Error> {
+        Ok(self.der.clone())
+    }
+}
+
+#[test]
+#[cfg(all(test, feature = "aws_lc_rs"))]
+fn test() -> Result<(), Error> {
+    use crate::{ProtocolError, crypto::key, x3dh};
+    use e2ee_provider::aws_lc_rs::AwsLcRs;
+    use signature::Provider;
+
+    let provider = EcProvider::<AwsLcRs>::new();
+
+    for algorithm in [
+        signature::Algorithm::EcdsaP256Sha256Asn1,
+        signature::Algorithm::EcdsaP384Sha384Fixed,
+        signature::Algorithm::Ed25519,
+        signature::Algorithm::Ed25519Ctx,
+        signature::Algorithm::Ed25519Ph,
+        signature::Algorithm::XEdDsa25519,
+    ] {
+        let signing_key = provider.generate_signing_key(algorithm)?;
+        let signing_key = provider.load_signing_key(algorithm, &signing_key.as_der()?)?;
+        let verifying_key = provider
+            .load_verifying_key(algorithm, &signing_key.compute_verifying_key()?.as_der()?)?;
+
+        let signature = signing_key.sign(b"context", b"message")?;
+
+        verifying_key.verify(b"context", b"message", &signature)?;
+        assert!(
+            verifying_key
+                .verify(b"other context", b"message", &signature)
+                .is_err()
+        );
+        assert!(
+            verifying_key
+                .verify(b"context", b"other message", &signature)
+                .is_err()
+        );
+        assert!(signing_key.sign(&[0; 256], b"message").is_err());
+
+        // The context is the RFC 8032 one of Ed25519ctx and Ed25519ph, and is
+        // prepended to the message otherwise.
+        let key = AwsLcRs::load_verifying_key_der(
+            algorithm,
+            &PublicKeyDer::X509Key(verifying_key.as_der()?),
+        )?;
+        assert_eq!(
+            key.verify_with_context(b"context", b"message", &signature)
+                .is_ok(),
+            has_context(algorithm)
+        );
+        assert_eq!(
+            key.verify(b"\x07contextmessage", &signature).is_ok(),
+            !has_context(algorithm)
+        );
+    }
+
+    assert!(matches!(
+        provider.generate_signing_key(signature::Algorithm::RsaPssSha256),
+        Err(Error::UnsupportedAlgorithm)
+    ));
+
+    // Prekeys and device keys signed with an X3DH peer's identity key.
+    let peer = x3dh::Peer {
+        identitiy_key: key::Provider::load_private_key(
+            &provider,
+            key::Algorithm::X25519,
+            &[1; 32],
+        )?,
+        key_storage: None,
+    };
+    let identity = peer.identitiy_key.compute_public_key()?;
+    let prekey = key::Provider::load_private_key(&provider, key::Algorithm::X25519, &[2; 32])?
+        .compute_public_key()?;
+
+    let prekey_signature = peer.sign_prekey(prekey.as_ref())?;
+    let certificate = peer.certify_device(prekey.as_ref())?;
+
+    x3dh::verify_prekey(identity.as_ref(), prekey.as_ref(), &prekey_signature)?;
+    x3dh::verify_device_certificate(identity.as_ref(), prekey.as_ref(), &certificate)?;
+    assert_eq!(
+        x3dh::verify_prekey(identity.as_ref(), prekey.as_ref(), &certificate),
+        Err(Error::Protocol(ProtocolError::BadSignature))
+    );
+    assert_eq!(
+        x3dh::verify_prekey(prekey.as_ref(), prekey.as_ref(), &prekey_signature),
+        Err(Error::Protocol(ProtocolError::BadSignature))
+    );
+
+    // Only X25519 identity keys sign.
+    let p256 = key::Provider::generate_ephemeral_private_key(&provider, key::Algorithm::EcdhP256)?
+        .compute_public_key()?;
+    assert!(matches!(
+        p256.to_verifying_key(),
+        Err(Error::UnsupportedAlgorithm)
+    ));
+
+    Ok(())
+}
@@ -1,25 +1,23 @@
+use super::{
+    CryptoProvider, hash,
+    signature::{SigningKey, VerifyingKey},
+};
 use crate::Error;
-use alloc::{boxed::Box, vec::Vec};
-use core::any::Any;
-use zeroize::Zeroize;
-
-/// Available key curve algorithms.
-#[non_exhaustive]
-#[derive(Debug, Clone, Copy)]
-pub enum Algorithm {
-    /// NSA Suite B P-256 (secp256r1) curve.
-    EcP256,
-    /// NSA Suite B P-384 (secp384r1) curve.
-    EcP384,
-    /// NSA Suite B P-521 (secp521r1) curve.
-    EcP521,
-    /// Curve25519
-    Curve25519,
-}
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::{
+    any::Any,
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+#[doc(inline)]
+pub use e2ee_provider::ec::agreement::{Algorithm, MAX_SHARED_SECRET_LEN, SharedSecret};
 
 /// Mechanism for loading/generating keys.
 pub trait Provider: Send + Sync {
     /// Loads private key from binary.
+    ///
+    /// X25519 keys are also accepted as their raw 32 bytes.
     fn load_private_key(
         &self,
         algorithm: Algorithm,
@@ -27,6 +25,11 @@ pub trait Provider: Send + Sync {
     ) -> Result<Box<dyn PrivateKey>, Error>;
 
     /// Loads public key from binary.
+    ///
+    /// X25519 keys are also accepted as their raw 32 bytes.
+    ///
+    /// The key is validated once here, so that the returned [`PublicKey`] can
+    /// be used in any number of key agreements.
     fn load_public_key(
         &self,
         algorithm: Algorithm,
@@ -49,21 +52,148 @@ pub trait PublicKey: Any {
     /// The algorithm for the public key.
     fn algorithm(&self) -> Algorithm;
 
-    /// Used to standartize key agreement material across different
-    /// [`CryptoProvider`]s.
-    ///
-    /// [`CryptoProvider`]: super::CryptoProvider
+    /// Converts public key into a DER `SubjectPublicKeyInfo` (RFC 5480,
+    /// RFC 8410), whatever format it was loaded from.
     fn as_der(&self) -> Result<Vec<u8>, Error>;
+
+    /// Canonical encoding of the key: the compressed SEC 1 point on NIST
+    /// curves, the 32-byte u-coordinate with its unused top bit cleared and
+    /// reduced modulo 2^255 - 19 on X25519.
+    ///
+    /// Keys are compared and hashed by this encoding, whatever format they
+    /// were loaded from.
+    fn as_raw_bytes(&self) -> &[u8];
+
+    /// Verifying key of the signatures of [`PrivateKey::to_signing_key`].
+    ///
+    /// Returns [`Error::UnsupportedAlgorithm`] for keys that only agree.
+    fn to_verifying_key(&self) -> Result<Box<dyn VerifyingKey>, Error>;
+}
+
+impl dyn PublicKey {
+    /// Computes the fingerprint of the key: its canonical encoding hashed
+    /// with `algorithm`, truncated to `len` bytes.
+    ///
+    /// Uses the process-default [`CryptoProvider`], or the one installed with
+    /// [`CryptoProvider::install_scoped`].
+    ///
+    /// Returns [`Error::Unspecified`] if `len` is zero or longer than the
+    /// output of the hash function.
+    pub fn fingerprint(
+        &self,
+        algorithm: hash::Algorithm,
+        len: usize,
+    ) -> Result<Fingerprint, Error> {
+        self.fingerprint_with_provider(
+            &CryptoProvider::get_default_or_install_from_crate_features(),
+            algorithm,
+            len,
+        )
+    }
+
+    /// Computes the fingerprint of the key with a specific
+    /// [`CryptoProvider`]; see [`fingerprint`].
+    ///
+    /// [`fingerprint`]: #method.fingerprint
+    pub fn fingerprint_with_provider(
+        &self,
+        provider: &CryptoProvider,
+        algorithm: hash::Algorithm,
+        len: usize,
+    ) -> Result<Fingerprint, Error> {
+        let digest = provider
+            .hash
+            .get(algorithm)
+            .ok_or(Error::UnsupportedAlgorithm)?
+            .hash(self.as_raw_bytes());
+
+        if len == 0 || len > digest.as_ref().len() {
+            return Err(Error::Unspecified);
+        }
+
+        let mut buf = [0; hash::MAX_OUTPUT_LEN];
+        buf[..len].copy_from_slice(&digest.as_ref()[..len]);
+
+        Ok(Fingerprint { buf, len })
+    }
+}
+
+impl PartialEq for dyn PublicKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.algorithm() == other.algorithm() && self.as_raw_bytes() == other.as_raw_bytes()
+    }
+}
+
+impl Eq for dyn PublicKey {}
+
+impl Hash for dyn PublicKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.algorithm().hash(state);
+        self.as_raw_bytes().hash(state);
+    }
+}
+
+/// Truncated hash of a public key, for users to compare keys out of band.
+///
+/// Displayed as lowercase hex; [`to_base32`] gives a shorter form.
+///
+/// [`to_base32`]: Fingerprint::to_base32
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Fingerprint {
+    buf: [u8; hash::MAX_OUTPUT_LEN],
+    len: usize,
+}
+
+impl Fingerprint {
+    /// RFC 4648 base32 encoding of the fingerprint, without padding.
+    pub fn to_base32(&self) -> String {
+        const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+        let mut encoded = String::with_capacity(self.len.div_ceil(5) * 8);
+        let (mut bits, mut pending) = (0u16, 0);
+        for byte in self.as_ref() {
+            bits = bits << 8 | *byte as u16;
+            pending += 8;
+            while pending >= 5 {
+                pending -= 5;
+                encoded.push(ALPHABET[(bits >> pending) as usize & 31] as char);
+            }
+        }
+        if pending > 0 {
+            encoded.push(ALPHABET[(bits << (5 - pending)) as usize & 31] as char);
+        }
+
+        encoded
+    }
+}
+
+impl AsRef<[u8]> for Fingerprint {
+    fn as_ref(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.as_ref() {
+            write!(f, "{byte:02x}")?;
+        }
+
+        Ok(())
+    }
 }
 
 /// A private key for key agreement and signing key generation. The signature
 /// of [`agree`] allows [`PrivateKey`] to be used for more than one key
 /// agreement.
 ///
+/// X25519 keys also sign, with XEdDSA, so that one identity key both agrees
+/// and signs as in Signal's X3DH.
+///
 /// [`agree`]: PrivateKey::agree
 pub trait PrivateKey {
     /// DH key agreement.
-    fn agree(&self, peer_public_key: Box<dyn PublicKey>) -> Result<SharedSecret, Error>;
+    fn agree(&self, peer_public_key: &dyn PublicKey) -> Result<SharedSecret, Error>;
 
     /// Computes public key of the private key.
     fn compute_public_key(&self) -> Result<Box<dyn PublicKey>, Error>;
@@ -73,6 +203,11 @@ pub trait PrivateKey {
 
     /// Converts private key into der.
     fn as_der(&self) -> Result<Vec<u8>, Error>;
+
+    /// Signing key sharing the secret of this key: XEdDSA for X25519.
+    ///
+    /// Returns [`Error::UnsupportedAlgorithm`] for keys that only agree.
+    fn to_signing_key(&self) -> Result<Box<dyn SigningKey>, Error>;
 }
 
 /// An ephemeral private key for use (only) with ephemeral key agreement. The
@@ -84,7 +219,7 @@ pub trait EphemeralPrivateKey {
     /// DH key agreement with ephemeral key.
     fn agree_ephemeral(
         self: Box<Self>,
-        peer_public_key: Box<dyn PublicKey>,
+        peer_public_key: &dyn PublicKey,
     ) -> Result<SharedSecret, Error>;
 
     /// Computes public key of the ephemeral key.
@@ -93,20 +228,3 @@ pub trait EphemeralPrivateKey {
     /// The algorithm for the ephemeral private key.
     fn algorithm(&self) -> Algorithm;
 }
-
-/// Key derived using `HKDF`.
-pub struct SharedSecret {
-    pub(crate) buf: Vec<u8>,
-}
-
-impl Drop for SharedSecret {
-    fn drop(&mut self) {
-        self.buf.zeroize();
-    }
-}
-
-impl AsRef<[u8]> for SharedSecret {
-    fn as_ref(&self) -> &[u8] {
-        &self.buf
-    }
-}
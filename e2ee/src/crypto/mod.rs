@@ -1,13 +1,32 @@
 use crate::sync::Arc;
+use e2ee_provider::{
+    AeadProvider, HashProvider, HkdfProvider, KeyWrapProvider, ec::KeyProvider, generic,
+};
 
 /// HMAC-based key derivation interface.
-pub mod hkdf;
+#[doc(inline)]
+pub use e2ee_provider::hkdf;
 
 /// Key interface used in e2ee.
 pub mod key;
 
+/// Digital signature interface used in e2ee.
+pub mod signature;
+
 /// Hashing interface.
-pub mod hash;
+#[doc(inline)]
+pub use e2ee_provider::digest as hash;
+
+/// Authenticated encryption with associated data (AEAD) interface.
+#[doc(inline)]
+pub use e2ee_provider::aead;
+
+/// Key wrapping (key encryption) interface.
+#[doc(inline)]
+pub use e2ee_provider::key_wrap;
+
+/// Key and signature providers over the keys of an [`e2ee_provider`] backend.
+pub mod ec;
 
 /// aws-lc-rs based `CryptoProvider`.
 #[cfg(feature = "aws_lc_rs")]
@@ -16,14 +35,51 @@ pub mod aws_lc_rs;
 /// Cryptographic functions used by e2ee.
 pub struct CryptoProvider {
     /// HMAC-based key derivation.
-    pub hkdf: &'static dyn hkdf::Provider,
+    pub hkdf: &'static dyn HkdfProvider,
     /// Hash functions.
-    pub hash: &'static dyn hash::Provider,
+    pub hash: &'static dyn HashProvider,
+    /// Key wrapping functions.
+    pub key_wrap: &'static dyn KeyWrapProvider,
+    /// Authenticated encryption functions.
+    pub aead: &'static dyn AeadProvider,
     /// For loading keys from `der` format.
     pub key: &'static dyn key::Provider,
+    /// For loading and generating signing keys.
+    pub signature: &'static dyn signature::Provider,
 }
 
 impl CryptoProvider {
+    /// Combines the functions of an [`e2ee_provider::CryptoProvider`] with key
+    /// and signature providers.
+    pub fn new(
+        functions: e2ee_provider::CryptoProvider,
+        key: &'static dyn key::Provider,
+        signature: &'static dyn signature::Provider,
+    ) -> Self {
+        Self {
+            hkdf: functions.hkdf,
+            hash: functions.hash,
+            key_wrap: functions.key_wrap,
+            aead: functions.aead,
+            key,
+            signature,
+        }
+    }
+
+    /// Uses every function of the [`e2ee_provider`] backend `P`, its keys
+    /// through [`ec::EcProvider`].
+    pub fn from_backend<P: generic::CryptoProvider + KeyProvider + 'static>(
+        backend: &'static P,
+    ) -> Self {
+        let keys = const { &ec::EcProvider::<P>::new() };
+
+        Self::new(
+            e2ee_provider::CryptoProvider::from_generic(backend),
+            keys,
+            keys,
+        )
+    }
+
     /// Sets this insance of `CryptoProvider` as the default for this process.
     pub fn install_default(self) -> Result<(), Arc<Self>> {
         static_default::install_default(self)
@@ -36,11 +92,30 @@ impl CryptoProvider {
         static_default::get_default()
     }
 
-    pub(crate) fn get_default_or_install_from_crate_features() -> &'static Arc<Self> {
-        if let Some(provider) = static_default::get_default() {
+    /// Makes `self` the provider of [`x3dh::Config::builder`] and of the
+    /// operations that are not given one, such as
+    /// [`PublicKey::fingerprint`], in place of the process default, until the
+    /// returned guard is dropped.
+    ///
+    /// With the `std` feature the override applies to the current thread;
+    /// without it, to the whole program. Guards may be nested and dropped in
+    /// any order: the latest provider whose guard is alive is in effect.
+    ///
+    /// [`x3dh::Config::builder`]: crate::x3dh::Config::builder
+    /// [`PublicKey::fingerprint`]: key::PublicKey#method.fingerprint
+    pub fn install_scoped(self: Arc<Self>) -> ScopedProviderGuard {
+        scoped_default::install_scoped(self)
+    }
+
+    pub(crate) fn get_default_or_install_from_crate_features() -> Arc<Self> {
+        if let Some(provider) = scoped_default::get_scoped() {
             return provider;
         }
 
+        if let Some(provider) = static_default::get_default() {
+            return provider.clone();
+        }
+
         let provider = Self::from_crate_features().expect(
             r#"
 Could not determine process-level CryptoProvider from e2ee crate features.
@@ -50,7 +125,7 @@ Call CryptoProvider::install_default() before this point to select a provider ma
 
         provider.install_default().ok();
 
-        Self::get_default().unwrap()
+        Self::get_default().unwrap().clone()
     }
 
     fn from_crate_features() -> Option<Self> {
@@ -64,6 +139,73 @@ Call CryptoProvider::install_default() before this point to select a provider ma
     }
 }
 
+/// Uninstalls its scoped provider when dropped; see
+/// [`CryptoProvider::install_scoped`].
+#[must_use = "the scoped provider is uninstalled when the guard is dropped"]
+pub struct ScopedProviderGuard {
+    id: usize,
+    // With `std` the override is per thread, so must be its guard.
+    _not_send: core::marker::PhantomData<*const ()>,
+}
+
+impl Drop for ScopedProviderGuard {
+    fn drop(&mut self) {
+        scoped_default::uninstall(self.id);
+    }
+}
+
+mod scoped_default {
+    use super::{CryptoProvider, ScopedProviderGuard};
+    use crate::sync::Arc;
+    use alloc::vec::Vec;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Installed providers with the ids of their guards, the last in effect.
+    type Stack = Vec<(usize, Arc<CryptoProvider>)>;
+
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+    #[cfg(feature = "std")]
+    std::thread_local! {
+        static SCOPED_PROVIDERS: core::cell::RefCell<Stack> = const {
+            core::cell::RefCell::new(Vec::new())
+        };
+    }
+
+    #[cfg(feature = "std")]
+    fn with_stack<R>(f: impl FnOnce(&mut Stack) -> R) -> R {
+        SCOPED_PROVIDERS.with(|stack| f(&mut stack.borrow_mut()))
+    }
+
+    #[cfg(not(feature = "std"))]
+    static SCOPED_PROVIDERS: spin::Mutex<Stack> = spin::Mutex::new(Vec::new());
+
+    #[cfg(not(feature = "std"))]
+    fn with_stack<R>(f: impl FnOnce(&mut Stack) -> R) -> R {
+        f(&mut SCOPED_PROVIDERS.lock())
+    }
+
+    pub(crate) fn install_scoped(provider: Arc<CryptoProvider>) -> ScopedProviderGuard {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        with_stack(|stack| stack.push((id, provider)));
+
+        ScopedProviderGuard {
+            id,
+            _not_send: core::marker::PhantomData,
+        }
+    }
+
+    /// Removes the provider of guard `id`, wherever it is in the stack, so
+    /// that guards dropped out of order leave the others in effect.
+    pub(crate) fn uninstall(id: usize) {
+        with_stack(|stack| stack.retain(|(installed, _)| *installed != id));
+    }
+
+    pub(crate) fn get_scoped() -> Option<Arc<CryptoProvider>> {
+        with_stack(|stack| stack.last().map(|(_, provider)| provider.clone()))
+    }
+}
+
 mod static_default {
     #[cfg(not(feature = "std"))]
     use alloc::boxed::Box;
@@ -102,3 +244,82 @@ mod static_default {
     #[cfg(not(feature = "std"))]
     static PROCESS_DEFAULT_PROVIDER: OnceBox<Arc<CryptoProvider>> = OnceBox::new();
 }
+
+#[test]
+#[cfg(all(test, feature = "aws_lc_rs"))]
+fn test() -> Result<(), crate::Error> {
+    use crate::x3dh::{Config, Role};
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use e2ee_provider::provider::Provider;
+
+    /// Hash functions of aws-lc-rs that count their lookups.
+    struct CountingHash;
+
+    static LOOKUPS: AtomicUsize = AtomicUsize::new(0);
+
+    impl Provider<hash::Algorithm, &'static dyn hash::Hash> for CountingHash {
+        fn get(&self, algorithm: hash::Algorithm) -> Option<&'static dyn hash::Hash> {
+            LOOKUPS.fetch_add(1, Ordering::Relaxed);
+            aws_lc_rs::default_provider().hash.get(algorithm)
+        }
+
+        fn supported_algorithms(&self) -> &'static [hash::Algorithm] {
+            aws_lc_rs::default_provider().hash.supported_algorithms()
+        }
+
+        fn is_algorithm_supported(&self, algorithm: hash::Algorithm) -> bool {
+            aws_lc_rs::default_provider()
+                .hash
+                .is_algorithm_supported(algorithm)
+        }
+    }
+
+    impl HashProvider for CountingHash {}
+
+    let lookups = || LOOKUPS.load(Ordering::Relaxed);
+
+    let default = Config::builder().with_recommended_algorithms().build()?;
+    let counting = Arc::new(CryptoProvider {
+        hash: &CountingHash,
+        ..aws_lc_rs::default_provider()
+    });
+    let other = Arc::new(aws_lc_rs::default_provider());
+    let key = default
+        .provider()
+        .key
+        .load_public_key(key::Algorithm::X25519, alloc::vec![9; 32])?;
+
+    let outer = counting.clone().install_scoped();
+    let config = Config::builder().with_recommended_algorithms().build()?;
+    assert!(Arc::ptr_eq(config.provider(), &counting));
+
+    // Both the operations of the config and those without one hash with the
+    // scoped provider.
+    let before = lookups();
+    config.transcript(&config.capabilities().encode(), Role::Initiator)?;
+    key.fingerprint(hash::Algorithm::Sha256, 16)?;
+    assert_eq!(lookups(), before + 2);
+
+    let inner = other.clone().install_scoped();
+    let before = lookups();
+    key.fingerprint(hash::Algorithm::Sha256, 16)?;
+    assert_eq!(lookups(), before);
+
+    // Dropping the outer guard first leaves the inner provider in effect.
+    drop(outer);
+    let config_inner = Config::builder().with_recommended_algorithms().build()?;
+    assert!(Arc::ptr_eq(config_inner.provider(), &other));
+
+    drop(inner);
+    let config_default = Config::builder().with_recommended_algorithms().build()?;
+    assert!(Arc::ptr_eq(config_default.provider(), default.provider()));
+    let before = lookups();
+    key.fingerprint(hash::Algorithm::Sha256, 16)?;
+    assert_eq!(lookups(), before);
+
+    // Configs keep the provider they were built with.
+    config.transcript(&config.capabilities().encode(), Role::Initiator)?;
+    assert_eq!(lookups(), before + 1);
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use crate::Error;
+use alloc::{boxed::Box, vec::Vec};
+use core::any::Any;
+
+#[doc(inline)]
+pub use e2ee_provider::ec::signature::Algorithm;
+
+/// Mechanism for loading/generating signing keys.
+pub trait Provider: Send + Sync {
+    /// Loads signing key from PKCS #8 DER.
+    fn load_signing_key(
+        &self,
+        algorithm: Algorithm,
+        key_der: &[u8],
+    ) -> Result<Box<dyn SigningKey>, Error>;
+
+    /// Generates a signing key.
+    fn generate_signing_key(&self, algorithm: Algorithm) -> Result<Box<dyn SigningKey>, Error>;
+
+    /// Loads verifying key from X.509 DER.
+    fn load_verifying_key(
+        &self,
+        algorithm: Algorithm,
+        key_der: &[u8],
+    ) -> Result<Box<dyn VerifyingKey>, Error>;
+
+    /// Whether or not the signature algorithm is supported.
+    fn is_algorithm_supported(&self, algorithm: Algorithm) -> bool;
+}
+
+/// A private key for digital signatures.
+///
+/// Every signature is bound to a `context` string naming the kind of signed
+/// object, such as [`PREKEY_SIGNATURE_CONTEXT`], so that no signature can be
+/// replayed as another kind. Ed25519ctx and Ed25519ph sign it as their
+/// RFC 8032 context; algorithms without domain separation sign the
+/// length-prefixed context ahead of the message.
+///
+/// [`PREKEY_SIGNATURE_CONTEXT`]: crate::x3dh::PREKEY_SIGNATURE_CONTEXT
+pub trait SigningKey {
+    /// Signs `message` under `context`.
+    ///
+    /// Returns [`Error::Unspecified`] if `context` is longer than 255 bytes,
+    /// or empty with Ed25519ctx.
+    fn sign(&self, context: &[u8], message: &[u8]) -> Result<Vec<u8>, Error>;
+
+    /// Computes verifying key of the signing key.
+    fn compute_verifying_key(&self) -> Result<Box<dyn VerifyingKey>, Error>;
+
+    /// The algorithm for the signing key.
+    fn algorithm(&self) -> Algorithm;
+
+    /// Converts signing key into PKCS #8 DER.
+    fn as_der(&self) -> Result<Vec<u8>, Error>;
+}
+
+/// A public key for verifying digital signatures.
+pub trait VerifyingKey: Any {
+    /// Verifies the signature of `message` made under `context`.
+    #[must_use = "a signature that fails to verify must be rejected"]
+    fn verify(&self, context: &[u8], message: &[u8], signature: &[u8]) -> Result<(), Error>;
+
+    /// The algorithm for the verifying key.
+    fn algorithm(&self) -> Algorithm;
+
+    /// Converts verifying key into X.509 DER.
+    fn as_der(&self) -> Result<Vec<u8>, Error>;
+}
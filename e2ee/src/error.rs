@@ -1,5 +1,17 @@
+use core::fmt;
+
 /// Error reporting.
-#[derive(Debug)]
+///
+/// Failures of cryptographic operations are opaque: [`Unspecified`],
+/// [`UnsupportedAlgorithm`] and [`KeyRejected`] carry no detail an attacker
+/// could learn from. What the peer did wrong is a [`ProtocolError`], which is
+/// safe to report.
+///
+/// [`Unspecified`]: Error::Unspecified
+/// [`UnsupportedAlgorithm`]: Error::UnsupportedAlgorithm
+/// [`KeyRejected`]: Error::KeyRejected
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Error {
     /// An error with absolutely no details.
     ///
@@ -11,18 +23,282 @@ pub enum Error {
     UnsupportedAlgorithm,
     /// An error parsing or validating a key.
     KeyRejected,
+    /// The peer violated the protocol.
+    Protocol(ProtocolError),
+    /// A configuration was rejected while building it.
+    Config(ConfigError),
+    /// The key storage failed to store or load a value.
+    ///
+    /// Reserved for the X3DH handshake, which does not use [`KeyStorage`]
+    /// yet.
+    ///
+    /// [`KeyStorage`]: crate::x3dh::KeyStorage
+    Storage,
+}
+
+/// Protocol violations, public knowledge to both peers.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtocolError {
+    /// A signature does not match the signed key or message.
+    BadSignature,
+    /// A message refers to a prekey that is not known, or was already used.
+    ///
+    /// Reserved for the X3DH handshake, not returned yet.
+    UnknownPrekey,
+    /// A message was received before.
+    ///
+    /// Reserved for the Double Ratchet, not returned yet.
+    Replay,
+    /// A message is so far ahead in its chain that skipping to it would store
+    /// more message keys than allowed.
+    ///
+    /// Reserved for the Double Ratchet, not returned yet.
+    TooManySkippedMessages,
+    /// The configurations of the peers have no algorithm in common.
+    IncompatibleConfig,
+    /// A message could not be decoded.
+    MalformedMessage,
+}
+
+/// Reasons a configuration is rejected.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigError {
+    /// A list of algorithms was not specified.
+    MissingAlgorithms,
+    /// A list of algorithms is empty.
+    EmptyAlgorithms,
+    /// A list of algorithms contains the same algorithm more than once.
+    DuplicateAlgorithm,
+    /// No hash or key derivation function is as strong as the strongest
+    /// curve.
+    WeakHash,
+}
+
+impl From<ConfigError> for Error {
+    fn from(error: ConfigError) -> Self {
+        Self::Config(error)
+    }
+}
+
+impl From<ProtocolError> for Error {
+    fn from(error: ProtocolError) -> Self {
+        Self::Protocol(error)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unspecified => f.write_str("cryptographic operation failed"),
+            Self::UnsupportedAlgorithm => f.write_str("algorithm is not supported by the provider"),
+            Self::KeyRejected => f.write_str("key rejected"),
+            Self::Protocol(error) => write!(f, "protocol error: {error}"),
+            Self::Config(error) => write!(f, "invalid configuration: {error}"),
+            Self::Storage => f.write_str("key storage failed"),
+        }
+    }
 }
 
-#[cfg(feature = "aws_lc_rs")]
-impl From<aws_lc_rs::error::Unspecified> for Error {
-    fn from(_: aws_lc_rs::error::Unspecified) -> Self {
-        Self::Unspecified
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::BadSignature => "bad signature",
+            Self::UnknownPrekey => "unknown prekey",
+            Self::Replay => "replayed message",
+            Self::TooManySkippedMessages => "too many skipped messages",
+            Self::IncompatibleConfig => "no algorithm in common with the peer",
+            Self::MalformedMessage => "malformed message",
+        })
     }
 }
 
-#[cfg(feature = "aws_lc_rs")]
-impl From<aws_lc_rs::error::KeyRejected> for Error {
-    fn from(_: aws_lc_rs::error::KeyRejected) -> Self {
-        Self::KeyRejected
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::MissingAlgorithms => "list of algorithms not specified",
+            Self::EmptyAlgorithms => "empty list of algorithms",
+            Self::DuplicateAlgorithm => "duplicate algorithm",
+            Self::WeakHash => "hash functions are weaker than the curves",
+        })
     }
 }
+
+impl core::error::Error for Error {}
+
+impl core::error::Error for ConfigError {}
+
+impl core::error::Error for ProtocolError {}
+
+impl From<e2ee_provider::Error> for Error {
+    fn from(error: e2ee_provider::Error) -> Self {
+        use e2ee_provider::Error as ProviderError;
+
+        match error {
+            ProviderError::UnsupportedEncoding
+            | ProviderError::UnsupportedAgreementAlgorithm
+            | ProviderError::UnsupportedSignatureAlgorithm
+            | ProviderError::UnsupportedKemAlgorithm
+            | ProviderError::UnsupportedDigestFunction
+            | ProviderError::UnsupportedHkdf
+            | ProviderError::UnsupportedKeyWrapAlgorithm
+            | ProviderError::UnsupportedAeadAlgorithm
+            | ProviderError::UnsupportedCurve => Self::UnsupportedAlgorithm,
+            ProviderError::KeyRejected => Self::KeyRejected,
+            ProviderError::BadSignature | ProviderError::NonCanonicalSignature => {
+                Self::Protocol(ProtocolError::BadSignature)
+            }
+            ProviderError::Unspecified => Self::Unspecified,
+        }
+    }
+}
+
+impl From<Error> for e2ee_provider::Error {
+    fn from(error: Error) -> Self {
+        match error {
+            // The only algorithms e2ee itself resolves are those of its key
+            // provider.
+            Error::UnsupportedAlgorithm => Self::UnsupportedAgreementAlgorithm,
+            Error::KeyRejected => Self::KeyRejected,
+            Error::Protocol(ProtocolError::BadSignature) => Self::BadSignature,
+            Error::Unspecified | Error::Protocol(_) | Error::Config(_) | Error::Storage => {
+                Self::Unspecified
+            }
+        }
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn test() -> Result<(), Error> {
+    use alloc::string::ToString;
+    use e2ee_provider::Error as ProviderError;
+
+    for (error, expected) in [
+        (
+            ProviderError::UnsupportedEncoding,
+            Error::UnsupportedAlgorithm,
+        ),
+        (
+            ProviderError::UnsupportedAgreementAlgorithm,
+            Error::UnsupportedAlgorithm,
+        ),
+        (
+            ProviderError::UnsupportedSignatureAlgorithm,
+            Error::UnsupportedAlgorithm,
+        ),
+        (
+            ProviderError::UnsupportedKemAlgorithm,
+            Error::UnsupportedAlgorithm,
+        ),
+        (
+            ProviderError::UnsupportedDigestFunction,
+            Error::UnsupportedAlgorithm,
+        ),
+        (ProviderError::UnsupportedHkdf, Error::UnsupportedAlgorithm),
+        (
+            ProviderError::UnsupportedKeyWrapAlgorithm,
+            Error::UnsupportedAlgorithm,
+        ),
+        (
+            ProviderError::UnsupportedAeadAlgorithm,
+            Error::UnsupportedAlgorithm,
+        ),
+        (ProviderError::UnsupportedCurve, Error::UnsupportedAlgorithm),
+        (ProviderError::KeyRejected, Error::KeyRejected),
+        (
+            ProviderError::BadSignature,
+            Error::Protocol(ProtocolError::BadSignature),
+        ),
+        (
+            ProviderError::NonCanonicalSignature,
+            Error::Protocol(ProtocolError::BadSignature),
+        ),
+        (ProviderError::Unspecified, Error::Unspecified),
+    ] {
+        assert_eq!(Error::from(error), expected);
+    }
+
+    for (error, expected) in [
+        (
+            Error::UnsupportedAlgorithm,
+            ProviderError::UnsupportedAgreementAlgorithm,
+        ),
+        (Error::KeyRejected, ProviderError::KeyRejected),
+        (
+            Error::Protocol(ProtocolError::BadSignature),
+            ProviderError::BadSignature,
+        ),
+        (
+            Error::Protocol(ProtocolError::Replay),
+            ProviderError::Unspecified,
+        ),
+        (
+            Error::Config(ConfigError::WeakHash),
+            ProviderError::Unspecified,
+        ),
+        (Error::Storage, ProviderError::Unspecified),
+        (Error::Unspecified, ProviderError::Unspecified),
+    ] {
+        // The provider error is not comparable.
+        assert_eq!(
+            alloc::format!("{:?}", ProviderError::from(error)),
+            alloc::format!("{expected:?}")
+        );
+    }
+
+    for (error, expected) in [
+        (Error::Unspecified, "cryptographic operation failed"),
+        (
+            Error::UnsupportedAlgorithm,
+            "algorithm is not supported by the provider",
+        ),
+        (Error::KeyRejected, "key rejected"),
+        (Error::Storage, "key storage failed"),
+        (
+            ProtocolError::BadSignature.into(),
+            "protocol error: bad signature",
+        ),
+        (
+            ProtocolError::UnknownPrekey.into(),
+            "protocol error: unknown prekey",
+        ),
+        (
+            ProtocolError::Replay.into(),
+            "protocol error: replayed message",
+        ),
+        (
+            ProtocolError::TooManySkippedMessages.into(),
+            "protocol error: too many skipped messages",
+        ),
+        (
+            ProtocolError::IncompatibleConfig.into(),
+            "protocol error: no algorithm in common with the peer",
+        ),
+        (
+            ProtocolError::MalformedMessage.into(),
+            "protocol error: malformed message",
+        ),
+        (
+            ConfigError::MissingAlgorithms.into(),
+            "invalid configuration: list of algorithms not specified",
+        ),
+        (
+            ConfigError::EmptyAlgorithms.into(),
+            "invalid configuration: empty list of algorithms",
+        ),
+        (
+            ConfigError::DuplicateAlgorithm.into(),
+            "invalid configuration: duplicate algorithm",
+        ),
+        (
+            ConfigError::WeakHash.into(),
+            "invalid configuration: hash functions are weaker than the curves",
+        ),
+    ] {
+        assert_eq!(error.to_string(), expected);
+    }
+
+    Ok(())
+}
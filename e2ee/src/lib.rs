@@ -21,7 +21,7 @@ pub mod x3dh;
 mod error;
 
 #[doc(inline)]
-pub use error::Error;
+pub use error::{ConfigError, Error, ProtocolError};
 
 /// Double Ratchet, as described by
 /// [Signal](https://signal.org/docs/specifications/doubleratchet/doubleratchet.pdf).
@@ -30,6 +30,10 @@ pub mod doubleratchet;
 /// Crypto provider interface.
 pub mod crypto;
 
+/// Cryptographic functions the provider interface is built on.
+#[doc(no_inline)]
+pub use e2ee_provider;
+
 /// Internal `sync` module aliases the `Arc` implementation to allow
 /// replacement of it in one centrral location.
 mod sync {
@@ -1,89 +1,263 @@
 use crate::crypto::{CryptoProvider, hash, hkdf, key};
 use crate::sync::Arc;
+use crate::{ConfigError, Error};
 use alloc::vec::Vec;
 
-use super::Config;
+use super::{CipherSuite, Config, Preference};
 
 /// A builder for [`x3dh::Config`].
 ///
+/// Every step validates its input against the [`CryptoProvider`], so that
+/// algorithm lists read from configuration files never panic.
+///
 /// [`x3dh::Config`]: crate::x3dh::Config
 pub struct ConfigBuilder {
     pub(crate) curve: Option<Vec<key::Algorithm>>,
     pub(crate) hkdf: Option<Vec<hkdf::Algorithm>>,
     pub(crate) hash: Option<Vec<hash::Algorithm>>,
+    pub(crate) suites: Vec<CipherSuite>,
+    pub(crate) preference: Preference,
 
     pub(crate) provider: Arc<CryptoProvider>,
 }
 
 impl ConfigBuilder {
     /// Specifies which curve algorithms are supported by the peer.
-    pub fn with_curve(mut self, curve: Vec<key::Algorithm>) -> Self {
-        for c in curve.iter() {
-            if !self.provider.key.is_curve_supported(*c) {
-                panic!("{c:?} is not supported by the provider.");
-            }
-        }
+    ///
+    /// Returns [`Error::UnsupportedAlgorithm`] if the provider lacks any of
+    /// them, or a [`ConfigError`] if the list is empty or has duplicates.
+    pub fn with_curve(mut self, curve: Vec<key::Algorithm>) -> Result<Self, Error> {
+        validate(&curve, |c| self.provider.key.is_curve_supported(c))?;
 
         self.curve = Some(curve);
-        self
+        Ok(self)
     }
 
     /// Specifies which key derivation algorithms are supported by the peer.
-    pub fn with_hkdf(mut self, hkdf: Vec<hkdf::Algorithm>) -> Self {
-        for c in hkdf.iter() {
-            if !self.provider.hkdf.is_algorithm_supported(*c) {
-                panic!("{c:?} is not supported by the provider.");
-            }
-        }
+    ///
+    /// Returns [`Error::UnsupportedAlgorithm`] if the provider lacks any of
+    /// them, or a [`ConfigError`] if the list is empty or has duplicates.
+    pub fn with_hkdf(mut self, hkdf: Vec<hkdf::Algorithm>) -> Result<Self, Error> {
+        validate(&hkdf, |c| self.provider.hkdf.is_algorithm_supported(c))?;
 
         self.hkdf = Some(hkdf);
-        self
+        Ok(self)
     }
 
-    /// Specifies which key derivation algorithms are supported by the peer.
-    pub fn with_hash(mut self, hash: Vec<hash::Algorithm>) -> Self {
-        for c in hash.iter() {
-            if !self.provider.hash.is_function_supported(*c) {
-                panic!("{c:?} is not supported by the provider.");
-            }
-        }
+    /// Specifies which hash algorithms are supported by the peer.
+    ///
+    /// Returns [`Error::UnsupportedAlgorithm`] if the provider lacks any of
+    /// them, or a [`ConfigError`] if the list is empty or has duplicates.
+    pub fn with_hash(mut self, hash: Vec<hash::Algorithm>) -> Result<Self, Error> {
+        validate(&hash, |c| self.provider.hash.is_algorithm_supported(c))?;
 
         self.hash = Some(hash);
+        Ok(self)
+    }
+
+    /// Specifies which cipher suites are supported by the peer, and sets the
+    /// curves, key derivation and hash functions to those of the suites, in
+    /// the order they first appear.
+    ///
+    /// Returns [`Error::UnsupportedAlgorithm`] if the provider lacks any
+    /// algorithm of the suites, or a [`ConfigError`] if the list is empty or
+    /// has duplicates.
+    pub fn with_suites(mut self, suites: Vec<CipherSuite>) -> Result<Self, Error> {
+        validate(&suites, |s| {
+            self.provider.aead.is_algorithm_supported(s.aead())
+        })?;
+
+        let curve = components(&suites, CipherSuite::curve);
+        let hkdf = components(&suites, CipherSuite::hkdf);
+        let hash = components(&suites, CipherSuite::hash);
+        self.suites = suites;
+
+        self.with_curve(curve)?.with_hkdf(hkdf)?.with_hash(hash)
+    }
+
+    /// Specifies whose order of preference wins in negotiation, the
+    /// responder's by default.
+    pub fn with_preference(mut self, preference: Preference) -> Self {
+        self.preference = preference;
         self
     }
 
     /// Sets algorithms with safe defaults.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the provider does not support all of the recommended
+    /// algorithms. Use the fallible steps for algorithms that are not
+    /// constants.
     pub fn with_recommended_algorithms(self) -> Self {
         self.with_hkdf(alloc::vec![
             hkdf::Algorithm::Sha256,
             hkdf::Algorithm::Sha512,
             hkdf::Algorithm::Sha384
         ])
-        .with_curve(alloc::vec![
-            key::Algorithm::EcP521,
-            key::Algorithm::EcP384,
-            key::Algorithm::EcP384
-        ])
-        .with_hash(alloc::vec![
-            hash::Algorithm::Sha256,
-            hash::Algorithm::Sha512,
-            hash::Algorithm::Sha224,
-            hash::Algorithm::Sha384,
-            hash::Algorithm::Sha3_256,
-            hash::Algorithm::Sha3_512,
-            hash::Algorithm::Sha3_384,
-        ])
+        .and_then(|builder| {
+            builder.with_curve(alloc::vec![
+                key::Algorithm::EcdhP521,
+                key::Algorithm::EcdhP384,
+                key::Algorithm::EcdhP256
+            ])
+        })
+        .and_then(|builder| {
+            builder.with_hash(alloc::vec![
+                hash::Algorithm::Sha256,
+                hash::Algorithm::Sha512,
+                hash::Algorithm::Sha224,
+                hash::Algorithm::Sha384,
+                hash::Algorithm::Sha3_256,
+                hash::Algorithm::Sha3_512,
+                hash::Algorithm::Sha3_384,
+            ])
+        })
+        .expect("Recommended algorithms are not supported by the provider.")
     }
 
     /// Finish builder to peer config.
-    pub fn build(self) -> Arc<Config> {
-        Arc::new(Config {
-            curve: self.curve.expect("No curve algorithm has been specified."),
-            hkdf: self
-                .hkdf
-                .expect("No key derivation function has been specified."),
-            hash: self.hash.expect("No key hash funciton has been specified."),
-            _provider: self.provider,
-        })
+    ///
+    /// Returns [`ConfigError::MissingAlgorithms`] if a list has not been
+    /// specified, and [`ConfigError::WeakHash`] if no hash function or no key
+    /// derivation function reaches the security strength of the strongest
+    /// curve.
+    pub fn build(self) -> Result<Arc<Config>, Error> {
+        let (Some(curve), Some(hkdf), Some(hash)) = (self.curve, self.hkdf, self.hash) else {
+            return Err(ConfigError::MissingAlgorithms.into());
+        };
+
+        let strength = curve.iter().map(curve_strength).max();
+        if hash.iter().map(hash_strength).max() < strength
+            || hkdf.iter().map(hkdf_strength).max() < strength
+        {
+            return Err(ConfigError::WeakHash.into());
+        }
+
+        Ok(Arc::new(Config {
+            curve,
+            hkdf,
+            hash,
+            suites: self.suites,
+            preference: self.preference,
+            provider: self.provider,
+        }))
+    }
+}
+
+/// Rejects empty lists, duplicates and algorithms the provider lacks.
+fn validate<T: Copy + PartialEq>(
+    algorithms: &[T],
+    is_supported: impl Fn(T) -> bool,
+) -> Result<(), Error> {
+    if algorithms.is_empty() {
+        return Err(ConfigError::EmptyAlgorithms.into());
+    }
+
+    for (i, algorithm) in algorithms.iter().enumerate() {
+        if algorithms[..i].contains(algorithm) {
+            return Err(ConfigError::DuplicateAlgorithm.into());
+        }
+
+        if !is_supported(*algorithm) {
+            return Err(Error::UnsupportedAlgorithm);
+        }
+    }
+
+    Ok(())
+}
+
+/// Algorithms of `suites` without duplicates, in the order they first appear.
+fn components<T: PartialEq>(suites: &[CipherSuite], component: fn(&CipherSuite) -> T) -> Vec<T> {
+    let mut algorithms = Vec::new();
+    for algorithm in suites.iter().map(component) {
+        if !algorithms.contains(&algorithm) {
+            algorithms.push(algorithm);
+        }
+    }
+
+    algorithms
+}
+
+/// Security strength of the curve in bits, as of NIST SP 800-57.
+pub(super) fn curve_strength(algorithm: &key::Algorithm) -> usize {
+    match algorithm {
+        key::Algorithm::EcdhP256 | key::Algorithm::X25519 => 128,
+        key::Algorithm::EcdhP384 => 192,
+        key::Algorithm::X448 => 224,
+        key::Algorithm::EcdhP521 => 256,
+        // Unknown curves are assumed to be the strongest.
+        _ => 256,
     }
 }
+
+/// Collision resistance of the hash function in bits.
+pub(super) fn hash_strength(algorithm: &hash::Algorithm) -> usize {
+    algorithm.output_len() * 4
+}
+
+/// Security strength of the key derivation function in bits.
+pub(super) fn hkdf_strength(algorithm: &hkdf::Algorithm) -> usize {
+    match algorithm {
+        hkdf::Algorithm::Sha256 => 128,
+        hkdf::Algorithm::Sha384 => 192,
+        hkdf::Algorithm::Sha512 => 256,
+        _ => 0,
+    }
+}
+
+#[test]
+#[cfg(all(test, feature = "aws_lc_rs"))]
+fn test() -> Result<(), Error> {
+    use crate::crypto::aws_lc_rs;
+
+    let builder = || Config::builder_with_provider(Arc::new(aws_lc_rs::default_provider()));
+
+    let config = builder().with_recommended_algorithms().build()?;
+    assert_eq!(config.curve.len(), 3);
+
+    assert_eq!(
+        builder().with_curve(alloc::vec![]).err(),
+        Some(Error::Config(ConfigError::EmptyAlgorithms))
+    );
+    assert_eq!(
+        builder()
+            .with_hash(alloc::vec![
+                hash::Algorithm::Sha256,
+                hash::Algorithm::Sha256
+            ])
+            .err(),
+        Some(Error::Config(ConfigError::DuplicateAlgorithm))
+    );
+    assert_eq!(
+        builder()
+            .with_curve(alloc::vec![key::Algorithm::X448])
+            .err(),
+        Some(Error::UnsupportedAlgorithm)
+    );
+    assert_eq!(
+        builder()
+            .with_curve(alloc::vec![key::Algorithm::X25519])?
+            .build()
+            .err(),
+        Some(Error::Config(ConfigError::MissingAlgorithms))
+    );
+
+    // SHA-256 is too weak for P-521.
+    assert_eq!(
+        builder()
+            .with_curve(alloc::vec![key::Algorithm::EcdhP521])?
+            .with_hkdf(alloc::vec![hkdf::Algorithm::Sha512])?
+            .with_hash(alloc::vec![hash::Algorithm::Sha256])?
+            .build()
+            .err(),
+        Some(Error::Config(ConfigError::WeakHash))
+    );
+    builder()
+        .with_curve(alloc::vec![key::Algorithm::X25519])?
+        .with_hkdf(alloc::vec![hkdf::Algorithm::Sha256])?
+        .with_hash(alloc::vec![hash::Algorithm::Sha256])?
+        .build()?;
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use super::ConfigBuilder;
+use super::{CipherSuite, ConfigBuilder, Preference};
 use crate::{
     crypto::{CryptoProvider, hash, hkdf, key},
     sync::Arc,
@@ -13,17 +13,21 @@ pub struct Config {
     pub hkdf: Vec<hkdf::Algorithm>,
     /// List of supported hash functions, ordered by preference.
     pub hash: Vec<hash::Algorithm>,
+    /// List of supported cipher suites, ordered by preference. Empty unless
+    /// the configuration was built from suites.
+    pub suites: Vec<CipherSuite>,
+    /// Whose order of preference wins in negotiation.
+    pub preference: Preference,
 
-    pub(crate) _provider: Arc<CryptoProvider>,
+    pub(crate) provider: Arc<CryptoProvider>,
 }
 
 impl Config {
     /// Create a builder for a client configuration with the process-default
-    /// [`CryptoProvider`].
+    /// [`CryptoProvider`], or the one installed on this thread with
+    /// [`CryptoProvider::install_scoped`].
     pub fn builder() -> ConfigBuilder {
-        Self::builder_with_provider(
-            CryptoProvider::get_default_or_install_from_crate_features().clone(),
-        )
+        Self::builder_with_provider(CryptoProvider::get_default_or_install_from_crate_features())
     }
 
     /// Create a builder for a client configuration with a specific
@@ -33,7 +37,14 @@ impl Config {
             curve: None,
             hash: None,
             hkdf: None,
+            suites: Vec::new(),
+            preference: Preference::default(),
             provider,
         }
     }
+
+    /// The [`CryptoProvider`] every operation of this configuration uses.
+    pub fn provider(&self) -> &Arc<CryptoProvider> {
+        &self.provider
+    }
 }
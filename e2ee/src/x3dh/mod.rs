@@ -2,20 +2,116 @@ use alloc::{boxed::Box, vec::Vec};
 
 mod builder;
 mod config;
+mod negotiation;
+mod suite;
+mod transcript;
 
 pub use builder::ConfigBuilder;
 pub use config::Config;
+pub use negotiation::{Capabilities, Preference, Role, Selection};
+pub use suite::CipherSuite;
+pub use transcript::Transcript;
 
-use crate::crypto::key::PrivateKey;
+use crate::{
+    Error,
+    crypto::key::{PrivateKey, PublicKey},
+};
+
+/// Context string of prekey signatures.
+///
+/// Each kind of signed object has its own fixed label, so that no signature
+/// can be replayed as another kind. Ed25519ctx and Ed25519ph signatures carry
+/// it as their RFC 8032 context; other algorithms sign it length-prefixed
+/// ahead of the object.
+pub const PREKEY_SIGNATURE_CONTEXT: &[u8] = b"e2ee x3dh prekey signature";
+
+/// Context string of device certificates, binding a device key to the
+/// identity key.
+pub const DEVICE_CERTIFICATE_CONTEXT: &[u8] = b"e2ee x3dh device certificate";
+
+/// Context string of handshake transcript signatures.
+pub const TRANSCRIPT_CONTEXT: &[u8] = b"e2ee x3dh transcript";
+
+/// Hash function of handshake transcripts.
+///
+/// Fixed rather than negotiated, so that stripping strong hash functions
+/// from an offer cannot weaken the hash that detects it.
+pub const TRANSCRIPT_HASH: crate::crypto::hash::Algorithm = crate::crypto::hash::Algorithm::Sha512;
 
 /// X3DH peer.
 pub struct Peer {
-    /// Peer's identity key, used for prekey signing and mutual authentication.
+    /// Peer's identity key, used for mutual authentication. It also signs
+    /// prekeys, device certificates and transcripts, with XEdDSA on X25519.
     pub identitiy_key: Box<dyn PrivateKey>,
     /// Storage for peer's prekeys.
     pub key_storage: Option<Box<dyn KeyStorage>>,
 }
 
+impl Peer {
+    /// Signs `prekey` under [`PREKEY_SIGNATURE_CONTEXT`].
+    pub fn sign_prekey(&self, prekey: &dyn PublicKey) -> Result<Vec<u8>, Error> {
+        self.identitiy_key
+            .to_signing_key()?
+            .sign(PREKEY_SIGNATURE_CONTEXT, prekey.as_raw_bytes())
+    }
+
+    /// Signs the negotiation `transcript` under [`TRANSCRIPT_CONTEXT`].
+    pub fn sign_transcript(&self, transcript: &Transcript) -> Result<Vec<u8>, Error> {
+        self.identitiy_key
+            .to_signing_key()?
+            .sign(TRANSCRIPT_CONTEXT, transcript.as_ref())
+    }
+
+    /// Certifies `device_key` as a key of this peer, under
+    /// [`DEVICE_CERTIFICATE_CONTEXT`].
+    pub fn certify_device(&self, device_key: &dyn PublicKey) -> Result<Vec<u8>, Error> {
+        self.identitiy_key
+            .to_signing_key()?
+            .sign(DEVICE_CERTIFICATE_CONTEXT, device_key.as_raw_bytes())
+    }
+}
+
+/// Verifies a signature of [`Peer::sign_prekey`] with the peer's identity
+/// key.
+#[must_use = "a prekey whose signature fails to verify must be rejected"]
+pub fn verify_prekey(
+    identity: &dyn PublicKey,
+    prekey: &dyn PublicKey,
+    signature: &[u8],
+) -> Result<(), Error> {
+    identity
+        .to_verifying_key()?
+        .verify(PREKEY_SIGNATURE_CONTEXT, prekey.as_raw_bytes(), signature)
+}
+
+/// Verifies a certificate of [`Peer::certify_device`] with the peer's
+/// identity key.
+#[must_use = "a device whose certificate fails to verify must be rejected"]
+pub fn verify_device_certificate(
+    identity: &dyn PublicKey,
+    device_key: &dyn PublicKey,
+    certificate: &[u8],
+) -> Result<(), Error> {
+    identity.to_verifying_key()?.verify(
+        DEVICE_CERTIFICATE_CONTEXT,
+        device_key.as_raw_bytes(),
+        certificate,
+    )
+}
+
+/// Verifies a signature of [`Peer::sign_transcript`] against the transcript
+/// computed locally, rejecting a negotiation tampered with.
+#[must_use = "a handshake whose transcript signature fails to verify must be aborted"]
+pub fn verify_transcript(
+    identity: &dyn PublicKey,
+    transcript: &Transcript,
+    signature: &[u8],
+) -> Result<(), Error> {
+    identity
+        .to_verifying_key()?
+        .verify(TRANSCRIPT_CONTEXT, transcript.as_ref(), signature)
+}
+
 /// Key storage for peer's prekeys and one-time prekeys.
 pub trait KeyStorage {
     /// Store prekeys encoded in value against key, overwrites any existing
@@ -0,0 +1,378 @@
+use super::{
+    CipherSuite, Config,
+    builder::{curve_strength, hash_strength, hkdf_strength},
+};
+use crate::{
+    Error, ProtocolError,
+    crypto::{hash, hkdf, key},
+};
+use alloc::vec::Vec;
+
+/// Role of a peer in the handshake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// The peer that sends the initial message.
+    Initiator,
+    /// The peer whose prekeys are used.
+    Responder,
+}
+
+/// Whose order of preference wins when more than one algorithm is supported
+/// by both peers.
+///
+/// Both peers must use the same rule to select the same algorithms, so the
+/// rule is advertised in [`Capabilities`] and negotiation fails if the peers'
+/// rules differ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Preference {
+    /// The initiator's order wins.
+    Initiator = 0,
+    /// The responder's order wins.
+    #[default]
+    Responder = 1,
+}
+
+/// Algorithms a peer advertises, ordered by preference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Whose order of preference wins.
+    pub preference: Preference,
+    /// Supported curves.
+    pub curve: Vec<key::Algorithm>,
+    /// Supported key derivation functions.
+    pub hkdf: Vec<hkdf::Algorithm>,
+    /// Supported hash functions.
+    pub hash: Vec<hash::Algorithm>,
+    /// Supported cipher suites, empty unless the configuration was built from
+    /// suites.
+    pub suites: Vec<CipherSuite>,
+}
+
+/// Algorithms selected for a handshake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Selection {
+    /// Selected curve.
+    pub curve: key::Algorithm,
+    /// Selected key derivation function.
+    pub hkdf: hkdf::Algorithm,
+    /// Selected hash function.
+    pub hash: hash::Algorithm,
+    /// Selected cipher suite, whose algorithms the others are, if suites were
+    /// negotiated.
+    pub suite: Option<CipherSuite>,
+}
+
+impl Config {
+    /// Algorithms of the configuration, to be advertised to peers.
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            preference: self.preference,
+            curve: self.curve.clone(),
+            hkdf: self.hkdf.clone(),
+            hash: self.hash.clone(),
+            suites: self.suites.clone(),
+        }
+    }
+
+    /// Selects the algorithms for a handshake with a peer advertising `peer`,
+    /// `role` being the role of this peer.
+    ///
+    /// If either peer offers cipher suites, the algorithms are those of the
+    /// suite selected by [`negotiate_suite`]. Otherwise the first curve in the
+    /// preferred peer's list that the other peer supports is selected, with
+    /// the first key derivation and hash functions in that list at least as
+    /// strong as the curve; see [`Preference`]. Curves without such functions
+    /// in common are skipped.
+    ///
+    /// Returns [`ProtocolError::IncompatibleConfig`] if the peers' preference
+    /// rules differ, only one of them offers suites, or nothing can be
+    /// selected.
+    ///
+    /// [`negotiate_suite`]: Config::negotiate_suite
+    pub fn negotiate(&self, peer: &Capabilities, role: Role) -> Result<Selection, Error> {
+        if peer.preference != self.preference {
+            return Err(ProtocolError::IncompatibleConfig.into());
+        }
+
+        if !self.suites.is_empty() || !peer.suites.is_empty() {
+            let suite = self.negotiate_suite(&peer.suites, role)?;
+
+            return Ok(Selection {
+                curve: suite.curve(),
+                hkdf: suite.hkdf(),
+                hash: suite.hash(),
+                suite: Some(suite),
+            });
+        }
+
+        let local_first = self.is_preferred(role);
+        let (preferred, other) = match local_first {
+            true => (&self.curve, &peer.curve),
+            false => (&peer.curve, &self.curve),
+        };
+
+        preferred
+            .iter()
+            .filter(|curve| other.contains(curve))
+            .find_map(|curve| {
+                let strength = curve_strength(curve);
+
+                Some(Selection {
+                    curve: *curve,
+                    hkdf: select_strong(&self.hkdf, &peer.hkdf, local_first, |a| {
+                        hkdf_strength(a) >= strength
+                    })?,
+                    hash: select_strong(&self.hash, &peer.hash, local_first, |a| {
+                        hash_strength(a) >= strength
+                    })?,
+                    suite: None,
+                })
+            })
+            .ok_or(ProtocolError::IncompatibleConfig.into())
+    }
+
+    /// Whether this peer's order of preference wins when it has `role`.
+    pub(super) fn is_preferred(&self, role: Role) -> bool {
+        (self.preference == Preference::Initiator) == (role == Role::Initiator)
+    }
+}
+
+/// First algorithm of the preferred list that the other list has and is
+/// strong enough.
+fn select_strong<T: Copy + PartialEq>(
+    local: &[T],
+    peer: &[T],
+    local_first: bool,
+    is_strong: impl Fn(&T) -> bool,
+) -> Option<T> {
+    let (preferred, other) = match local_first {
+        true => (local, peer),
+        false => (peer, local),
+    };
+
+    preferred
+        .iter()
+        .find(|algorithm| other.contains(algorithm) && is_strong(algorithm))
+        .copied()
+}
+
+pub(super) fn select<T: Copy + PartialEq>(
+    local: &[T],
+    peer: &[T],
+    local_first: bool,
+) -> Result<T, Error> {
+    let (preferred, other) = match local_first {
+        true => (local, peer),
+        false => (peer, local),
+    };
+
+    preferred
+        .iter()
+        .find(|algorithm| other.contains(algorithm))
+        .copied()
+        .ok_or(ProtocolError::IncompatibleConfig.into())
+}
+
+impl Capabilities {
+    /// Compact wire encoding: the [`Preference`] byte, then for curves, key
+    /// derivation functions, hash functions and cipher suites in turn, a
+    /// count byte followed by the NIDs or suite identifiers as big-endian
+    /// `u16`s.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(
+            5 + 2 * (self.curve.len() + self.hkdf.len() + self.hash.len() + self.suites.len()),
+        );
+
+        buf.push(self.preference as u8);
+
+        encode_list(&mut buf, self.curve.iter().map(|a| *a as u16));
+        encode_list(&mut buf, self.hkdf.iter().map(|a| *a as u16));
+        encode_list(&mut buf, self.hash.iter().map(|a| *a as u16));
+        encode_list(&mut buf, self.suites.iter().map(|s| u16::from(*s)));
+
+        buf
+    }
+
+    /// Decodes capabilities from their [`encode`]d form.
+    ///
+    /// Algorithms and suites unknown to this crate are skipped, so that newer
+    /// peers can advertise them. Returns [`ProtocolError::MalformedMessage`] on
+    /// truncated or trailing input, or an unknown [`Preference`].
+    ///
+    /// [`encode`]: Capabilities::encode
+    pub fn decode(bytes: &[u8]) -> Result<Self, Error> {
+        let (preference, mut bytes) = match bytes.split_first() {
+            Some((0, bytes)) => (Preference::Initiator, bytes),
+            Some((1, bytes)) => (Preference::Responder, bytes),
+            _ => return Err(ProtocolError::MalformedMessage.into()),
+        };
+
+        let capabilities = Self {
+            preference,
+            curve: decode_list(&mut bytes, |nid| nid.try_into().ok())?,
+            hkdf: decode_list(&mut bytes, |nid| nid.try_into().ok())?,
+            hash: decode_list(&mut bytes, |nid| nid.try_into().ok())?,
+            suites: decode_list(&mut bytes, |id| CipherSuite::try_from(id as u16).ok())?,
+        };
+
+        match bytes.is_empty() {
+            true => Ok(capabilities),
+            false => Err(ProtocolError::MalformedMessage.into()),
+        }
+    }
+}
+
+fn encode_list(buf: &mut Vec<u8>, nids: impl ExactSizeIterator<Item = u16>) {
+    // Configs hold no duplicates, so every list is shorter than 256.
+    buf.push(nids.len() as u8);
+    for nid in nids {
+        buf.extend_from_slice(&nid.to_be_bytes());
+    }
+}
+
+fn decode_list<T>(bytes: &mut &[u8], known: fn(i32) -> Option<T>) -> Result<Vec<T>, Error> {
+    let (&len, rest) = bytes.split_first().ok_or(ProtocolError::MalformedMessage)?;
+    let (nids, rest) = rest
+        .split_at_checked(2 * len as usize)
+        .ok_or(ProtocolError::MalformedMessage)?;
+    *bytes = rest;
+
+    Ok(nids
+        .chunks_exact(2)
+        .filter_map(|nid| known(u16::from_be_bytes([nid[0], nid[1]]) as i32))
+        .collect())
+}
+
+#[test]
+#[cfg(all(test, feature = "aws_lc_rs"))]
+fn test() -> Result<(), Error> {
+    use crate::{crypto::aws_lc_rs, sync::Arc};
+
+    let builder = || Config::builder_with_provider(Arc::new(aws_lc_rs::default_provider()));
+
+    let alice = builder()
+        .with_curve(alloc::vec![
+            key::Algorithm::X25519,
+            key::Algorithm::EcdhP256
+        ])?
+        .with_hkdf(alloc::vec![
+            hkdf::Algorithm::Sha256,
+            hkdf::Algorithm::Sha512
+        ])?
+        .with_hash(alloc::vec![
+            hash::Algorithm::Sha256,
+            hash::Algorithm::Sha512
+        ])?
+        .build()?;
+    let bob = builder()
+        .with_curve(alloc::vec![
+            key::Algorithm::EcdhP256,
+            key::Algorithm::X25519
+        ])?
+        .with_hkdf(alloc::vec![
+            hkdf::Algorithm::Sha512,
+            hkdf::Algorithm::Sha256
+        ])?
+        .with_hash(alloc::vec![hash::Algorithm::Sha512])?
+        .build()?;
+
+    let encoded = alice.capabilities().encode();
+    assert_eq!(hex::encode(&encoded), "0102040a02ca0205d805da0202a002a200");
+    assert_eq!(Capabilities::decode(&encoded)?, alice.capabilities());
+
+    // Both peers select the responder's preference.
+    let selection = Selection {
+        curve: key::Algorithm::EcdhP256,
+        hkdf: hkdf::Algorithm::Sha512,
+        hash: hash::Algorithm::Sha512,
+        suite: None,
+    };
+    assert_eq!(
+        alice.negotiate(&bob.capabilities(), Role::Initiator)?,
+        selection
+    );
+    assert_eq!(
+        bob.negotiate(&Capabilities::decode(&encoded)?, Role::Responder)?,
+        selection
+    );
+
+    // Unknown algorithms are skipped; malformed input is rejected.
+    assert_eq!(
+        Capabilities::decode(&hex::decode("0102ffff02ca000000").unwrap())?.curve,
+        alloc::vec![key::Algorithm::EcdhP256]
+    );
+    assert_eq!(
+        Capabilities::decode(&encoded[..encoded.len() - 1]),
+        Err(ProtocolError::MalformedMessage.into())
+    );
+    assert_eq!(
+        Capabilities::decode(&[&[2], &encoded[1..]].concat()),
+        Err(ProtocolError::MalformedMessage.into())
+    );
+
+    // The preference rule is on the wire; peers with different rules would
+    // select differently, so they do not negotiate.
+    let mut initiator_first = alice.capabilities();
+    initiator_first.preference = Preference::Initiator;
+    assert_eq!(
+        Capabilities::decode(&initiator_first.encode())?,
+        initiator_first
+    );
+    assert_eq!(
+        bob.negotiate(&initiator_first, Role::Responder),
+        Err(ProtocolError::IncompatibleConfig.into())
+    );
+
+    // SHA-256 is too weak for P-521, so P-256 is selected over it.
+    let dave = builder()
+        .with_curve(alloc::vec![
+            key::Algorithm::EcdhP521,
+            key::Algorithm::EcdhP256
+        ])?
+        .with_hkdf(alloc::vec![
+            hkdf::Algorithm::Sha256,
+            hkdf::Algorithm::Sha512
+        ])?
+        .with_hash(alloc::vec![
+            hash::Algorithm::Sha256,
+            hash::Algorithm::Sha512
+        ])?
+        .build()?;
+    assert_eq!(
+        dave.negotiate(&dave.capabilities(), Role::Responder)?,
+        Selection {
+            curve: key::Algorithm::EcdhP521,
+            hkdf: hkdf::Algorithm::Sha512,
+            hash: hash::Algorithm::Sha512,
+            suite: None,
+        }
+    );
+    let mut weak = dave.capabilities();
+    weak.hash.retain(|a| *a == hash::Algorithm::Sha256);
+    assert_eq!(
+        dave.negotiate(&weak, Role::Responder)?,
+        Selection {
+            curve: key::Algorithm::EcdhP256,
+            hkdf: hkdf::Algorithm::Sha256,
+            hash: hash::Algorithm::Sha256,
+            suite: None,
+        }
+    );
+    weak.curve.retain(|a| *a == key::Algorithm::EcdhP521);
+    assert_eq!(
+        dave.negotiate(&weak, Role::Responder),
+        Err(ProtocolError::IncompatibleConfig.into())
+    );
+
+    let carol = builder()
+        .with_curve(alloc::vec![key::Algorithm::EcdhP384])?
+        .with_hkdf(alloc::vec![hkdf::Algorithm::Sha384])?
+        .with_hash(alloc::vec![hash::Algorithm::Sha384])?
+        .build()?;
+    assert_eq!(
+        carol.negotiate(&alice.capabilities(), Role::Responder),
+        Err(ProtocolError::IncompatibleConfig.into())
+    );
+
+    Ok(())
+}
@@ -0,0 +1,235 @@
+use super::{Config, Role, negotiation::select};
+use crate::{
+    Error,
+    crypto::{aead, hash, hkdf, key},
+};
+
+/// Named combinations of a curve, a hash function (also used for HKDF) and
+/// an AEAD, in the manner of TLS cipher suites.
+///
+/// The discriminants are stable 16-bit wire identifiers; the algorithms
+/// themselves keep the NIDs of their [`e2ee_provider`] enums. Suite
+/// identifiers cannot be derived from those NIDs: a suite names three
+/// algorithms whose NIDs take 10 to 11 bits each, which do not fit in 16 bits,
+/// and a hash of them could collide. They are assigned here in order instead,
+/// starting at 1 so that 0 can stand for no suite.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CipherSuite {
+    /// `X25519_SHA256_CHACHA20POLY1305`
+    X25519Sha256ChaCha20Poly1305 = 0x0001,
+    /// `X25519_SHA256_AES128GCM`
+    X25519Sha256Aes128Gcm = 0x0002,
+    /// `P256_SHA256_AES128GCM`
+    P256Sha256Aes128Gcm = 0x0003,
+    /// `P384_SHA384_AES256GCM`
+    P384Sha384Aes256Gcm = 0x0004,
+    /// `P521_SHA512_AES256GCM`
+    P521Sha512Aes256Gcm = 0x0005,
+}
+
+impl TryFrom<u16> for CipherSuite {
+    type Error = Error;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        match value {
+            0x0001 => Ok(Self::X25519Sha256ChaCha20Poly1305),
+            0x0002 => Ok(Self::X25519Sha256Aes128Gcm),
+            0x0003 => Ok(Self::P256Sha256Aes128Gcm),
+            0x0004 => Ok(Self::P384Sha384Aes256Gcm),
+            0x0005 => Ok(Self::P521Sha512Aes256Gcm),
+            _ => Err(Error::UnsupportedAlgorithm),
+        }
+    }
+}
+
+impl From<CipherSuite> for u16 {
+    fn from(suite: CipherSuite) -> Self {
+        suite as u16
+    }
+}
+
+impl CipherSuite {
+    /// All cipher suites, in order of preference.
+    pub const ALL: &'static [Self] = &[
+        Self::X25519Sha256ChaCha20Poly1305,
+        Self::X25519Sha256Aes128Gcm,
+        Self::P256Sha256Aes128Gcm,
+        Self::P384Sha384Aes256Gcm,
+        Self::P521Sha512Aes256Gcm,
+    ];
+
+    /// Key agreement algorithm of the suite.
+    pub fn curve(&self) -> key::Algorithm {
+        match self {
+            Self::X25519Sha256ChaCha20Poly1305 | Self::X25519Sha256Aes128Gcm => {
+                key::Algorithm::X25519
+            }
+            Self::P256Sha256Aes128Gcm => key::Algorithm::EcdhP256,
+            Self::P384Sha384Aes256Gcm => key::Algorithm::EcdhP384,
+            Self::P521Sha512Aes256Gcm => key::Algorithm::EcdhP521,
+        }
+    }
+
+    /// Key derivation function of the suite.
+    pub fn hkdf(&self) -> hkdf::Algorithm {
+        match self {
+            Self::X25519Sha256ChaCha20Poly1305
+            | Self::X25519Sha256Aes128Gcm
+            | Self::P256Sha256Aes128Gcm => hkdf::Algorithm::Sha256,
+            Self::P384Sha384Aes256Gcm => hkdf::Algorithm::Sha384,
+            Self::P521Sha512Aes256Gcm => hkdf::Algorithm::Sha512,
+        }
+    }
+
+    /// Hash function of the suite.
+    pub fn hash(&self) -> hash::Algorithm {
+        match self {
+            Self::X25519Sha256ChaCha20Poly1305
+            | Self::X25519Sha256Aes128Gcm
+            | Self::P256Sha256Aes128Gcm => hash::Algorithm::Sha256,
+            Self::P384Sha384Aes256Gcm => hash::Algorithm::Sha384,
+            Self::P521Sha512Aes256Gcm => hash::Algorithm::Sha512,
+        }
+    }
+
+    /// Authenticated encryption algorithm of the suite.
+    pub fn aead(&self) -> aead::Algorithm {
+        match self {
+            Self::X25519Sha256ChaCha20Poly1305 => aead::Algorithm::ChaCha20Poly1305,
+            Self::X25519Sha256Aes128Gcm | Self::P256Sha256Aes128Gcm => aead::Algorithm::Aes128Gcm,
+            Self::P384Sha384Aes256Gcm | Self::P521Sha512Aes256Gcm => aead::Algorithm::Aes256Gcm,
+        }
+    }
+}
+
+impl Config {
+    /// Selects the cipher suite for a handshake with a peer offering
+    /// `peer`, `role` being the role of this peer, following the same
+    /// [`Preference`] as [`negotiate`].
+    ///
+    /// Returns [`ProtocolError::IncompatibleConfig`] if there is no suite in
+    /// common, which is always the case if this configuration was not built
+    /// from suites.
+    ///
+    /// [`Preference`]: super::Preference
+    /// [`negotiate`]: Config::negotiate
+    /// [`ProtocolError::IncompatibleConfig`]: crate::ProtocolError::IncompatibleConfig
+    pub fn negotiate_suite(&self, peer: &[CipherSuite], role: Role) -> Result<CipherSuite, Error> {
+        select(&self.suites, peer, self.is_preferred(role))
+    }
+}
+
+#[test]
+#[cfg(all(test, feature = "aws_lc_rs"))]
+fn test() -> Result<(), Error> {
+    use super::Capabilities;
+    use crate::{ConfigError, ProtocolError, crypto::aws_lc_rs, sync::Arc};
+
+    let builder = || Config::builder_with_provider(Arc::new(aws_lc_rs::default_provider()));
+
+    for suite in CipherSuite::ALL {
+        assert_eq!(CipherSuite::try_from(u16::from(*suite))?, *suite);
+    }
+    assert!(CipherSuite::try_from(0).is_err());
+
+    let alice = builder()
+        .with_suites(alloc::vec![
+            CipherSuite::X25519Sha256ChaCha20Poly1305,
+            CipherSuite::X25519Sha256Aes128Gcm,
+            CipherSuite::P384Sha384Aes256Gcm,
+        ])?
+        .build()?;
+    assert_eq!(
+        alice.curve,
+        [key::Algorithm::X25519, key::Algorithm::EcdhP384]
+    );
+    assert_eq!(
+        alice.hkdf,
+        [hkdf::Algorithm::Sha256, hkdf::Algorithm::Sha384]
+    );
+
+    let bob = builder()
+        .with_suites(alloc::vec![
+            CipherSuite::P384Sha384Aes256Gcm,
+            CipherSuite::X25519Sha256Aes128Gcm,
+        ])?
+        .build()?;
+    assert_eq!(
+        alice.negotiate_suite(&bob.suites, Role::Initiator)?,
+        CipherSuite::P384Sha384Aes256Gcm
+    );
+    assert_eq!(
+        bob.negotiate_suite(&[CipherSuite::P521Sha512Aes256Gcm], Role::Responder),
+        Err(ProtocolError::IncompatibleConfig.into())
+    );
+
+    assert_eq!(
+        builder()
+            .with_suites(alloc::vec![
+                CipherSuite::P256Sha256Aes128Gcm,
+                CipherSuite::P256Sha256Aes128Gcm
+            ])
+            .err(),
+        Some(ConfigError::DuplicateAlgorithm.into())
+    );
+
+    // The algorithms are those of the common suite, not the strongest of each
+    // list: the responder's X25519 and SHA-256 are not combined with P-384.
+    let responder = builder()
+        .with_suites(alloc::vec![
+            CipherSuite::X25519Sha256ChaCha20Poly1305,
+            CipherSuite::P384Sha384Aes256Gcm,
+        ])?
+        .build()?;
+    let initiator = builder()
+        .with_suites(alloc::vec![
+            CipherSuite::P384Sha384Aes256Gcm,
+            CipherSuite::P256Sha256Aes128Gcm,
+        ])?
+        .build()?;
+    let offer = Capabilities::decode(&initiator.capabilities().encode())?;
+    assert_eq!(offer.suites, initiator.suites);
+
+    let selection = responder.negotiate(&offer, Role::Responder)?;
+    assert_eq!(selection.suite, Some(CipherSuite::P384Sha384Aes256Gcm));
+    assert_eq!(selection.curve, key::Algorithm::EcdhP384);
+    assert_eq!(selection.hkdf, hkdf::Algorithm::Sha384);
+    assert_eq!(selection.hash, hash::Algorithm::Sha384);
+    assert_eq!(
+        initiator.negotiate(&responder.capabilities(), Role::Initiator)?,
+        selection
+    );
+
+    // A peer without suites cannot negotiate with one that has them.
+    let plain = builder()
+        .with_curve(alloc::vec![key::Algorithm::EcdhP384])?
+        .with_hkdf(alloc::vec![hkdf::Algorithm::Sha384])?
+        .with_hash(alloc::vec![hash::Algorithm::Sha384])?
+        .build()?;
+    assert_eq!(
+        plain.negotiate(&responder.capabilities(), Role::Initiator),
+        Err(ProtocolError::IncompatibleConfig.into())
+    );
+    assert_eq!(
+        responder.negotiate(&plain.capabilities(), Role::Responder),
+        Err(ProtocolError::IncompatibleConfig.into())
+    );
+
+    // Stripping a suite from the offer changes the transcript, even if the
+    // same suite is selected.
+    let mut stripped = initiator.capabilities();
+    stripped
+        .suites
+        .retain(|s| *s != CipherSuite::P256Sha256Aes128Gcm);
+    assert_ne!(
+        responder
+            .transcript(&initiator.capabilities().encode(), Role::Responder)?
+            .as_ref(),
+        responder
+            .transcript(&stripped.encode(), Role::Responder)?
+            .as_ref()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,219 @@
+use super::{Capabilities, Config, Role, Selection, TRANSCRIPT_CONTEXT, TRANSCRIPT_HASH};
+use crate::{
+    Error,
+    crypto::{hash, hkdf},
+};
+
+/// Hash of the negotiation: the capabilities offered by both peers, with
+/// their [`Preference`] rules and cipher suites, and the algorithms and suite
+/// selected from them, hashed with [`TRANSCRIPT_HASH`].
+///
+/// The peer's offer is hashed as received, including the algorithms this
+/// crate does not know, so that peers of different versions agree.
+///
+/// The transcript is signed with [`Peer::sign_transcript`] and is the info of
+/// [`derive_key`]. If an attacker tampers with either list, the peers compute
+/// different transcripts, so the signature fails to verify and the derived
+/// keys differ.
+///
+/// [`Preference`]: super::Preference
+/// [`Peer::sign_transcript`]: super::Peer::sign_transcript
+/// [`derive_key`]: Transcript::derive_key
+pub struct Transcript {
+    digest: hash::Digest,
+    selection: Selection,
+    hkdf: &'static dyn hkdf::Hkdf,
+}
+
+impl Config {
+    /// Negotiates the algorithms with a peer whose [`Capabilities::encode`]d
+    /// offer is `peer`, `role` being the role of this peer, and hashes the
+    /// negotiation.
+    ///
+    /// Returns [`Error::UnsupportedAlgorithm`] if the provider lacks
+    /// [`TRANSCRIPT_HASH`], and the errors of [`Capabilities::decode`].
+    pub fn transcript(&self, peer: &[u8], role: Role) -> Result<Transcript, Error> {
+        let selection = self.negotiate(&Capabilities::decode(peer)?, role)?;
+        let local = self.capabilities().encode();
+        let (initiator, responder) = match role {
+            Role::Initiator => (&local[..], peer),
+            Role::Responder => (peer, &local[..]),
+        };
+
+        // Suite identifiers start at 1, so 0 stands for no suite.
+        let mut nids = [0; 8];
+        for (bytes, nid) in nids.chunks_exact_mut(2).zip([
+            selection.curve as u16,
+            selection.hkdf as u16,
+            selection.hash as u16,
+            selection.suite.map_or(0, u16::from),
+        ]) {
+            bytes.copy_from_slice(&nid.to_be_bytes());
+        }
+
+        let digest = self
+            .provider
+            .hash
+            .get(TRANSCRIPT_HASH)
+            .ok_or(Error::UnsupportedAlgorithm)?
+            .hash_parts(&[TRANSCRIPT_CONTEXT, initiator, responder, &nids]);
+
+        Ok(Transcript {
+            digest,
+            selection,
+            hkdf: self
+                .provider
+                .hkdf
+                .get(selection.hkdf)
+                .ok_or(Error::UnsupportedAlgorithm)?,
+        })
+    }
+}
+
+impl Transcript {
+    /// Algorithms selected in the negotiation.
+    pub fn selection(&self) -> Selection {
+        self.selection
+    }
+
+    /// Derives `len` bytes of key material from the key agreement `secret`
+    /// with the selected key derivation function, bound to the transcript.
+    ///
+    /// Returns [`Error::Unspecified`] if `len` is larger than
+    /// [`hkdf::MAX_OKM_LEN`].
+    pub fn derive_key(&self, secret: &[u8], len: usize) -> Result<hkdf::Okm, Error> {
+        let prk = self.hkdf.extract_prk(&[], secret);
+
+        Ok(self.hkdf.expand_prk(
+            prk.as_ref(),
+            &[TRANSCRIPT_CONTEXT, self.digest.as_ref()],
+            len,
+        )?)
+    }
+}
+
+impl AsRef<[u8]> for Transcript {
+    fn as_ref(&self) -> &[u8] {
+        self.digest.as_ref()
+    }
+}
+
+#[test]
+#[cfg(all(test, feature = "aws_lc_rs"))]
+fn test() -> Result<(), Error> {
+    use super::{Peer, verify_transcript};
+    use crate::{
+        ProtocolError,
+        crypto::{aws_lc_rs, key},
+        sync::Arc,
+    };
+
+    let provider = Arc::new(aws_lc_rs::default_provider());
+    let config = || {
+        Config::builder_with_provider(provider.clone())
+            .with_curve(alloc::vec![
+                key::Algorithm::X25519,
+                key::Algorithm::EcdhP256
+            ])?
+            .with_hkdf(alloc::vec![
+                hkdf::Algorithm::Sha512,
+                hkdf::Algorithm::Sha256
+            ])?
+            .with_hash(alloc::vec![
+                hash::Algorithm::Sha512,
+                hash::Algorithm::Sha256
+            ])?
+            .build()
+    };
+    let (alice, bob) = (config()?, config()?);
+
+    let bob_peer = Peer {
+        identitiy_key: provider
+            .key
+            .load_private_key(key::Algorithm::X25519, &[2; 32])?,
+        key_storage: None,
+    };
+    let bob_identity = bob_peer.identitiy_key.compute_public_key()?;
+    // Stands in for the output of the key agreements.
+    let secret = [7; 32];
+
+    // Without tampering, both peers agree.
+    let alice_transcript = alice.transcript(&bob.capabilities().encode(), Role::Initiator)?;
+    let bob_transcript = bob.transcript(&alice.capabilities().encode(), Role::Responder)?;
+    let signature = bob_peer.sign_transcript(&bob_transcript)?;
+
+    verify_transcript(bob_identity.as_ref(), &alice_transcript, &signature)?;
+    assert_eq!(alice_transcript.as_ref(), bob_transcript.as_ref());
+    assert_eq!(
+        alice_transcript.derive_key(&secret, 32)?.as_ref(),
+        bob_transcript.derive_key(&secret, 32)?.as_ref()
+    );
+
+    // A man in the middle strips the strong options from Alice's offer, so
+    // that Bob selects SHA-256.
+    let mut stripped = alice.capabilities();
+    stripped.hkdf.retain(|a| *a == hkdf::Algorithm::Sha256);
+    stripped.hash.retain(|a| *a == hash::Algorithm::Sha256);
+
+    let bob_transcript = bob.transcript(&stripped.encode(), Role::Responder)?;
+    let signature = bob_peer.sign_transcript(&bob_transcript)?;
+
+    assert_eq!(bob_transcript.selection().hash, hash::Algorithm::Sha256);
+    // The transcript itself is not weakened.
+    assert_eq!(bob_transcript.as_ref().len(), TRANSCRIPT_HASH.output_len());
+    assert_eq!(
+        verify_transcript(bob_identity.as_ref(), &alice_transcript, &signature),
+        Err(ProtocolError::BadSignature.into())
+    );
+
+    // The man in the middle strips X25519 from Bob's answer.
+    let mut stripped = bob.capabilities();
+    stripped.curve.retain(|a| *a != key::Algorithm::X25519);
+
+    let alice_transcript = alice.transcript(&stripped.encode(), Role::Initiator)?;
+    let bob_transcript = bob.transcript(&alice.capabilities().encode(), Role::Responder)?;
+    let signature = bob_peer.sign_transcript(&bob_transcript)?;
+
+    assert_eq!(alice_transcript.selection().curve, key::Algorithm::EcdhP256);
+    assert!(verify_transcript(bob_identity.as_ref(), &alice_transcript, &signature).is_err());
+    assert_ne!(
+        alice_transcript.derive_key(&secret, 32)?.as_ref(),
+        bob_transcript.derive_key(&secret, 32)?.as_ref()
+    );
+
+    // A newer Alice offers a curve unknown to Bob, who hashes it all the same.
+    let known = alice.capabilities().encode();
+    let mut offer = known.clone();
+    offer[1] += 1;
+    offer.splice(2..2, [0xff, 0xff]);
+
+    let bob_transcript = bob.transcript(&offer, Role::Responder)?;
+    let selection = bob_transcript.selection();
+    let mut nids = alloc::vec::Vec::new();
+    for nid in [
+        selection.curve as u16,
+        selection.hkdf as u16,
+        selection.hash as u16,
+        0,
+    ] {
+        nids.extend_from_slice(&nid.to_be_bytes());
+    }
+    let newer_alice_transcript = provider
+        .hash
+        .get(TRANSCRIPT_HASH)
+        .ok_or(Error::UnsupportedAlgorithm)?
+        .hash_parts(&[
+            TRANSCRIPT_CONTEXT,
+            &offer,
+            &bob.capabilities().encode(),
+            &nids,
+        ]);
+
+    assert_eq!(bob_transcript.as_ref(), newer_alice_transcript.as_ref());
+    assert_ne!(
+        bob_transcript.as_ref(),
+        bob.transcript(&known, Role::Responder)?.as_ref()
+    );
+
+    Ok(())
+}